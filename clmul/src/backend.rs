@@ -41,11 +41,40 @@ impl soft::Clmul {
     }
 }
 
+/// A carryless multiplication backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Portable 32-bit software backend.
+    Soft32,
+    /// Portable 64-bit software backend.
+    Soft64,
+    /// x86 `CLMUL` intrinsics.
+    Clmul,
+    /// ARMv8 `PMULL` intrinsics.
+    Pmull,
+}
+
+/// The software backend selected for the target.
+const SOFT_BACKEND: Backend = if cfg!(target_pointer_width = "64") {
+    Backend::Soft64
+} else {
+    Backend::Soft32
+};
+
 cfg_if! {
     if #[cfg(all(target_arch = "aarch64", clmul_armv8, not(clmul_force_soft)))] {
         mod autodetect;
         mod pmull;
         pub use crate::backend::autodetect::Clmul;
+
+        /// Returns the backend which is used for carryless multiplication.
+        pub fn active_backend() -> Backend {
+            if autodetect::has_intrinsics() {
+                Backend::Pmull
+            } else {
+                SOFT_BACKEND
+            }
+        }
     } else if #[cfg(
         all(
             any(target_arch = "x86_64", target_arch = "x86"),
@@ -55,7 +84,21 @@ cfg_if! {
         mod autodetect;
         mod clmul;
         pub use crate::backend::autodetect::Clmul;
+
+        /// Returns the backend which is used for carryless multiplication.
+        pub fn active_backend() -> Backend {
+            if autodetect::has_intrinsics() {
+                Backend::Clmul
+            } else {
+                SOFT_BACKEND
+            }
+        }
     } else {
         pub use crate::backend::soft::Clmul;
+
+        /// Returns the backend which is used for carryless multiplication.
+        pub fn active_backend() -> Backend {
+            SOFT_BACKEND
+        }
     }
 }
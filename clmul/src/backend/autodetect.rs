@@ -17,6 +17,11 @@ cpufeatures::new!(mul_intrinsics, "aes"); // `aes` implies PMULL
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 cpufeatures::new!(mul_intrinsics, "pclmulqdq");
 
+/// Returns whether the intrinsics backend is used.
+pub(crate) fn has_intrinsics() -> bool {
+    !cfg!(clmul_force_soft) && mul_intrinsics::get()
+}
+
 /// Carryless multiplication
 #[derive(Clone, Copy)]
 pub struct Clmul {
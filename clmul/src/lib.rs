@@ -18,6 +18,7 @@
 //! carry-spilling.
 //!
 //! Usage of the soft-backend can be forced by setting the `clmul_force_soft` RUSTFLAG.
+//! The backend which is used at runtime can be queried with [`active_backend`].
 //!
 //! ```text
 //! $ RUSTFLAGS="--cfg clmul_force_soft" cargo bench
@@ -55,7 +56,7 @@
 #![cfg_attr(all(clmul_armv8, target_arch = "aarch64"), feature(stdsimd))]
 
 mod backend;
-pub use backend::Clmul;
+pub use backend::{active_backend, Backend, Clmul};
 
 #[cfg(test)]
 #[path = ""]
@@ -99,6 +100,18 @@ mod tests {
         assert_eq!(r64_1, d);
     }
 
+    #[test]
+    #[cfg(all(target_arch = "x86_64", not(clmul_force_soft)))]
+    fn active_backend_test() {
+        use super::{active_backend, Backend};
+
+        if std::is_x86_feature_detected!("pclmulqdq") {
+            assert_eq!(active_backend(), Backend::Clmul);
+        } else {
+            assert_eq!(active_backend(), Backend::Soft64);
+        }
+    }
+
     #[test]
     // test soft32 backend
     fn clmul_xor_eq_soft32() {
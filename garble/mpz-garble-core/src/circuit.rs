@@ -3,7 +3,7 @@ use std::ops::Index;
 use mpz_core::Block;
 use serde::{Deserialize, Serialize};
 
-use crate::EncodingCommitment;
+use crate::{Decoding, EncodingCommitment};
 
 /// Encrypted gate truth table
 ///
@@ -35,11 +35,154 @@ impl Index<usize> for EncryptedGate {
     }
 }
 
+/// An error that can occur when deserializing a [`GarbledCircuit`].
+#[derive(Debug, thiserror::Error)]
+#[error("invalid garbled circuit: {0}")]
+pub struct GarbledCircuitError(#[from] bincode::Error);
+
 /// A garbled circuit
+///
+/// Together with the active encodings of its inputs, a garbled circuit contains everything
+/// an evaluator needs to evaluate the circuit offline, see [`to_bytes`](Self::to_bytes).
+///
+/// It does not contain commitments to the input encodings. The evaluator receives its
+/// active input encodings from the generator, directly or via OT, so a commitment made by the
+/// same generator would not authenticate them. The outputs are authenticated by the
+/// output commitments instead.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GarbledCircuit {
     /// Encrypted gates of the circuit
     pub gates: Vec<EncryptedGate>,
     /// Encoding commitments of the circuit outputs
     pub commitments: Option<Vec<EncodingCommitment>>,
+    /// Decodings of the circuit outputs
+    decodings: Option<Vec<Decoding>>,
+}
+
+impl GarbledCircuit {
+    /// Creates a new garbled circuit without output decodings.
+    ///
+    /// # Arguments
+    ///
+    /// * `gates` - The encrypted gates of the circuit.
+    /// * `commitments` - The encoding commitments of the circuit outputs.
+    pub fn new(gates: Vec<EncryptedGate>, commitments: Option<Vec<EncodingCommitment>>) -> Self {
+        Self {
+            gates,
+            commitments,
+            decodings: None,
+        }
+    }
+
+    /// Sets the decodings of the circuit outputs, allowing the evaluator to decode them.
+    pub fn with_decodings(mut self, decodings: Vec<Decoding>) -> Self {
+        self.decodings = Some(decodings);
+        self
+    }
+
+    /// Returns the decodings of the circuit outputs, if any.
+    pub fn decodings(&self) -> Option<&[Decoding]> {
+        self.decodings.as_deref()
+    }
+
+    /// Serializes the garbled circuit, eg. to garble a circuit offline and evaluate it later.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("garbled circuit should be serializable")
+    }
+
+    /// Deserializes a garbled circuit from bytes produced by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, GarbledCircuitError> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aes::{
+        cipher::{BlockEncrypt, KeyInit},
+        Aes128,
+    };
+    use mpz_circuits::circuits::AES128;
+
+    use super::*;
+    use crate::{encoding_state, ChaChaEncoder, EncodedValue, Encoder, Evaluator, Generator};
+
+    #[test]
+    fn test_garbled_circuit_bytes() {
+        let encoder = ChaChaEncoder::new([0; 32]);
+
+        let key = [69u8; 16];
+        let msg = [42u8; 16];
+
+        let expected: [u8; 16] = {
+            let cipher = Aes128::new_from_slice(&key).unwrap();
+            let mut out = msg.into();
+            cipher.encrypt_block(&mut out);
+            out.into()
+        };
+
+        let full_inputs: Vec<EncodedValue<encoding_state::Full>> = AES128
+            .inputs()
+            .iter()
+            .map(|input| encoder.encode_by_type(0, &input.value_type()))
+            .collect();
+
+        let active_inputs: Vec<EncodedValue<encoding_state::Active>> = vec![
+            full_inputs[0].clone().select(key).unwrap(),
+            full_inputs[1].clone().select(msg).unwrap(),
+        ];
+
+        // Garble offline
+        let mut gen = Generator::new(AES128.clone(), encoder.delta(), &full_inputs).unwrap();
+        let gates: Vec<_> = gen.by_ref().collect();
+        let full_outputs = gen.outputs().unwrap();
+
+        let garbled = GarbledCircuit::new(
+            gates,
+            Some(full_outputs.iter().map(|output| output.commit()).collect()),
+        )
+        .with_decodings(
+            full_outputs
+                .iter()
+                .map(|output| output.decoding())
+                .collect(),
+        );
+
+        let garbled = GarbledCircuit::from_bytes(&garbled.to_bytes()).unwrap();
+
+        // Evaluate online
+        let mut ev = Evaluator::new(AES128.clone(), &active_inputs).unwrap();
+        ev.evaluate(garbled.gates.iter());
+        let active_outputs = ev.outputs().unwrap();
+
+        for (commitment, output) in garbled
+            .commitments
+            .as_ref()
+            .unwrap()
+            .iter()
+            .zip(&active_outputs)
+        {
+            commitment.verify(output).unwrap();
+        }
+
+        let actual: [u8; 16] = active_outputs[0]
+            .decode(&garbled.decodings().unwrap()[0])
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        // Evaluate directly
+        let mut gen = Generator::new(AES128.clone(), encoder.delta(), &full_inputs).unwrap();
+        let mut ev = Evaluator::new(AES128.clone(), &active_inputs).unwrap();
+        let gates: Vec<_> = gen.by_ref().collect();
+        ev.evaluate(gates.iter());
+        let direct: [u8; 16] = ev.outputs().unwrap()[0]
+            .decode(&gen.outputs().unwrap()[0].decoding())
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(actual, direct);
+    }
 }
@@ -0,0 +1,284 @@
+use serde::{Deserialize, Serialize};
+
+use mpz_core::{
+    hash::{DomainSeparatedHash, Hash},
+    impl_domain_separated_hash,
+};
+
+use crate::encoding::{state, EncodedValue, EncodingCommitment, ValueError};
+
+#[derive(Serialize)]
+struct LeafCommit<'a>(&'a EncodingCommitment);
+
+impl_domain_separated_hash!(LeafCommit<'_>, "ENCODING_COMMITMENT_LEAF");
+
+#[derive(Serialize)]
+struct NodeCommit(Hash, Hash);
+
+impl_domain_separated_hash!(NodeCommit, "ENCODING_COMMITMENT_NODE");
+
+#[derive(Serialize)]
+struct RootCommit(u64, Hash);
+
+impl_domain_separated_hash!(RootCommit, "ENCODING_COMMITMENT_ROOT");
+
+/// A commitment to the encodings of a batch of values.
+///
+/// The commitment is the root of a Merkle tree over the [`EncodingCommitment`] of each value,
+/// so the evaluator only needs to receive the root up front. Each value can later be opened
+/// individually using its [`EncodingOpening`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchEncodingCommitment {
+    root: Hash,
+    openings: Vec<EncodingOpening>,
+}
+
+impl BatchEncodingCommitment {
+    /// Returns the root of the commitment.
+    pub fn root(&self) -> Hash {
+        self.root
+    }
+
+    /// Returns the openings for each value, in the order the values were committed.
+    pub fn openings(&self) -> &[EncodingOpening] {
+        &self.openings
+    }
+
+    /// Returns the root and the openings for each value.
+    pub fn into_parts(self) -> (Hash, Vec<EncodingOpening>) {
+        (self.root, self.openings)
+    }
+}
+
+/// An opening of a single value's commitment against a [`BatchEncodingCommitment`] root.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncodingOpening {
+    index: usize,
+    count: usize,
+    commitment: EncodingCommitment,
+    path: Vec<Hash>,
+}
+
+impl EncodingOpening {
+    /// Returns the index of the value in the batch.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the commitment to the encoding of the value.
+    pub fn commitment(&self) -> &EncodingCommitment {
+        &self.commitment
+    }
+
+    /// Verifies the opening against the root of the batch, and that the given active
+    /// encoding matches the commitment.
+    ///
+    /// If this function returns an error the generator may be malicious.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root of the batch commitment.
+    /// * `active` - The active encoding of the value.
+    pub fn verify(
+        &self,
+        root: &Hash,
+        active: &EncodedValue<state::Active>,
+    ) -> Result<(), ValueError> {
+        if self.index >= self.count {
+            return Err(ValueError::InvalidCommitment);
+        }
+
+        let mut hash = LeafCommit(&self.commitment).domain_separated_hash();
+        let mut path = self.path.iter();
+        let mut index = self.index;
+        let mut len = self.count;
+        while len > 1 {
+            // The last node of a level with an odd length has no sibling.
+            if index ^ 1 < len {
+                let sibling = *path.next().ok_or(ValueError::InvalidCommitment)?;
+                hash = if index & 1 == 0 {
+                    NodeCommit(hash, sibling)
+                } else {
+                    NodeCommit(sibling, hash)
+                }
+                .domain_separated_hash();
+            }
+
+            index >>= 1;
+            len = len.div_ceil(2);
+        }
+
+        if path.next().is_some()
+            || &RootCommit(self.count as u64, hash).domain_separated_hash() != root
+        {
+            return Err(ValueError::InvalidCommitment);
+        }
+
+        self.commitment.verify(active)
+    }
+}
+
+impl EncodedValue<state::Full> {
+    /// Returns a single commitment to the encodings of a batch of values.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The values to commit to.
+    pub fn commit_batch(values: &[EncodedValue<state::Full>]) -> BatchEncodingCommitment {
+        let commitments: Vec<_> = values.iter().map(|value| value.commit()).collect();
+
+        let mut levels = vec![commitments
+            .iter()
+            .map(|commitment| LeafCommit(commitment).domain_separated_hash())
+            .collect::<Vec<_>>()];
+        while levels.last().expect("levels is not empty").len() > 1 {
+            let level = levels
+                .last()
+                .expect("levels is not empty")
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => NodeCommit(*left, *right).domain_separated_hash(),
+                    [node] => *node,
+                    _ => unreachable!("chunks are of length 1 or 2"),
+                })
+                .collect();
+            levels.push(level);
+        }
+
+        let tree_root = levels
+            .last()
+            .expect("levels is not empty")
+            .first()
+            .copied()
+            .unwrap_or(Hash::from([0u8; 32]));
+        let root = RootCommit(values.len() as u64, tree_root).domain_separated_hash();
+
+        let openings = commitments
+            .into_iter()
+            .enumerate()
+            .map(|(index, commitment)| {
+                let mut path = Vec::new();
+                let mut idx = index;
+                for level in &levels[..levels.len() - 1] {
+                    if let Some(sibling) = level.get(idx ^ 1) {
+                        path.push(*sibling);
+                    }
+                    idx >>= 1;
+                }
+
+                EncodingOpening {
+                    index,
+                    count: values.len(),
+                    commitment,
+                    path,
+                }
+            })
+            .collect();
+
+        BatchEncodingCommitment { root, openings }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits::types::{Value, ValueType};
+
+    use crate::{ChaChaEncoder, Encoder};
+
+    use super::*;
+
+    fn values() -> Vec<(ValueType, Value)> {
+        vec![
+            (ValueType::Bit, true.into()),
+            (ValueType::U8, 42u8.into()),
+            (ValueType::U32, 69u32.into()),
+            (ValueType::U64, 1u64.into()),
+            (ValueType::new_array::<u8>(4), [1u8, 2, 3, 4].into()),
+        ]
+    }
+
+    #[test]
+    fn test_commit_batch() {
+        let encoder = ChaChaEncoder::new([0u8; 32]);
+
+        let all_values = values();
+        for count in 0..=all_values.len() {
+            let values = &all_values[..count];
+            let full: Vec<_> = values
+                .iter()
+                .enumerate()
+                .map(|(id, (ty, _))| encoder.encode_by_type(id as u64, ty))
+                .collect();
+            let active: Vec<_> = full
+                .iter()
+                .zip(values)
+                .map(|(full, (_, value))| full.select(value.clone()).unwrap())
+                .collect();
+
+            let batch = EncodedValue::commit_batch(&full);
+            let root = batch.root();
+
+            assert_eq!(batch.openings().len(), count);
+            for (opening, active) in batch.openings().iter().zip(&active) {
+                opening.verify(&root, active).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_commit_batch_tampered() {
+        let encoder = ChaChaEncoder::new([0u8; 32]);
+
+        let values = values();
+        let full: Vec<_> = values
+            .iter()
+            .enumerate()
+            .map(|(id, (ty, _))| encoder.encode_by_type(id as u64, ty))
+            .collect();
+
+        let (root, openings) = EncodedValue::commit_batch(&full).into_parts();
+
+        // An active encoding which was not committed to.
+        let (ty, value) = &values[1];
+        let tampered = encoder
+            .encode_by_type(100, ty)
+            .select(value.clone())
+            .unwrap();
+        assert!(matches!(
+            openings[1].verify(&root, &tampered),
+            Err(ValueError::InvalidCommitment)
+        ));
+
+        let active = full[1].select(value.clone()).unwrap();
+        openings[1].verify(&root, &active).unwrap();
+
+        // An opening which does not match the root.
+        let mut opening = openings[1].clone();
+        opening.commitment = encoder.encode_by_type(100, ty).commit();
+        assert!(matches!(
+            opening.verify(&root, &active),
+            Err(ValueError::InvalidCommitment)
+        ));
+
+        let mut opening = openings[1].clone();
+        opening.index = 0;
+        assert!(matches!(
+            opening.verify(&root, &active),
+            Err(ValueError::InvalidCommitment)
+        ));
+
+        let mut opening = openings[1].clone();
+        opening.count = 2;
+        assert!(matches!(
+            opening.verify(&root, &active),
+            Err(ValueError::InvalidCommitment)
+        ));
+
+        // A root of a different batch.
+        let other_root = EncodedValue::commit_batch(&full).root();
+        assert!(matches!(
+            openings[1].verify(&other_root, &active),
+            Err(ValueError::InvalidCommitment)
+        ));
+    }
+}
@@ -31,6 +31,17 @@ pub trait Encoder: Send + Sync {
     /// * `id` - Unique id of value
     /// * `ty` - Type of value
     fn encode_by_type(&self, id: u64, ty: &ValueType) -> EncodedValue<state::Full>;
+
+    /// Encodes many values, returning the same encodings as calling
+    /// [`encode_by_type`](Self::encode_by_type) for each value.
+    ///
+    /// * `ids_and_types` - Unique id and type of each value
+    fn encode_many(&self, ids_and_types: &[(u64, ValueType)]) -> Vec<EncodedValue<state::Full>> {
+        ids_and_types
+            .iter()
+            .map(|(id, ty)| self.encode_by_type(*id, ty))
+            .collect()
+    }
 }
 
 /// Encodes values using the ChaCha algorithm.
@@ -119,6 +130,36 @@ impl Encoder for ChaChaEncoder {
             _ => unimplemented!("encoding of type {:?} is not implemented", ty),
         }
     }
+
+    fn encode_many(&self, ids_and_types: &[(u64, ValueType)]) -> Vec<EncodedValue<state::Full>> {
+        // The key schedule is only computed once, each value is then encoded by
+        // seeking a copy of the rng to the value's stream.
+        let rng = ChaCha20Rng::from_seed(self.seed);
+        let mut labels = Vec::new();
+
+        ids_and_types
+            .iter()
+            .map(|(id, ty)| {
+                if *id == DELTA_STREAM_ID {
+                    panic!("stream id {} is reserved", DELTA_STREAM_ID);
+                }
+
+                let mut rng = rng.clone();
+                rng.set_stream(*id);
+                rng.set_word_pos(0);
+
+                labels.clear();
+                labels.extend(
+                    Block::random_vec(&mut rng, ty.len())
+                        .into_iter()
+                        .map(Label::new),
+                );
+
+                EncodedValue::<state::Full>::from_labels(ty.clone(), self.delta, &labels)
+                    .expect("bit length should be correct")
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -151,4 +192,28 @@ mod test {
 
         assert_eq!(encoded, encoded2);
     }
+
+    #[rstest]
+    fn test_encoder_encode_many(encoder: ChaChaEncoder) {
+        let ids_and_types = vec![
+            (0, ValueType::Bit),
+            (1, ValueType::U8),
+            (2, ValueType::U16),
+            (3, ValueType::U32),
+            (4, ValueType::U64),
+            (5, ValueType::U128),
+            (6, ValueType::Array(Box::new(ValueType::U8), 16)),
+            (7, ValueType::Array(Box::new(ValueType::U32), 4)),
+            (1, ValueType::U8),
+        ];
+
+        let encoded = encoder.encode_many(&ids_and_types);
+
+        let expected = ids_and_types
+            .iter()
+            .map(|(id, ty)| encoder.encode_by_type(*id, ty))
+            .collect::<Vec<_>>();
+
+        assert_eq!(encoded, expected);
+    }
 }
@@ -12,6 +12,7 @@
 //! The Free-XOR technique stipulates that a [global binary offset](Delta) is used such that the labels for bit
 //! value 1 are generated by XORing the label for bit value 0 with the global offset, ie W_1 = W_0 ^ Delta.
 
+mod batch;
 mod encoder;
 mod equality;
 mod ops;
@@ -26,6 +27,7 @@ use mpz_core::Block;
 use rand::{CryptoRng, Rng};
 use serde::{Deserialize, Deserializer, Serialize};
 
+pub use batch::{BatchEncodingCommitment, EncodingOpening};
 pub use encoder::{ChaChaEncoder, Encoder};
 pub use equality::EqualityCheck;
 pub use value::{Decoding, Encode, EncodedValue, EncodingCommitment, ValueError};
@@ -194,6 +194,17 @@ macro_rules! define_encoded_value {
                 active.decode(&self.decoding())
             }
 
+            /// Returns whether the active encoding decodes to `value` under the decoding of
+            /// this encoding.
+            ///
+            /// This is intended for debugging whether two parties' encodings correspond to the
+            /// same plaintext, and does not verify the authenticity of the active encoding.
+            pub fn decodes_to(&self, active: &EncodedValue<state::Active>, value: &Value) -> bool {
+                active
+                    .decode(&self.decoding())
+                    .is_ok_and(|decoded| &decoded == value)
+            }
+
             /// Returns an iterator over the blocks of an encoded value.
             pub fn iter_blocks(&self) -> Box<dyn Iterator<Item = [Block; 2]> + Send + '_> {
                 match self {
@@ -833,4 +844,14 @@ mod tests {
         assert_eq!(decoded_value.value_type(), T::value_type());
         assert_eq!(decoded_value, value.into());
     }
+
+    #[rstest]
+    fn test_decodes_to(encoder: ChaChaEncoder) {
+        let encoded: EncodedValue<_> = encoder.encode_by_type(0, &u8::value_type());
+        let active = encoded.select(42u8).unwrap();
+
+        assert!(encoded.decodes_to(&active, &Value::from(42u8)));
+        assert!(!encoded.decodes_to(&active, &Value::from(43u8)));
+        assert!(!encoded.decodes_to(&active, &Value::from(42u16)));
+    }
 }
@@ -23,6 +23,8 @@ pub enum EvaluatorError {
     CircuitError(#[from] CircuitError),
     #[error("evaluator not finished")]
     NotFinished,
+    #[error("output wire {wire} was never assigned")]
+    UnassignedOutput { wire: usize },
 }
 
 /// Evaluates half-gate garbled AND gate
@@ -68,6 +70,8 @@ pub struct Evaluator {
     gid: usize,
     /// Whether the evaluator is finished
     complete: bool,
+    /// Whether to check that every output wire was assigned
+    strict: bool,
     /// Hasher to use to hash the encrypted gates
     hasher: Option<Hasher>,
 }
@@ -83,7 +87,24 @@ impl Evaluator {
         circ: Arc<Circuit>,
         inputs: &[EncodedValue<state::Active>],
     ) -> Result<Self, EvaluatorError> {
-        Self::new_with(circ, inputs, None)
+        Self::new_with(circ, inputs, None, false)
+    }
+
+    /// Creates a new evaluator for the given circuit which checks that every output
+    /// wire was assigned during evaluation.
+    ///
+    /// [`outputs`](Self::outputs) returns [`EvaluatorError::UnassignedOutput`] if any
+    /// output wire was never written.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to evaluate.
+    /// * `inputs` - The inputs to the circuit.
+    pub fn new_strict(
+        circ: Arc<Circuit>,
+        inputs: &[EncodedValue<state::Active>],
+    ) -> Result<Self, EvaluatorError> {
+        Self::new_with(circ, inputs, None, true)
     }
 
     /// Creates a new evaluator for the given circuit. Evaluator will compute
@@ -97,13 +118,14 @@ impl Evaluator {
         circ: Arc<Circuit>,
         inputs: &[EncodedValue<state::Active>],
     ) -> Result<Self, EvaluatorError> {
-        Self::new_with(circ, inputs, Some(Hasher::new()))
+        Self::new_with(circ, inputs, Some(Hasher::new()), false)
     }
 
     fn new_with(
         circ: Arc<Circuit>,
         inputs: &[EncodedValue<state::Active>],
         hasher: Option<Hasher>,
+        strict: bool,
     ) -> Result<Self, EvaluatorError> {
         if inputs.len() != circ.inputs().len() {
             return Err(CircuitError::InvalidInputCount(
@@ -133,6 +155,7 @@ impl Evaluator {
             pos: 0,
             gid: 1,
             complete: false,
+            strict,
             hasher,
         };
 
@@ -206,20 +229,56 @@ impl Evaluator {
             return Err(EvaluatorError::NotFinished);
         }
 
-        Ok(self
-            .circ
+        self.circ
             .outputs()
             .iter()
             .map(|output| {
                 let labels: Vec<Label> = output
                     .iter()
-                    .map(|node| self.active_labels[node.id()].expect("feed should be initialized"))
-                    .collect();
+                    .map(|node| match self.active_labels[node.id()] {
+                        Some(label) => Ok(label),
+                        None if self.strict => {
+                            Err(EvaluatorError::UnassignedOutput { wire: node.id() })
+                        }
+                        None => panic!("feed should be initialized"),
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                Ok(
+                    EncodedValue::<state::Active>::from_labels(output.value_type(), &labels)
+                        .expect("encoding should be correct"),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the active encoded outputs of the circuit which are ready, ie
+    /// every label of the output has been evaluated so far.
+    ///
+    /// This can be used to decode outputs before the rest of the circuit has
+    /// been evaluated.
+    ///
+    /// # Returns
+    ///
+    /// A vector of tuples containing the index of each ready output and its encoding.
+    pub fn ready_outputs(&self) -> Vec<(usize, EncodedValue<state::Active>)> {
+        self.circ
+            .outputs()
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, output)| {
+                let labels: Vec<Label> = output
+                    .iter()
+                    .map(|node| self.active_labels[node.id()])
+                    .collect::<Option<_>>()?;
 
-                EncodedValue::<state::Active>::from_labels(output.value_type(), &labels)
-                    .expect("encoding should be correct")
+                let value =
+                    EncodedValue::<state::Active>::from_labels(output.value_type(), &labels)
+                        .expect("encoding should be correct");
+
+                Some((idx, value))
             })
-            .collect())
+            .collect()
     }
 
     /// Returns the hash of the encrypted gates.
@@ -230,3 +289,103 @@ impl Evaluator {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits::{ops::WrappingAdd, CircuitBuilder};
+
+    use crate::{ChaChaEncoder, Encoder, Generator};
+
+    use super::*;
+
+    #[test]
+    fn test_evaluator_ready_outputs() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        let c = a & b;
+        let d = a.wrapping_add(b);
+        builder.add_output(c);
+        builder.add_output(d);
+        let circ = Arc::new(builder.build().unwrap());
+
+        let encoder = ChaChaEncoder::new([0; 32]);
+        let full_inputs: [EncodedValue<state::Full>; 2] = [
+            encoder.encode::<u8>(0).into(),
+            encoder.encode::<u8>(1).into(),
+        ];
+        let active_inputs = [
+            full_inputs[0].clone().select(3u8).unwrap(),
+            full_inputs[1].clone().select(5u8).unwrap(),
+        ];
+
+        let mut gen = Generator::new(circ.clone(), encoder.delta(), &full_inputs).unwrap();
+        let mut ev = Evaluator::new(circ, &active_inputs).unwrap();
+
+        let enc_gates: Vec<EncryptedGate> = gen.by_ref().collect();
+        let full_outputs = gen.outputs().unwrap();
+
+        assert!(ev.ready_outputs().is_empty());
+
+        // The first output only depends on the first 8 AND gates.
+        ev.evaluate(enc_gates[..8].iter());
+
+        let ready = ev.ready_outputs();
+        assert!(!ev.is_complete());
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].0, 0);
+
+        let c: u8 = ready[0]
+            .1
+            .decode(&full_outputs[0].decoding())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(c, 3u8 & 5u8);
+
+        ev.evaluate(enc_gates[8..].iter());
+
+        let ready = ev.ready_outputs();
+        assert!(ev.is_complete());
+        assert_eq!(ready.len(), 2);
+        assert_eq!(ready[1].0, 1);
+
+        let d: u8 = ready[1]
+            .1
+            .decode(&full_outputs[1].decoding())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(d, 3u8.wrapping_add(5u8));
+    }
+
+    #[test]
+    fn test_evaluator_strict_unassigned_output() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        builder.add_output(a ^ b);
+        let mut circ = builder.build().unwrap();
+
+        let dropped = circ.pop_gate().unwrap();
+        let circ = Arc::new(circ);
+
+        let encoder = ChaChaEncoder::new([0; 32]);
+        let active_inputs = [
+            EncodedValue::<state::Full>::from(encoder.encode::<u8>(0))
+                .select(3u8)
+                .unwrap(),
+            EncodedValue::<state::Full>::from(encoder.encode::<u8>(1))
+                .select(5u8)
+                .unwrap(),
+        ];
+
+        let ev = Evaluator::new_strict(circ, &active_inputs).unwrap();
+
+        assert!(ev.is_complete());
+        assert!(matches!(
+            ev.outputs(),
+            Err(EvaluatorError::UnassignedOutput { wire }) if wire == dropped.z().id()
+        ));
+    }
+}
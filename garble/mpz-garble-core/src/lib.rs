@@ -59,10 +59,10 @@ mod evaluator;
 mod generator;
 pub mod msg;
 
-pub use circuit::{EncryptedGate, GarbledCircuit};
+pub use circuit::{EncryptedGate, GarbledCircuit, GarbledCircuitError};
 pub use encoding::{
-    state as encoding_state, ChaChaEncoder, Decoding, Delta, Encode, EncodedValue, Encoder,
-    EncodingCommitment, EqualityCheck, Label, ValueError,
+    state as encoding_state, BatchEncodingCommitment, ChaChaEncoder, Decoding, Delta, Encode,
+    EncodedValue, Encoder, EncodingCommitment, EncodingOpening, EqualityCheck, Label, ValueError,
 };
 pub use evaluator::{Evaluator, EvaluatorError};
 pub use generator::{Generator, GeneratorError};
@@ -71,9 +71,12 @@ pub use generator::{Generator, GeneratorError};
 mod tests {
     use aes::{
         cipher::{BlockEncrypt, KeyInit},
-        Aes128,
+        Aes128, Aes256,
+    };
+    use mpz_circuits::{
+        circuits::{AES128, AES256},
+        types::Value,
     };
-    use mpz_circuits::{circuits::AES128, types::Value};
     use mpz_core::aes::FIXED_KEY_AES;
     use rand::SeedableRng;
     use rand_chacha::ChaCha12Rng;
@@ -164,4 +167,66 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_garble_aes256() {
+        let encoder = ChaChaEncoder::new([0; 32]);
+
+        let key = [69u8; 32];
+        let msg = [42u8; 16];
+        const BATCH_SIZE: usize = 1000;
+
+        let expected: [u8; 16] = {
+            let cipher = Aes256::new_from_slice(&key).unwrap();
+            let mut out = msg.into();
+            cipher.encrypt_block(&mut out);
+            out.into()
+        };
+
+        let full_inputs: Vec<EncodedValue<encoding_state::Full>> = AES256
+            .inputs()
+            .iter()
+            .map(|input| encoder.encode_by_type(0, &input.value_type()))
+            .collect();
+
+        let active_inputs: Vec<EncodedValue<encoding_state::Active>> = vec![
+            full_inputs[0].clone().select(key).unwrap(),
+            full_inputs[1].clone().select(msg).unwrap(),
+        ];
+
+        let mut gen =
+            Generator::new_with_hasher(AES256.clone(), encoder.delta(), &full_inputs).unwrap();
+        let mut ev = Evaluator::new_with_hasher(AES256.clone(), &active_inputs).unwrap();
+
+        while !(gen.is_complete() && ev.is_complete()) {
+            let mut batch = Vec::with_capacity(BATCH_SIZE);
+            for enc_gate in gen.by_ref() {
+                batch.push(enc_gate);
+                if batch.len() == BATCH_SIZE {
+                    break;
+                }
+            }
+            ev.evaluate(batch.iter());
+        }
+
+        let full_outputs = gen.outputs().unwrap();
+        let active_outputs = ev.outputs().unwrap();
+
+        let gen_digest = gen.hash().unwrap();
+        let ev_digest = ev.hash().unwrap();
+
+        assert_eq!(gen_digest, ev_digest);
+
+        let outputs: Vec<Value> = active_outputs
+            .iter()
+            .zip(full_outputs)
+            .map(|(active_output, full_output)| {
+                active_output.decode(&full_output.decoding()).unwrap()
+            })
+            .collect();
+
+        let actual: [u8; 16] = outputs[0].clone().try_into().unwrap();
+
+        assert_eq!(actual, expected);
+    }
 }
@@ -1,6 +1,6 @@
 //! Messages used in garbled circuit protocols.
 
-use mpz_core::{commit::Decommitment, hash::Hash};
+use mpz_core::{commit::Decommitment, hash::Hash, Block};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -26,4 +26,8 @@ pub enum GarbleMessage {
     ProofDecommitments(Vec<Decommitment<Hash>>),
     Delta(Delta),
     EncoderSeed(Vec<u8>),
+    CircuitHash([u8; 32]),
+    /// Whether the sender prefers the leader role, if it has a preference.
+    RolePreference(Option<bool>),
+    SeedDecommitment(Decommitment<Block>),
 }
@@ -331,13 +331,9 @@ impl Evaluator {
             None
         };
 
-        self.state().garbled_circuits.insert(
-            refs,
-            GarbledCircuit {
-                gates,
-                commitments: encoding_commitments,
-            },
-        );
+        self.state()
+            .garbled_circuits
+            .insert(refs, GarbledCircuit::new(gates, encoding_commitments));
 
         Ok(())
     }
@@ -387,8 +383,9 @@ impl Evaluator {
 
         // If we've already received the garbled circuit, we evaluate it, otherwise we stream the encrypted gates
         // from the generator.
-        let encoded_outputs = if let Some(GarbledCircuit { gates, commitments }) =
-            existing_garbled_circuit
+        let encoded_outputs = if let Some(GarbledCircuit {
+            gates, commitments, ..
+        }) = existing_garbled_circuit
         {
             ev = Backend::spawn(move || {
                 ev.evaluate(gates.iter());
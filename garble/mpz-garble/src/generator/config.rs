@@ -1,4 +1,7 @@
 use derive_builder::Builder;
+use futures::channel::mpsc;
+
+use super::GenProgress;
 
 /// Generator configuration.
 #[derive(Debug, Clone, Builder)]
@@ -9,6 +12,9 @@ pub struct GeneratorConfig {
     /// The batch size for encrypted gates sent to the evaluator.
     #[builder(default = "1024")]
     pub(crate) batch_size: usize,
+    /// A channel to send progress updates to while garbling.
+    #[builder(default, setter(custom))]
+    pub(crate) progress: Option<mpsc::Sender<GenProgress>>,
 }
 
 impl GeneratorConfig {
@@ -24,6 +30,15 @@ impl GeneratorConfigBuilder {
         self.encoding_commitments = Some(true);
         self
     }
+
+    /// Sets a channel to send progress updates to while garbling.
+    ///
+    /// An update is sent after each batch of encrypted gates. Garbling waits for the
+    /// channel to have capacity, so the receiver should be polled concurrently.
+    pub fn progress(&mut self, tx: mpsc::Sender<GenProgress>) -> &mut Self {
+        self.progress = Some(Some(tx));
+        self
+    }
 }
 
 impl Default for GeneratorConfig {
@@ -19,6 +19,10 @@ pub enum GeneratorError {
     DuplicateEncoding(ValueRef),
     #[error("missing encoding for value: {0:?}")]
     MissingEncoding(ValueRef),
+    #[error("value was made active by a previous generator: {0:?}")]
+    ReusedActiveEncoding(ValueRef),
+    #[error("active id log does not match the generator's delta")]
+    DeltaMismatch,
     #[error(transparent)]
     EncodingRegistryError(#[from] crate::memory::EncodingMemoryError),
 }
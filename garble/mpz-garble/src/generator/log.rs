@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+
+use mpz_garble_core::Delta;
+
+use crate::value::ValueId;
+
+/// A log of the values which have been made active under a global offset (delta).
+///
+/// Generators seeded with the same encoder seed share the same delta, so the log can be
+/// persisted and used to restore a generator with [`Generator::new_with_log`](super::Generator::new_with_log).
+/// The restored generator refuses to activate any value in the log, which guarantees that
+/// the same encoding is never used with different active values across restarts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveIdLog {
+    delta: Delta,
+    ids: HashSet<ValueId>,
+}
+
+impl ActiveIdLog {
+    /// Creates a new, empty log for the given delta.
+    pub fn new(delta: Delta) -> Self {
+        Self {
+            delta,
+            ids: HashSet::new(),
+        }
+    }
+
+    /// Creates a log for the given delta from previously active value ids.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The global offset the values were encoded with.
+    /// * `ids` - The ids of the values which were made active.
+    pub fn from_ids(delta: Delta, ids: impl IntoIterator<Item = ValueId>) -> Self {
+        Self {
+            delta,
+            ids: ids.into_iter().collect(),
+        }
+    }
+
+    /// Returns the delta of the log.
+    pub fn delta(&self) -> Delta {
+        self.delta
+    }
+
+    /// Returns whether the value has been made active.
+    pub fn contains(&self, id: &ValueId) -> bool {
+        self.ids.contains(id)
+    }
+
+    /// Returns an iterator over the ids of the values which have been made active.
+    pub fn ids(&self) -> impl Iterator<Item = &ValueId> {
+        self.ids.iter()
+    }
+
+    pub(crate) fn extend(&mut self, ids: impl IntoIterator<Item = ValueId>) {
+        self.ids.extend(ids);
+    }
+}
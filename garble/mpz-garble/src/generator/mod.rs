@@ -2,6 +2,7 @@
 
 mod config;
 mod error;
+mod log;
 
 use std::{
     collections::{HashMap, HashSet},
@@ -9,14 +10,14 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use futures::{Sink, SinkExt};
+use futures::{channel::oneshot, stream, Sink, SinkExt, Stream, TryStreamExt};
 use mpz_circuits::{
     types::{Value, ValueType},
     Circuit,
 };
 use mpz_core::hash::Hash;
 use mpz_garble_core::{
-    encoding_state, msg::GarbleMessage, ChaChaEncoder, EncodedValue, Encoder,
+    encoding_state, msg::GarbleMessage, ChaChaEncoder, EncodedValue, Encoder, EncryptedGate,
     Generator as GeneratorCore,
 };
 use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
@@ -30,6 +31,18 @@ use crate::{
 
 pub use config::{GeneratorConfig, GeneratorConfigBuilder};
 pub use error::GeneratorError;
+pub use log::ActiveIdLog;
+
+/// A progress update sent while garbling a circuit.
+///
+/// Only AND gates are counted, as they are the only gates which are encrypted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenProgress {
+    /// The number of gates which have been garbled.
+    pub gates_done: usize,
+    /// The total number of gates in the circuit.
+    pub gates_total: usize,
+}
 
 /// A garbled circuit generator.
 #[derive(Debug, Default)]
@@ -55,6 +68,8 @@ struct State {
     /// This is used to guarantee that the same encoding is never used
     /// with different active values.
     active: HashSet<ValueId>,
+    /// Values which were made active by a previous generator with the same delta.
+    log: Option<ActiveIdLog>,
 }
 
 impl Generator {
@@ -66,6 +81,57 @@ impl Generator {
         }
     }
 
+    /// Create a new generator, restoring the values which were made active by a
+    /// previous generator with the same encoder seed.
+    ///
+    /// The generator refuses to activate any value in the log.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The generator configuration.
+    /// * `encoder_seed` - The seed used to generate encodings.
+    /// * `log` - The log of a previous generator, see [`Generator::active_log`].
+    pub fn new_with_log(
+        config: GeneratorConfig,
+        encoder_seed: [u8; 32],
+        log: ActiveIdLog,
+    ) -> Result<Self, GeneratorError> {
+        let encoder = ChaChaEncoder::new(encoder_seed);
+
+        if encoder.delta() != log.delta() {
+            return Err(GeneratorError::DeltaMismatch);
+        }
+
+        let mut state = State::new(encoder);
+        state.log = Some(log);
+
+        Ok(Self {
+            config,
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Returns the number of encrypted gates sent to the evaluator in each message.
+    pub(crate) fn batch_size(&self) -> usize {
+        self.config.batch_size
+    }
+
+    /// Returns a log of every value which has been made active under this generator's delta,
+    /// including those restored with [`Generator::new_with_log`].
+    ///
+    /// The log should be persisted so that a restarted generator can be restored from it.
+    pub fn active_log(&self) -> ActiveIdLog {
+        let state = self.state();
+
+        let mut log = state
+            .log
+            .clone()
+            .unwrap_or_else(|| ActiveIdLog::new(state.encoder.delta()));
+        log.extend(state.active.iter().cloned());
+
+        log
+    }
+
     /// Convenience method for grabbing a lock to the state.
     fn state(&self) -> impl DerefMut<Target = State> + '_ {
         self.state.lock().unwrap()
@@ -265,44 +331,17 @@ impl Generator {
             inputs: inputs.to_vec(),
             outputs: outputs.to_vec(),
         };
-        let (delta, inputs) = {
-            let state = self.state();
 
-            // If the circuit has already been garbled, return early
-            if let Some(hash) = state.garbled.get(&refs) {
-                return Ok((
-                    outputs
-                        .iter()
-                        .map(|output| {
-                            state
-                                .memory
-                                .get_encoding(output)
-                                .expect("encoding exists if circuit is garbled already")
-                        })
-                        .collect(),
-                    *hash,
-                ));
-            }
-
-            let delta = state.encoder.delta();
-            let inputs = inputs
-                .iter()
-                .map(|value| {
-                    state
-                        .memory
-                        .get_encoding(value)
-                        .ok_or(GeneratorError::MissingEncoding(value.clone()))
-                })
-                .collect::<Result<Vec<_>, _>>()?;
+        // If the circuit has already been garbled, return early
+        if let Some(garbled) = self.garbled_outputs(&refs) {
+            return Ok(garbled);
+        }
 
-            (delta, inputs)
-        };
+        let mut gen = self.new_core(circ.clone(), inputs, hash)?;
 
-        let mut gen = if hash {
-            GeneratorCore::new_with_hasher(circ.clone(), delta, &inputs)?
-        } else {
-            GeneratorCore::new(circ.clone(), delta, &inputs)?
-        };
+        let mut progress = self.config.progress.clone();
+        let gates_total = circ.and_count();
+        let mut gates_done = 0;
 
         let mut batch: Vec<_>;
         let batch_size = self.config.batch_size;
@@ -316,7 +355,18 @@ impl Generator {
             .await;
 
             if !batch.is_empty() {
+                gates_done += batch.len();
                 sink.send(GarbleMessage::EncryptedGates(batch)).await?;
+
+                if let Some(progress) = progress.as_mut() {
+                    // Progress updates are best-effort, the receiver may have been dropped.
+                    _ = progress
+                        .send(GenProgress {
+                            gates_done,
+                            gates_total,
+                        })
+                        .await;
+                }
             }
         }
 
@@ -333,9 +383,176 @@ impl Generator {
                 .await?;
         }
 
-        // Add the outputs to the memory and set as active.
+        self.set_garbled(refs, &encoded_outputs, hash)?;
+
+        Ok((encoded_outputs, hash))
+    }
+
+    /// Returns a stream of the encrypted gates of a garbled circuit, for integrators which
+    /// transfer the gates themselves.
+    ///
+    /// The circuit is garbled lazily in batches as the stream is polled, with the compute
+    /// offloaded to the non-blocking backend. Once the stream completes, the returned receiver
+    /// resolves to the encodings of the outputs, and optionally a hash of the circuit. If the
+    /// stream is dropped early or yields an error, the receiver is canceled.
+    ///
+    /// Unlike [`generate`](Self::generate), commitments to the output encodings are not sent
+    /// to the evaluator.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to garble
+    /// * `inputs` - The inputs of the circuit
+    /// * `outputs` - The outputs of the circuit
+    /// * `hash` - Whether to hash the circuit
+    #[allow(clippy::type_complexity)]
+    pub fn gate_stream(
+        &self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        hash: bool,
+    ) -> (
+        impl Stream<Item = Result<EncryptedGate, GeneratorError>> + Send + '_,
+        oneshot::Receiver<(Vec<EncodedValue<encoding_state::Full>>, Option<Hash>)>,
+    ) {
+        let (sender, receiver) = oneshot::channel();
+        let state = GateStreamState {
+            refs: CircuitRefs {
+                inputs: inputs.to_vec(),
+                outputs: outputs.to_vec(),
+            },
+            circ,
+            hash,
+            gen: None,
+            sender,
+        };
+        let batch_size = self.config.batch_size;
+
+        let stream = stream::try_unfold(Some(state), move |state| {
+            self.next_gate_batch(state, batch_size)
+        })
+        .map_ok(|batch| stream::iter(batch.into_iter().map(Ok)))
+        .try_flatten();
+
+        (stream, receiver)
+    }
+
+    /// Garbles the next batch of gates of a stream returned by [`gate_stream`](Self::gate_stream).
+    ///
+    /// Returns `None` once the circuit has been garbled and the outputs have been sent.
+    #[allow(clippy::type_complexity)]
+    async fn next_gate_batch(
+        &self,
+        state: Option<GateStreamState>,
+        batch_size: usize,
+    ) -> Result<Option<(Vec<EncryptedGate>, Option<GateStreamState>)>, GeneratorError> {
+        let Some(mut state) = state else {
+            return Ok(None);
+        };
+
+        let mut gen = match state.gen.take() {
+            Some(gen) => gen,
+            None => {
+                // If the circuit has already been garbled, there are no gates to yield
+                if let Some(garbled) = self.garbled_outputs(&state.refs) {
+                    _ = state.sender.send(garbled);
+                    return Ok(None);
+                }
+
+                self.new_core(state.circ.clone(), &state.refs.inputs, state.hash)?
+            }
+        };
+
+        if gen.is_complete() {
+            let encoded_outputs = gen.outputs()?;
+            let hash = gen.hash();
+
+            self.set_garbled(state.refs, &encoded_outputs, hash)?;
+
+            // The receiver may have been dropped.
+            _ = state.sender.send((encoded_outputs, hash));
+
+            return Ok(None);
+        }
+
+        // Move the generator to another thread to produce the next batch
+        // then send it back
+        let batch: Vec<_>;
+        (gen, batch) = Backend::spawn(move || {
+            let batch = gen.by_ref().take(batch_size).collect();
+            (gen, batch)
+        })
+        .await;
+
+        state.gen = Some(gen);
+
+        Ok(Some((batch, Some(state))))
+    }
+
+    /// Returns the output encodings and hash of a circuit which has already been garbled.
+    #[allow(clippy::type_complexity)]
+    fn garbled_outputs(
+        &self,
+        refs: &CircuitRefs,
+    ) -> Option<(Vec<EncodedValue<encoding_state::Full>>, Option<Hash>)> {
+        let state = self.state();
+        let hash = state.garbled.get(refs)?;
+
+        Some((
+            refs.outputs
+                .iter()
+                .map(|output| {
+                    state
+                        .memory
+                        .get_encoding(output)
+                        .expect("encoding exists if circuit is garbled already")
+                })
+                .collect(),
+            *hash,
+        ))
+    }
+
+    /// Creates a core generator for the circuit from the encodings of its inputs.
+    fn new_core(
+        &self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        hash: bool,
+    ) -> Result<GeneratorCore, GeneratorError> {
+        let (delta, inputs) = {
+            let state = self.state();
+
+            let delta = state.encoder.delta();
+            let inputs = inputs
+                .iter()
+                .map(|value| {
+                    state
+                        .memory
+                        .get_encoding(value)
+                        .ok_or(GeneratorError::MissingEncoding(value.clone()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            (delta, inputs)
+        };
+
+        Ok(if hash {
+            GeneratorCore::new_with_hasher(circ, delta, &inputs)?
+        } else {
+            GeneratorCore::new(circ, delta, &inputs)?
+        })
+    }
+
+    /// Adds the output encodings of a garbled circuit to the memory and sets them as active.
+    fn set_garbled(
+        &self,
+        refs: CircuitRefs,
+        encoded_outputs: &[EncodedValue<encoding_state::Full>],
+        hash: Option<Hash>,
+    ) -> Result<(), GeneratorError> {
         let mut state = self.state();
-        for (output, encoding) in outputs.iter().zip(encoded_outputs.iter()) {
+        for (output, encoding) in refs.outputs.iter().zip(encoded_outputs) {
             state.memory.set_encoding(output, encoding.clone())?;
             output.iter().for_each(|id| {
                 state.active.insert(id.clone());
@@ -344,7 +561,7 @@ impl Generator {
 
         state.garbled.insert(refs, hash);
 
-        Ok((encoded_outputs, hash))
+        Ok(())
     }
 
     /// Send value decoding information to the evaluator.
@@ -378,6 +595,16 @@ impl Generator {
     }
 }
 
+/// The state of a stream returned by [`Generator::gate_stream`].
+struct GateStreamState {
+    refs: CircuitRefs,
+    circ: Arc<Circuit>,
+    hash: bool,
+    /// The core generator, created when the stream is first polled.
+    gen: Option<GeneratorCore>,
+    sender: oneshot::Sender<(Vec<EncodedValue<encoding_state::Full>>, Option<Hash>)>,
+}
+
 impl State {
     fn new(encoder: ChaChaEncoder) -> Self {
         Self {
@@ -429,6 +656,13 @@ impl State {
             .get_encoding_by_id(id)
             .ok_or_else(|| GeneratorError::MissingEncoding(ValueRef::Value { id: id.clone() }))?;
 
+        // Returns error if the encoding was made active by a previous generator
+        if self.log.as_ref().is_some_and(|log| log.contains(id)) {
+            return Err(GeneratorError::ReusedActiveEncoding(ValueRef::Value {
+                id: id.clone(),
+            }));
+        }
+
         // Returns error if the encoding is already active
         if !self.active.insert(id.clone()) {
             return Err(GeneratorError::DuplicateEncoding(ValueRef::Value {
@@ -439,3 +673,116 @@ impl State {
         Ok(encoding)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures::{channel::mpsc, StreamExt};
+    use mpz_circuits::{circuits::AES128, types::StaticValueType};
+
+    use crate::{config::Visibility, ValueMemory};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_generator_progress() {
+        let (tx, rx) = mpsc::channel(1);
+        let gen = Generator::new(
+            GeneratorConfig::builder()
+                .batch_size(1000)
+                .progress(tx)
+                .build()
+                .unwrap(),
+            [0u8; 32],
+        );
+
+        let mut memory = ValueMemory::default();
+        let key = memory
+            .new_input("key", <[u8; 16]>::value_type(), Visibility::Private)
+            .unwrap();
+        let msg = memory
+            .new_input("msg", <[u8; 16]>::value_type(), Visibility::Private)
+            .unwrap();
+        let ciphertext = memory
+            .new_output("ciphertext", <[u8; 16]>::value_type())
+            .unwrap();
+
+        gen.generate_input_encoding(&key, &<[u8; 16]>::value_type());
+        gen.generate_input_encoding(&msg, &<[u8; 16]>::value_type());
+
+        let mut sink = futures::sink::drain()
+            .sink_map_err(|_| std::io::Error::new(std::io::ErrorKind::UnexpectedEof, ""));
+
+        let (_, updates) = futures::join!(
+            async {
+                gen.generate(AES128.clone(), &[key, msg], &[ciphertext], &mut sink, false)
+                    .await
+                    .unwrap();
+
+                // Drop the generator to close the progress channel.
+                drop(gen);
+            },
+            rx.collect::<Vec<_>>()
+        );
+
+        let gates_total = AES128.and_count();
+        assert_eq!(updates.len(), gates_total.div_ceil(1000));
+        assert!(updates
+            .windows(2)
+            .all(|w| w[0].gates_done < w[1].gates_done));
+        assert!(updates
+            .iter()
+            .all(|update| update.gates_total == gates_total));
+        assert_eq!(updates.last().unwrap().gates_done, gates_total);
+    }
+
+    #[tokio::test]
+    async fn test_generator_active_log() {
+        let seed = [0u8; 32];
+        let id = ValueId::new("value");
+        let value = ValueRef::Value { id: id.clone() };
+
+        let mut sink = futures::sink::drain()
+            .sink_map_err(|_| std::io::Error::new(std::io::ErrorKind::UnexpectedEof, ""));
+
+        let gen = Generator::new(GeneratorConfig::default(), seed);
+        gen.generate_input_encoding(&value, &ValueType::U8);
+        gen.direct_send_active_encodings(&[(id.clone(), 1u8.into())], &mut sink)
+            .await
+            .unwrap();
+
+        let log = gen.active_log();
+        assert!(log.contains(&id));
+        drop(gen);
+
+        // A generator with a different delta can not be restored from the log.
+        assert!(matches!(
+            Generator::new_with_log(GeneratorConfig::default(), [1u8; 32], log.clone()),
+            Err(GeneratorError::DeltaMismatch)
+        ));
+
+        let gen = Generator::new_with_log(GeneratorConfig::default(), seed, log).unwrap();
+        gen.generate_input_encoding(&value, &ValueType::U8);
+
+        let err = gen
+            .direct_send_active_encodings(&[(id.clone(), 2u8.into())], &mut sink)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, GeneratorError::ReusedActiveEncoding(_)));
+
+        // Values which were not active before can still be activated.
+        let other_id = ValueId::new("other");
+        gen.generate_input_encoding(
+            &ValueRef::Value {
+                id: other_id.clone(),
+            },
+            &ValueType::U8,
+        );
+        gen.direct_send_active_encodings(&[(other_id.clone(), 3u8.into())], &mut sink)
+            .await
+            .unwrap();
+
+        let log = gen.active_log();
+        assert!(log.contains(&id));
+        assert!(log.contains(&other_id));
+    }
+}
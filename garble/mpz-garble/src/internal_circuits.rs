@@ -1,6 +1,10 @@
 use std::sync::Arc;
 
-use mpz_circuits::{types::ValueType, Circuit, CircuitBuilder, Tracer};
+use mpz_circuits::{
+    circuits::big_num::nbyte_add_mod_le_trace,
+    types::{BinaryRepr, ValueType, U8},
+    Circuit, CircuitBuilder, Tracer,
+};
 
 /// Builds a circuit for applying one-time pads to the provided values.
 pub(crate) fn build_otp_circuit(inputs: &[ValueType]) -> Arc<Circuit> {
@@ -41,3 +45,66 @@ pub(crate) fn build_otp_shared_circuit(inputs: &[ValueType]) -> Arc<Circuit> {
 
     Arc::new(circ)
 }
+
+/// Builds a circuit for applying field-additive one-time pads to secret share the provided values.
+///
+/// Each value is interpreted as a little-endian integer and is added to both pads modulo
+/// the provided modulus.
+///
+/// # Arguments
+///
+/// * `inputs` - The types of the values, which must be byte-aligned.
+/// * `modulus` - The modulus of the field encoded as bytes in little-endian order.
+pub(crate) fn build_otp_shared_field_circuit(inputs: &[ValueType], modulus: &[u8]) -> Arc<Circuit> {
+    let builder = CircuitBuilder::new();
+    let otp_typ = ValueType::Array(Box::new(ValueType::U8), modulus.len());
+
+    for input_ty in inputs {
+        let input = builder.add_input_by_type(input_ty.clone());
+        let otp_0 = builder.add_input_by_type(otp_typ.clone());
+        let otp_1 = builder.add_input_by_type(otp_typ.clone());
+
+        let zero = builder.get_constant(0u8);
+        let mut input: Vec<_> = to_le_bytes(input)
+            .into_iter()
+            .map(|byte| Tracer::new(builder.state(), byte))
+            .collect();
+        input.resize(modulus.len(), zero);
+
+        let otp_0: Vec<_> = to_le_bytes(otp_0)
+            .into_iter()
+            .map(|byte| Tracer::new(builder.state(), byte))
+            .collect();
+        let otp_1: Vec<_> = to_le_bytes(otp_1)
+            .into_iter()
+            .map(|byte| Tracer::new(builder.state(), byte))
+            .collect();
+
+        let masked = nbyte_add_mod_le_trace(builder.state(), &input, &otp_0, modulus);
+        let masked = nbyte_add_mod_le_trace(builder.state(), &masked, &otp_1, modulus);
+
+        builder.add_output(
+            masked
+                .into_iter()
+                .map(|byte| byte.to_inner())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    let circ = builder.build().expect("circuit should be valid");
+
+    Arc::new(circ)
+}
+
+/// Returns the bytes of a byte-aligned binary representation in little-endian order.
+fn to_le_bytes(repr: BinaryRepr) -> Vec<U8> {
+    match repr {
+        BinaryRepr::U8(v) => vec![v],
+        BinaryRepr::U16(v) => v.to_le_bytes().to_vec(),
+        BinaryRepr::U32(v) => v.to_le_bytes().to_vec(),
+        BinaryRepr::U64(v) => v.to_le_bytes().to_vec(),
+        BinaryRepr::U128(v) => v.to_le_bytes().to_vec(),
+        BinaryRepr::Array(v) => v.into_iter().flat_map(to_le_bytes).collect(),
+        repr => panic!("value type is not byte-aligned: {}", repr.value_type()),
+    }
+}
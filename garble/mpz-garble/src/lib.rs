@@ -25,7 +25,9 @@ mod threadpool;
 pub mod value;
 
 pub use evaluator::{Evaluator, EvaluatorConfig, EvaluatorConfigBuilder, EvaluatorError};
-pub use generator::{Generator, GeneratorConfig, GeneratorConfigBuilder, GeneratorError};
+pub use generator::{
+    ActiveIdLog, GenProgress, Generator, GeneratorConfig, GeneratorConfigBuilder, GeneratorError,
+};
 pub use memory::{AssignedValues, ValueMemory};
 pub use threadpool::ThreadPool;
 
@@ -119,6 +121,14 @@ pub enum ExecutionError {
     IOError(#[from] std::io::Error),
     #[error(transparent)]
     ProtocolError(#[from] Box<dyn std::error::Error + Send + Sync>),
+    #[error("circuit expects {expected} inputs, but {actual} were provided")]
+    InputCountMismatch { expected: usize, actual: usize },
+    #[error("input {index} has type {actual:?}, but the circuit expects {expected:?}")]
+    InputMismatch {
+        index: usize,
+        expected: ValueType,
+        actual: ValueType,
+    },
 }
 
 /// Errors that can occur when proving the output of a circuit.
@@ -162,6 +172,12 @@ pub trait Vm {
     /// Creates a new thread.
     async fn new_thread(&mut self, id: &str) -> Result<Self::Thread, VmError>;
 
+    /// Shuts down the vm.
+    ///
+    /// After shutdown the vm no longer creates new threads, returning [`VmError::Shutdown`]
+    /// instead. Threads which have already been created can complete their operations.
+    async fn shutdown(&mut self) -> Result<(), VmError>;
+
     /// Creates a new thread pool.
     ///
     /// A thread pool must have at least one thread.
@@ -357,6 +373,15 @@ pub trait Prove {
 
     /// Proves the the authenticity and correctness of the provided values.
     async fn prove(&mut self, values: &[ValueRef]) -> Result<(), ProveError>;
+
+    /// Executes the provided circuit as the prover, proving the outputs and returning them
+    /// to the prover.
+    async fn execute_and_prove(
+        &mut self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<Vec<Value>, ProveError>;
 }
 
 /// This trait provides methods for verifying the authenticity and correctness of the output of a
@@ -377,6 +402,16 @@ pub trait Verify {
         values: &[ValueRef],
         expected_values: &[Value],
     ) -> Result<(), VerifyError>;
+
+    /// Executes the provided circuit as the verifier, verifying the outputs against the
+    /// expected values.
+    async fn execute_and_verify(
+        &mut self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        expected_outputs: &[Value],
+    ) -> Result<(), VerifyError>;
 }
 
 /// This trait provides methods for decoding values.
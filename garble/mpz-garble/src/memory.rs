@@ -10,7 +10,7 @@ use crate::{
 };
 
 /// Collection of assigned values.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AssignedValues {
     /// Public values.
     pub public: Vec<(ValueId, Value)>,
@@ -20,6 +20,7 @@ pub struct AssignedValues {
     pub blind: Vec<(ValueId, ValueType)>,
 }
 
+#[derive(Clone)]
 enum AssignedValue {
     Public(Value),
     Private(Value),
@@ -58,11 +59,32 @@ pub struct ValueMemory {
     assigned: HashSet<ValueId>,
     /// Buffer containing assigned values
     assigned_buffer: HashMap<ValueId, AssignedValue>,
+    /// Namespace prepended to the ID of each value
+    namespace: Option<String>,
 }
 
 opaque_debug::implement!(ValueMemory);
 
 impl ValueMemory {
+    /// Creates a new memory which prepends `namespace` to the ID of each value.
+    ///
+    /// Values are still referred to by their un-namespaced IDs, the namespace only affects
+    /// the underlying [`ValueId`]s, and thus the encodings derived from them.
+    pub fn new_with_namespace(namespace: &str) -> Self {
+        Self {
+            namespace: Some(namespace.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the value ID for the provided ID.
+    fn value_id(&self, id: &str) -> ValueId {
+        match &self.namespace {
+            Some(namespace) => ValueId::new(&format!("{namespace}/{id}")),
+            None => ValueId::new(id),
+        }
+    }
+
     /// Adds a new input value to the memory.
     ///
     /// # Arguments
@@ -76,7 +98,7 @@ impl ValueMemory {
         typ: ValueType,
         visibility: Visibility,
     ) -> Result<ValueRef, MemoryError> {
-        let value_id = ValueId::new(id);
+        let value_id = self.value_id(id);
         let value_ref = if let ValueType::Array(typ, len) = typ {
             let typ = *typ;
             let mut ids = Vec::with_capacity(len);
@@ -141,7 +163,7 @@ impl ValueMemory {
     /// * `id` - The ID of the value.
     /// * `typ` - The type of the value.
     pub fn new_output(&mut self, id: &str, typ: ValueType) -> Result<ValueRef, MemoryError> {
-        let value_id = ValueId::new(id);
+        let value_id = self.value_id(id);
         let value_ref = if let ValueType::Array(typ, len) = typ {
             let typ = *typ;
             let mut ids = Vec::with_capacity(len);
@@ -275,28 +297,61 @@ impl ValueMemory {
         }
     }
 
+    /// Returns the visibility of an input value, or `None` if the value is an output.
+    pub fn get_visibility(&self, value_ref: &ValueRef) -> Option<Visibility> {
+        let id = match value_ref {
+            ValueRef::Array(array) => &array.ids()[0],
+            ValueRef::Value { id } => id,
+        };
+
+        match self
+            .details
+            .get(id)
+            .expect("value is defined if reference exists")
+        {
+            ValueDetails::Input { visibility, .. } => Some(*visibility),
+            ValueDetails::Output { .. } => None,
+        }
+    }
+
+    /// Returns a snapshot of the assigned values in the buffer, without removing them.
+    ///
+    /// The returned values are the same as those which would be returned by
+    /// [`drain_assigned`](Self::drain_assigned).
+    pub fn peek_assigned(&self, values: &[ValueRef]) -> AssignedValues {
+        collect_assigned(values, |id| self.assigned_buffer.get(id).cloned())
+    }
+
     /// Drains assigned values from buffer if they are present.
     ///
     /// Returns a tuple of public, private, and blind values.
     pub fn drain_assigned(&mut self, values: &[ValueRef]) -> AssignedValues {
-        let mut public = Vec::new();
-        let mut private = Vec::new();
-        let mut blind = Vec::new();
-        for id in values.iter().flat_map(|value| value.iter()) {
-            if let Some(value) = self.assigned_buffer.remove(id) {
-                match value {
-                    AssignedValue::Public(v) => public.push((id.clone(), v)),
-                    AssignedValue::Private(v) => private.push((id.clone(), v)),
-                    AssignedValue::Blind(v) => blind.push((id.clone(), v)),
-                }
+        collect_assigned(values, |id| self.assigned_buffer.remove(id))
+    }
+}
+
+/// Collects the assigned values for the provided references using `get`.
+fn collect_assigned(
+    values: &[ValueRef],
+    mut get: impl FnMut(&ValueId) -> Option<AssignedValue>,
+) -> AssignedValues {
+    let mut public = Vec::new();
+    let mut private = Vec::new();
+    let mut blind = Vec::new();
+    for id in values.iter().flat_map(|value| value.iter()) {
+        if let Some(value) = get(id) {
+            match value {
+                AssignedValue::Public(v) => public.push((id.clone(), v)),
+                AssignedValue::Private(v) => private.push((id.clone(), v)),
+                AssignedValue::Blind(v) => blind.push((id.clone(), v)),
             }
         }
+    }
 
-        AssignedValues {
-            public,
-            private,
-            blind,
-        }
+    AssignedValues {
+        public,
+        private,
+        blind,
     }
 }
 
@@ -509,6 +564,38 @@ mod tests {
         assert!(matches!(err, MemoryError::DuplicateValueId(_)));
     }
 
+    #[test]
+    fn test_value_memory_peek_assigned() {
+        let mut memory = ValueMemory::default();
+
+        let a = memory
+            .new_input("a", ValueType::U8, Visibility::Public)
+            .unwrap();
+        let b = memory
+            .new_input("b", <[u32; 4]>::value_type(), Visibility::Private)
+            .unwrap();
+        let c = memory
+            .new_input("c", ValueType::U64, Visibility::Blind)
+            .unwrap();
+
+        memory.assign(&a, 1u8.into()).unwrap();
+        memory.assign(&b, [1u32, 2, 3, 4].into()).unwrap();
+
+        let values = [a, b, c];
+
+        let peeked = memory.peek_assigned(&values);
+        assert_eq!(peeked.public.len(), 1);
+        assert_eq!(peeked.private.len(), 4);
+        assert_eq!(peeked.blind.len(), 1);
+        assert_eq!(memory.peek_assigned(&values), peeked);
+
+        let drained = memory.drain_assigned(&values);
+        assert_eq!(peeked, drained);
+
+        let empty = memory.peek_assigned(&values);
+        assert!(empty.public.is_empty() && empty.private.is_empty() && empty.blind.is_empty());
+    }
+
     #[rstest]
     #[case::bit(PhantomData::<bool>)]
     #[case::u8(PhantomData::<u8>)]
@@ -1,3 +1,4 @@
+use mpz_circuits::types::ValueType;
 use mpz_garble_core::{msg::GarbleMessage, ValueError};
 
 use crate::{value::ValueRef, DecodeError, ExecutionError, LoadError, ProveError, VerifyError};
@@ -22,6 +23,22 @@ pub enum DEAPError {
     ValueDoesNotExist(ValueRef),
     #[error("missing encoding for value: {0:?}")]
     MissingEncoding(ValueRef),
+    #[error("peer loaded a different circuit")]
+    CircuitMismatch,
+    #[error("circuit expects {expected} inputs, but {actual} were provided")]
+    InputCountMismatch { expected: usize, actual: usize },
+    #[error("input {index} has type {actual:?}, but the circuit expects {expected:?}")]
+    InputMismatch {
+        index: usize,
+        expected: ValueType,
+        actual: ValueType,
+    },
+    #[error("no circuit was executed under operation id: {0}")]
+    UnknownOperationId(String),
+    #[error("operation id was already used: {0}")]
+    DuplicateOperationId(String),
+    #[error("can not decode value into field shares: {0}")]
+    InvalidFieldDecoding(String),
     #[error(transparent)]
     FinalizationError(#[from] FinalizationError),
 }
@@ -65,6 +82,18 @@ impl From<DEAPError> for ExecutionError {
     fn from(err: DEAPError) -> Self {
         match err {
             DEAPError::IOError(err) => ExecutionError::IOError(err),
+            DEAPError::InputCountMismatch { expected, actual } => {
+                ExecutionError::InputCountMismatch { expected, actual }
+            }
+            DEAPError::InputMismatch {
+                index,
+                expected,
+                actual,
+            } => ExecutionError::InputMismatch {
+                index,
+                expected,
+                actual,
+            },
             err => ExecutionError::ProtocolError(Box::new(err)),
         }
     }
@@ -5,15 +5,17 @@
 mod error;
 mod memory;
 pub mod mock;
+mod plan;
 mod vm;
 
 use std::{
-    collections::HashMap,
+    collections::{hash_map::Entry, HashMap, HashSet},
     ops::DerefMut,
     sync::{Arc, Mutex},
 };
 
 use futures::{Sink, SinkExt, Stream, StreamExt, TryFutureExt};
+use itybity::IntoBits;
 use mpz_circuits::{
     types::{Value, ValueType},
     Circuit,
@@ -21,22 +23,27 @@ use mpz_circuits::{
 use mpz_core::{
     commit::{Decommitment, HashCommit},
     hash::{Hash, SecureHash},
+    Block,
 };
+use mpz_fields::Field;
 use mpz_garble_core::{msg::GarbleMessage, EqualityCheck};
-use rand::thread_rng;
+use rand::{thread_rng, Rng};
 use utils_aio::expect_msg_or_err;
 
 use crate::{
     config::{Role, Visibility},
     evaluator::{Evaluator, EvaluatorConfigBuilder},
     generator::{Generator, GeneratorConfigBuilder},
-    internal_circuits::{build_otp_circuit, build_otp_shared_circuit},
+    internal_circuits::{
+        build_otp_circuit, build_otp_shared_circuit, build_otp_shared_field_circuit,
+    },
     memory::ValueMemory,
     ot::{OTReceiveEncoding, OTSendEncoding, OTVerifyEncoding},
     value::ValueRef,
 };
 
 pub use error::{DEAPError, PeerEncodingsError};
+pub use plan::CostPlan;
 pub use vm::{DEAPThread, DEAPVm, PeerEncodings};
 
 use self::error::FinalizationError;
@@ -48,13 +55,27 @@ pub struct DEAP {
     gen: Generator,
     ev: Evaluator,
     state: Mutex<State>,
+    epoch: u64,
     finalized: bool,
 }
 
+/// The authenticity status of a decoded value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Authenticity {
+    /// The value is guaranteed to be authentic.
+    Authenticated,
+    /// The value is assumed to be authentic, but is not verified until
+    /// [`finalize`](DEAP::finalize) is called.
+    PendingFinalization,
+}
+
 #[derive(Debug, Default)]
 struct State {
     memory: ValueMemory,
 
+    /// IDs of the operations which have been executed
+    operation_ids: HashSet<String>,
+
     /// Equality check decommitments withheld by the leader
     /// prior to finalization
     ///
@@ -73,6 +94,8 @@ struct State {
     ///
     /// Operation ID => (Expected GC output hash, hash commitment from leader)
     proof_commitments: HashMap<String, (Hash, Hash)>,
+    /// The estimated cost of the planned operations
+    plan: CostPlan,
 }
 
 struct FinalizedState {
@@ -91,6 +114,17 @@ struct FinalizedState {
 impl DEAP {
     /// Creates a new DEAP protocol instance.
     pub fn new(role: Role, encoder_seed: [u8; 32]) -> Self {
+        Self::new_with_epoch(role, encoder_seed, 0)
+    }
+
+    /// Creates a new DEAP protocol instance for the provided session epoch.
+    ///
+    /// The epoch namespaces the IDs of all values, including those derived from operation
+    /// IDs, so that sessions sharing an encoder seed derive fresh encodings even if they
+    /// reuse the same ID strings. Callers which reuse an encoder seed across sessions must
+    /// use a monotonically increasing epoch for each session, and both parties must agree
+    /// on it. Epoch 0 does not namespace IDs, matching [`DEAP::new`].
+    pub fn new_with_epoch(role: Role, encoder_seed: [u8; 32], epoch: u64) -> Self {
         let mut gen_config_builder = GeneratorConfigBuilder::default();
         let mut ev_config_builder = EvaluatorConfigBuilder::default();
 
@@ -117,15 +151,128 @@ impl DEAP {
             role,
             gen,
             ev,
-            state: Mutex::new(State::default()),
+            state: Mutex::new(State {
+                memory: match epoch {
+                    0 => ValueMemory::default(),
+                    epoch => ValueMemory::new_with_namespace(&format!("epoch/{epoch}")),
+                },
+                ..Default::default()
+            }),
+            epoch,
             finalized: false,
         }
     }
 
+    /// Negotiates the roles of the two parties with the peer, and creates a new DEAP protocol
+    /// instance with the assigned role.
+    ///
+    /// If both parties state a preference, the preferences must be complementary. If only one
+    /// party states a preference, it is assigned that role. Otherwise, the roles are assigned
+    /// using a coin-toss, so neither party can bias which role it is assigned.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the peer.
+    /// * `stream` - The stream to receive messages from the peer.
+    /// * `preferred` - The preferred role of this party, if any.
+    /// * `encoder_seed` - The encoder seed of the new instance.
+    pub async fn negotiate<
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    >(
+        sink: &mut T,
+        stream: &mut U,
+        preferred: Option<Role>,
+        encoder_seed: [u8; 32],
+    ) -> Result<Self, DEAPError> {
+        sink.send(GarbleMessage::RolePreference(
+            preferred.map(|role| role == Role::Leader),
+        ))
+        .await?;
+
+        let peer_preferred = expect_msg_or_err!(stream, GarbleMessage::RolePreference)?
+            .map(|leader| if leader { Role::Leader } else { Role::Follower });
+
+        let role = match (preferred, peer_preferred) {
+            (Some(role), Some(peer_role)) if role != peer_role => role,
+            (Some(role), Some(_)) => {
+                return Err(DEAPError::RoleError(format!(
+                    "both parties prefer the {role:?} role"
+                )))
+            }
+            (Some(role), None) => role,
+            (None, Some(Role::Leader)) => Role::Follower,
+            (None, Some(Role::Follower)) => Role::Leader,
+            (None, None) => {
+                // Commit to a seed before revealing it, so that neither party can choose its
+                // seed depending on the other's.
+                let seed: Block = thread_rng().gen();
+                let (decommitment, commitment) = seed.hash_commit();
+
+                sink.send(GarbleMessage::HashCommitment(commitment)).await?;
+                let peer_commitment = expect_msg_or_err!(stream, GarbleMessage::HashCommitment)?;
+
+                sink.send(GarbleMessage::SeedDecommitment(decommitment))
+                    .await?;
+                let peer_decommitment =
+                    expect_msg_or_err!(stream, GarbleMessage::SeedDecommitment)?;
+
+                peer_decommitment
+                    .verify(&peer_commitment)
+                    .map_err(|err| DEAPError::RoleError(err.to_string()))?;
+
+                let peer_seed = *peer_decommitment.data();
+
+                // The coin is unbiased as long as one of the seeds is random, and the party
+                // with the smaller seed is assigned the leader role if the coin is zero.
+                let coin = (seed ^ peer_seed).lsb() == 1;
+                let smaller = match seed.to_bytes().cmp(&peer_seed.to_bytes()) {
+                    std::cmp::Ordering::Less => true,
+                    std::cmp::Ordering::Greater => false,
+                    std::cmp::Ordering::Equal => {
+                        return Err(DEAPError::RoleError(
+                            "peer revealed the same seed".to_string(),
+                        ))
+                    }
+                };
+
+                if smaller != coin {
+                    Role::Leader
+                } else {
+                    Role::Follower
+                }
+            }
+        };
+
+        Ok(Self::new(role, encoder_seed))
+    }
+
     fn state(&self) -> impl DerefMut<Target = State> + '_ {
         self.state.lock().unwrap()
     }
 
+    /// Returns the session epoch of this instance.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Checks that a circuit was executed under the provided operation ID.
+    fn check_operation_id(&self, id: &str) -> Result<(), DEAPError> {
+        if !self.state().operation_ids.contains(id) {
+            return Err(DEAPError::UnknownOperationId(id.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the authenticity status of values decoded by this party.
+    pub(crate) fn authenticity(&self) -> Authenticity {
+        match self.role {
+            Role::Leader => Authenticity::Authenticated,
+            Role::Follower => Authenticity::PendingFinalization,
+        }
+    }
+
     /// Performs pre-processing for executing the provided circuit.
     ///
     /// # Arguments
@@ -147,6 +294,8 @@ impl DEAP {
         T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
         U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
     {
+        self.check_circuit(&circ, sink, stream).await?;
+
         // Generate and receive concurrently.
         // Drop the encoded outputs, we don't need them here
         _ = futures::try_join!(
@@ -161,6 +310,55 @@ impl DEAP {
         Ok(())
     }
 
+    /// Returns the estimated cost of the operations recorded with
+    /// [`plan_execute`](Self::plan_execute) and [`plan_decode`](Self::plan_decode).
+    ///
+    /// Planning does not perform any I/O, and can be used to estimate the cost of
+    /// a sequence of operations prior to running them.
+    pub fn plan(&self) -> CostPlan {
+        self.state().plan
+    }
+
+    /// Records a planned execution of a circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to execute.
+    /// * `inputs` - The inputs to the circuit.
+    pub fn plan_execute(&self, circ: &Circuit, inputs: &[ValueRef]) {
+        let mut state = self.state();
+
+        let inputs = inputs
+            .iter()
+            .filter_map(|input| {
+                state
+                    .memory
+                    .get_visibility(input)
+                    .map(|visibility| (state.memory.get_value_type(input), visibility))
+            })
+            .collect::<Vec<_>>();
+
+        state
+            .plan
+            .record_execute(self.role, circ, self.gen.batch_size(), inputs);
+    }
+
+    /// Records a planned decoding of values.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The values to decode.
+    pub fn plan_decode(&self, values: &[ValueRef]) {
+        let mut state = self.state();
+
+        let types = values
+            .iter()
+            .map(|value| state.memory.get_value_type(value))
+            .collect::<Vec<_>>();
+
+        state.plan.record_decode(self.role, types);
+    }
+
     /// Executes a circuit.
     ///
     /// # Arguments
@@ -191,7 +389,14 @@ impl DEAP {
         OTS: OTSendEncoding,
         OTR: OTReceiveEncoding,
     {
-        let assigned_values = self.state().memory.drain_assigned(inputs);
+        self.check_inputs(&circ, inputs)?;
+        self.check_circuit(&circ, sink, stream).await?;
+
+        let assigned_values = {
+            let mut state = self.state();
+            state.operation_ids.insert(id.to_string());
+            state.memory.drain_assigned(inputs)
+        };
 
         let id_0 = format!("{}/0", id);
         let id_1 = format!("{}/1", id);
@@ -225,6 +430,58 @@ impl DEAP {
         Ok(())
     }
 
+    /// Checks that the provided inputs match the arity and types expected by the circuit.
+    fn check_inputs(&self, circ: &Circuit, inputs: &[ValueRef]) -> Result<(), DEAPError> {
+        if inputs.len() != circ.inputs().len() {
+            return Err(DEAPError::InputCountMismatch {
+                expected: circ.inputs().len(),
+                actual: inputs.len(),
+            });
+        }
+
+        let state = self.state();
+        for (index, (input, expected)) in inputs.iter().zip(circ.inputs()).enumerate() {
+            let expected = expected.value_type();
+            let actual = state.memory.get_value_type(input);
+
+            if actual != expected {
+                return Err(DEAPError::InputMismatch {
+                    index,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the peer is using the same circuit.
+    ///
+    /// Both parties send the structural hash of their circuit and compare it
+    /// with the one received from their peer.
+    async fn check_circuit<T, U>(
+        &self,
+        circ: &Circuit,
+        sink: &mut T,
+        stream: &mut U,
+    ) -> Result<(), DEAPError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    {
+        let hash = circ.structural_hash();
+
+        sink.send(GarbleMessage::CircuitHash(hash)).await?;
+        let peer_hash = expect_msg_or_err!(stream, GarbleMessage::CircuitHash)?;
+
+        if hash != peer_hash {
+            return Err(DEAPError::CircuitMismatch);
+        }
+
+        Ok(())
+    }
+
     /// Proves the output of a circuit to the other party.
     ///
     /// # Notes
@@ -264,7 +521,11 @@ impl DEAP {
             ))?;
         }
 
-        let assigned_values = self.state().memory.drain_assigned(inputs);
+        let assigned_values = {
+            let mut state = self.state();
+            state.operation_ids.insert(id.to_string());
+            state.memory.drain_assigned(inputs)
+        };
 
         // The prover only acts as the evaluator for ZKPs instead of
         // dual-execution.
@@ -315,7 +576,11 @@ impl DEAP {
             ))?;
         }
 
-        let assigned_values = self.state().memory.drain_assigned(inputs);
+        let assigned_values = {
+            let mut state = self.state();
+            state.operation_ids.insert(id.to_string());
+            state.memory.drain_assigned(inputs)
+        };
 
         // The verifier only acts as the generator for ZKPs instead of
         // dual-execution.
@@ -332,6 +597,101 @@ impl DEAP {
         Ok(())
     }
 
+    /// Executes the circuit as the prover, proving the outputs to the follower upon
+    /// finalization and returning the outputs to the prover.
+    ///
+    /// # Notes
+    ///
+    /// This function can only be called by the leader, and must be paired with
+    /// [`execute_and_verify`](Self::execute_and_verify) by the follower.
+    ///
+    /// The prover commits to the outputs before learning them, so the proof is bound to the
+    /// inputs used in the execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the circuit.
+    /// * `circ` - The circuit to execute.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs to the circuit.
+    /// * `sink` - The sink to send messages to.
+    /// * `stream` - The stream to receive messages from.
+    /// * `ot_recv` - The OT receiver.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn execute_and_prove<T, S, OTR>(
+        &self,
+        id: &str,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        sink: &mut T,
+        stream: &mut S,
+        ot_recv: &OTR,
+    ) -> Result<Vec<Value>, DEAPError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        S: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+        OTR: OTReceiveEncoding,
+    {
+        self.execute_prove(id, circ, inputs, outputs, stream, ot_recv)
+            .await?;
+
+        self.defer_prove(id, outputs, sink).await?;
+
+        self.ev
+            .decode(outputs, stream)
+            .map_err(DEAPError::from)
+            .await
+    }
+
+    /// Executes the circuit as the verifier, verifying the outputs against the expected
+    /// values upon finalization.
+    ///
+    /// # Notes
+    ///
+    /// This function can only be called by the follower, and must be paired with
+    /// [`execute_and_prove`](Self::execute_and_prove) by the leader.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the circuit.
+    /// * `circ` - The circuit to execute.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs to the circuit.
+    /// * `expected_outputs` - The expected values of the outputs.
+    /// * `sink` - The sink to send messages to.
+    /// * `stream` - The stream to receive messages from.
+    /// * `ot_send` - The OT sender.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn execute_and_verify<T, S, OTS>(
+        &self,
+        id: &str,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        expected_outputs: &[Value],
+        sink: &mut T,
+        stream: &mut S,
+        ot_send: &OTS,
+    ) -> Result<(), DEAPError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        S: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+        OTS: OTSendEncoding,
+    {
+        self.execute_verify(id, circ, inputs, outputs, sink, ot_send)
+            .await?;
+
+        self.defer_verify(id, outputs, expected_outputs, stream)
+            .await?;
+
+        // Reveal the outputs to the prover only after it has committed to them.
+        self.gen
+            .decode(outputs, sink)
+            .map_err(DEAPError::from)
+            .await
+    }
+
     /// Sends a commitment to the provided values, proving them to the follower upon finalization.
     pub async fn defer_prove<S: Sink<GarbleMessage, Error = std::io::Error> + Unpin>(
         &self,
@@ -409,6 +769,11 @@ impl DEAP {
     /// * `values` - The values to decode
     /// * `sink` - The sink to send messages to.
     /// * `stream` - The stream to receive messages from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DEAPError::UnknownOperationId`] if no circuit was executed under `id`, and
+    /// [`DEAPError::DuplicateOperationId`] if values were already decoded under `id`.
     pub async fn decode<T, U>(
         &self,
         id: &str,
@@ -420,6 +785,41 @@ impl DEAP {
         T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
         U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
     {
+        self.check_operation_id(id)?;
+
+        self.decode_unchecked(id, values, sink, stream).await
+    }
+
+    /// Decodes the provided values without checking that a circuit was executed under `id`.
+    ///
+    /// This is used by the VM, which allocates a fresh operation ID for each decoding.
+    ///
+    /// See [`decode`](Self::decode) for more information.
+    pub(crate) async fn decode_unchecked<T, U>(
+        &self,
+        id: &str,
+        values: &[ValueRef],
+        sink: &mut T,
+        stream: &mut U,
+    ) -> Result<Vec<Value>, DEAPError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    {
+        // Reject a reused ID before communicating, otherwise the equality check of the
+        // earlier decoding would be overwritten.
+        {
+            let state = self.state();
+            let is_duplicate = match self.role {
+                Role::Leader => state.eq_decommitments.contains_key(id),
+                Role::Follower => state.eq_commitments.contains_key(id),
+            };
+
+            if is_duplicate {
+                return Err(DEAPError::DuplicateOperationId(id.to_string()));
+            }
+        }
+
         let full = values
             .iter()
             .map(|value| {
@@ -459,9 +859,7 @@ impl DEAP {
                 let (decommitment, commit) = eq_check.hash_commit();
 
                 // Store equality check decommitment until finalization
-                self.state()
-                    .eq_decommitments
-                    .insert(id.to_string(), decommitment);
+                insert_unique(&mut self.state().eq_decommitments, id, decommitment)?;
 
                 // Send commitment to equality check to follower
                 sink.send(GarbleMessage::HashCommitment(commit)).await?;
@@ -481,9 +879,7 @@ impl DEAP {
                 let commit = expect_msg_or_err!(stream, GarbleMessage::HashCommitment)?;
 
                 // Store equality check commitment until finalization
-                self.state()
-                    .eq_commitments
-                    .insert(id.to_string(), (eq_check, commit));
+                insert_unique(&mut self.state().eq_commitments, id, (eq_check, commit))?;
 
                 // Send active encoded values to leader
                 sink.send(GarbleMessage::ActiveValues(active)).await?;
@@ -496,6 +892,38 @@ impl DEAP {
         Ok(output)
     }
 
+    /// Decodes the provided values, revealing the plaintext value to both parties
+    /// along with the authenticity status of each value.
+    ///
+    /// See [`decode`](Self::decode) for more information.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the operation
+    /// * `values` - The values to decode
+    /// * `sink` - The sink to send messages to.
+    /// * `stream` - The stream to receive messages from.
+    pub async fn decode_checked<T, U>(
+        &self,
+        id: &str,
+        values: &[ValueRef],
+        sink: &mut T,
+        stream: &mut U,
+    ) -> Result<Vec<(Value, Authenticity)>, DEAPError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    {
+        let authenticity = self.authenticity();
+
+        let output = self.decode(id, values, sink, stream).await?;
+
+        Ok(output
+            .into_iter()
+            .map(|value| (value, authenticity))
+            .collect())
+    }
+
     pub(crate) async fn decode_private<T, U, OTS, OTR>(
         &self,
         id: &str,
@@ -690,41 +1118,165 @@ impl DEAP {
         }
     }
 
-    /// Finalize the DEAP instance.
-    ///
-    /// If this instance is the leader, this function will return the follower's
-    /// encoder seed.
-    ///
-    /// # Notes
+    /// Decodes the provided values, returning additive shares of the plaintext values
+    /// over the field `F` to both parties.
     ///
-    /// **This function will reveal all private inputs of the follower.**
-    ///
-    /// The follower reveals all his secrets to the leader, who can then verify
-    /// that all oblivious transfers, circuit garbling, and value decoding was
-    /// performed correctly.
-    ///
-    /// After the leader has verified everything, they decommit to all equality checks
-    /// and ZK proofs from the session. The follower then verifies the decommitments
-    /// and that all the equality checks and proofs were performed as expected.
+    /// Each value is interpreted as a little-endian integer, and must be strictly smaller
+    /// than [`Field::BIT_SIZE`] bits so that it is a valid field element. Only prime fields
+    /// are supported, for binary fields use [`decode_shared`](Self::decode_shared).
     ///
     /// # Arguments
     ///
-    /// - `channel` - The channel to communicate with the other party
-    /// - `ot` - The OT verifier to use
-    pub async fn finalize<
-        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
-        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
-        OT: OTVerifyEncoding,
-    >(
-        &mut self,
+    /// * `id` - The ID of the operation
+    /// * `values` - The values to decode
+    /// * `sink` - The sink to send messages to.
+    /// * `stream` - The stream to receive messages from.
+    /// * `ot_send` - The OT sender.
+    /// * `ot_recv` - The OT receiver.
+    pub(crate) async fn decode_shared_field<F, T, U, OTS, OTR>(
+        &self,
+        id: &str,
+        values: &[ValueRef],
         sink: &mut T,
         stream: &mut U,
-        ot: &OT,
-    ) -> Result<Option<[u8; 32]>, DEAPError> {
-        if self.finalized {
-            return Err(FinalizationError::AlreadyFinalized)?;
-        } else {
-            self.finalized = true;
+        ot_send: &OTS,
+        ot_recv: &OTR,
+    ) -> Result<Vec<F>, DEAPError>
+    where
+        F: Field,
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+        OTS: OTSendEncoding,
+        OTR: OTReceiveEncoding,
+    {
+        if F::one() + F::one() == F::zero() {
+            return Err(DEAPError::InvalidFieldDecoding(
+                "fields with characteristic 2 are not supported".to_string(),
+            ));
+        }
+
+        let modulus = F::MODULUS_LE_BYTES;
+
+        #[allow(clippy::type_complexity)]
+        let ((((otp_0_refs, otp_1_refs), typs), otp_values), mask_refs): (
+            (((Vec<_>, Vec<_>), Vec<_>), Vec<_>),
+            Vec<_>,
+        ) = {
+            let mut state = self.state();
+
+            let typs = values
+                .iter()
+                .map(|value| state.memory.get_value_type(value))
+                .collect::<Vec<_>>();
+
+            for typ in &typs {
+                if !is_byte_aligned(typ) || typ.len() >= F::BIT_SIZE as usize {
+                    return Err(DEAPError::InvalidFieldDecoding(format!(
+                        "value of type {typ} can not be represented as a field element"
+                    )));
+                }
+            }
+
+            typs.into_iter()
+                .enumerate()
+                .map(|(idx, typ)| {
+                    let (otp_0_ref, otp_1_ref, otp_value, otp_typ) = match self.role {
+                        Role::Leader => {
+                            let (otp_0_ref, otp_value) = state
+                                .new_private_field_otp::<F>(&format!("{id}/{idx}/otp_0"), modulus);
+                            let (otp_1_ref, otp_typ) =
+                                state.new_blind_field_otp(&format!("{id}/{idx}/otp_1"), modulus);
+                            (otp_0_ref, otp_1_ref, otp_value, otp_typ)
+                        }
+                        Role::Follower => {
+                            let (otp_0_ref, otp_typ) =
+                                state.new_blind_field_otp(&format!("{id}/{idx}/otp_0"), modulus);
+                            let (otp_1_ref, otp_value) = state
+                                .new_private_field_otp::<F>(&format!("{id}/{idx}/otp_1"), modulus);
+                            (otp_0_ref, otp_1_ref, otp_value, otp_typ)
+                        }
+                    };
+                    let mask_ref = state
+                        .memory
+                        .new_output(&format!("{id}/{idx}/mask"), otp_typ.clone())
+                        .expect("mask id is unique");
+                    self.gen.generate_input_encoding(&otp_0_ref, &otp_typ);
+                    self.gen.generate_input_encoding(&otp_1_ref, &otp_typ);
+                    ((((otp_0_ref, otp_1_ref), typ), otp_value), mask_ref)
+                })
+                .unzip()
+        };
+
+        // Apply OTPs to values
+        let circ = build_otp_shared_field_circuit(&typs, modulus);
+
+        let inputs = values
+            .iter()
+            .zip(&otp_0_refs)
+            .zip(&otp_1_refs)
+            .flat_map(|((value, otp_0), otp_1)| [value, otp_0, otp_1])
+            .cloned()
+            .collect::<Vec<_>>();
+
+        self.execute(
+            id, circ, &inputs, &mask_refs, sink, stream, ot_send, ot_recv,
+        )
+        .await?;
+
+        // Decode masked values
+        let masked_values = self.decode(id, &mask_refs, sink, stream).await?;
+
+        match self.role {
+            Role::Leader => {
+                // Leader removes his OTP
+                Ok(masked_values
+                    .into_iter()
+                    .zip(otp_values)
+                    .map(|(masked, otp)| F::from_lsb0_iter(masked.into_iter_lsb0()) + -otp)
+                    .collect::<Vec<_>>())
+            }
+            Role::Follower => {
+                // Follower uses his negated OTP as his share
+                Ok(otp_values.into_iter().map(|otp| -otp).collect())
+            }
+        }
+    }
+
+    /// Finalize the DEAP instance.
+    ///
+    /// If this instance is the leader, this function will return the follower's
+    /// encoder seed.
+    ///
+    /// # Notes
+    ///
+    /// **This function will reveal all private inputs of the follower.**
+    ///
+    /// The follower reveals all his secrets to the leader, who can then verify
+    /// that all oblivious transfers, circuit garbling, and value decoding was
+    /// performed correctly.
+    ///
+    /// After the leader has verified everything, they decommit to all equality checks
+    /// and ZK proofs from the session. The follower then verifies the decommitments
+    /// and that all the equality checks and proofs were performed as expected.
+    ///
+    /// # Arguments
+    ///
+    /// - `channel` - The channel to communicate with the other party
+    /// - `ot` - The OT verifier to use
+    pub async fn finalize<
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+        OT: OTVerifyEncoding,
+    >(
+        &mut self,
+        sink: &mut T,
+        stream: &mut U,
+        ot: &OT,
+    ) -> Result<Option<[u8; 32]>, DEAPError> {
+        if self.finalized {
+            return Err(FinalizationError::AlreadyFinalized)?;
+        } else {
+            self.finalized = true;
         }
 
         let FinalizedState {
@@ -850,6 +1402,44 @@ impl State {
         )
     }
 
+    pub(crate) fn new_private_field_otp<F: Field>(
+        &mut self,
+        id: &str,
+        modulus: &[u8],
+    ) -> (ValueRef, F) {
+        let typ = ValueType::Array(Box::new(ValueType::U8), modulus.len());
+        let otp = F::rand(&mut thread_rng());
+
+        let mut bytes = otp.to_le_bytes();
+        bytes.resize(modulus.len(), 0);
+
+        let value_ref = self
+            .memory
+            .new_input(id, typ, Visibility::Private)
+            .expect("otp id is unique");
+
+        self.memory
+            .assign(&value_ref, bytes.into())
+            .expect("value should assign");
+
+        (value_ref, otp)
+    }
+
+    pub(crate) fn new_blind_field_otp(
+        &mut self,
+        id: &str,
+        modulus: &[u8],
+    ) -> (ValueRef, ValueType) {
+        let typ = ValueType::Array(Box::new(ValueType::U8), modulus.len());
+
+        (
+            self.memory
+                .new_input(id, typ.clone(), Visibility::Blind)
+                .expect("otp id is unique"),
+            typ,
+        )
+    }
+
     pub(crate) fn new_output_mask(&mut self, id: &str, value_ref: &ValueRef) -> ValueRef {
         let typ = self.memory.get_value_type(value_ref);
         self.memory.new_output(id, typ).expect("mask id is unique")
@@ -886,57 +1476,791 @@ impl State {
     }
 }
 
+/// Returns whether a value type is composed of whole bytes.
+fn is_byte_aligned(typ: &ValueType) -> bool {
+    match typ {
+        ValueType::Bit => false,
+        ValueType::Array(typ, _) => is_byte_aligned(typ),
+        _ => true,
+    }
+}
+
+/// Inserts a value into a map keyed by operation ID.
+///
+/// Returns an error if the map already contains the ID, instead of overwriting its value.
+fn insert_unique<V>(map: &mut HashMap<String, V>, id: &str, value: V) -> Result<(), DEAPError> {
+    match map.entry(id.to_string()) {
+        Entry::Occupied(_) => Err(DEAPError::DuplicateOperationId(id.to_string())),
+        Entry::Vacant(entry) => {
+            entry.insert(value);
+            Ok(())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use mpz_circuits::{circuits::AES128, ops::WrappingAdd, CircuitBuilder};
-    use mpz_ot::ideal::ideal_ot_shared_pair;
+    use mpz_circuits::{
+        circuits::AES128,
+        ops::{WrappingAdd, WrappingSub},
+        CircuitBuilder,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use mpz_core::Block;
+    use mpz_fields::p256::P256;
+    use mpz_ot::{ideal::ideal_ot_shared_pair, OTError, OTReceiverShared, OTSenderShared};
     use utils_aio::duplex::MemoryDuplex;
 
     use crate::Memory;
 
     use super::*;
 
-    fn adder_circ() -> Arc<Circuit> {
-        let builder = CircuitBuilder::new();
+    fn adder_circ() -> Arc<Circuit> {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        let c = a.wrapping_add(b);
+
+        builder.add_output(c);
+
+        Arc::new(builder.build().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_deap() {
+        let (leader_channel, follower_channel) = MemoryDuplex::<GarbleMessage>::new();
+        let (leader_ot_send, follower_ot_recv) = ideal_ot_shared_pair();
+        let (follower_ot_send, leader_ot_recv) = ideal_ot_shared_pair();
+
+        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let leader_fut = {
+            let (mut sink, mut stream) = leader_channel.split();
+
+            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader.assign(&key_ref, key).unwrap();
+
+            async move {
+                leader
+                    .execute(
+                        "test",
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                        &mut sink,
+                        &mut stream,
+                        &leader_ot_send,
+                        &leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let outputs = leader
+                    .decode("test", &[ciphertext_ref], &mut sink, &mut stream)
+                    .await
+                    .unwrap();
+
+                leader
+                    .finalize(&mut sink, &mut stream, &leader_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let follower_fut = {
+            let (mut sink, mut stream) = follower_channel.split();
+
+            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            follower.assign(&msg_ref, msg).unwrap();
+
+            async move {
+                follower
+                    .execute(
+                        "test",
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                        &mut sink,
+                        &mut stream,
+                        &follower_ot_send,
+                        &follower_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let outputs = follower
+                    .decode("test", &[ciphertext_ref], &mut sink, &mut stream)
+                    .await
+                    .unwrap();
+
+                follower
+                    .finalize(&mut sink, &mut stream, &follower_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let (leader_output, follower_output) = tokio::join!(leader_fut, follower_fut);
+
+        assert_eq!(leader_output, follower_output);
+    }
+
+    #[tokio::test]
+    async fn test_deap_decode_unknown_operation_id() {
+        let (leader_channel, follower_channel) = MemoryDuplex::<GarbleMessage>::new();
+        let (leader_ot_send, follower_ot_recv) = ideal_ot_shared_pair();
+        let (follower_ot_send, leader_ot_recv) = ideal_ot_shared_pair();
+
+        let leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let leader_fut = {
+            let (mut sink, mut stream) = leader_channel.split();
+
+            let a_ref = leader.new_private_input::<u8>("a").unwrap();
+            let b_ref = leader.new_blind_input::<u8>("b").unwrap();
+            let c_ref = leader.new_output::<u8>("c").unwrap();
+
+            leader.assign(&a_ref, 1u8).unwrap();
+
+            async move {
+                leader
+                    .execute(
+                        "test",
+                        adder_circ(),
+                        &[a_ref, b_ref],
+                        &[c_ref.clone()],
+                        &mut sink,
+                        &mut stream,
+                        &leader_ot_send,
+                        &leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                leader
+                    .decode("tset", &[c_ref], &mut sink, &mut stream)
+                    .await
+            }
+        };
+
+        let follower_fut = {
+            let (mut sink, mut stream) = follower_channel.split();
+
+            let a_ref = follower.new_blind_input::<u8>("a").unwrap();
+            let b_ref = follower.new_private_input::<u8>("b").unwrap();
+            let c_ref = follower.new_output::<u8>("c").unwrap();
+
+            follower.assign(&b_ref, 2u8).unwrap();
+
+            async move {
+                follower
+                    .execute(
+                        "test",
+                        adder_circ(),
+                        &[a_ref, b_ref],
+                        &[c_ref.clone()],
+                        &mut sink,
+                        &mut stream,
+                        &follower_ot_send,
+                        &follower_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                follower
+                    .decode("tset", &[c_ref], &mut sink, &mut stream)
+                    .await
+            }
+        };
+
+        let (leader_result, follower_result) = tokio::join!(leader_fut, follower_fut);
+
+        assert!(matches!(
+            leader_result.unwrap_err(),
+            DEAPError::UnknownOperationId(id) if id == "tset"
+        ));
+        assert!(matches!(
+            follower_result.unwrap_err(),
+            DEAPError::UnknownOperationId(id) if id == "tset"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_deap_decode_duplicate_operation_id() {
+        let (leader_channel, follower_channel) = MemoryDuplex::<GarbleMessage>::new();
+        let (leader_ot_send, follower_ot_recv) = ideal_ot_shared_pair();
+        let (follower_ot_send, leader_ot_recv) = ideal_ot_shared_pair();
+
+        let leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let leader_fut = {
+            let (mut sink, mut stream) = leader_channel.split();
+
+            let a_ref = leader.new_private_input::<u8>("a").unwrap();
+            let b_ref = leader.new_blind_input::<u8>("b").unwrap();
+            let c_ref = leader.new_output::<u8>("c").unwrap();
+
+            leader.assign(&a_ref, 1u8).unwrap();
+
+            async move {
+                leader
+                    .execute(
+                        "test",
+                        adder_circ(),
+                        &[a_ref, b_ref],
+                        &[c_ref.clone()],
+                        &mut sink,
+                        &mut stream,
+                        &leader_ot_send,
+                        &leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                leader
+                    .decode("test", &[c_ref.clone()], &mut sink, &mut stream)
+                    .await
+                    .unwrap();
+
+                leader
+                    .decode("test", &[c_ref], &mut sink, &mut stream)
+                    .await
+            }
+        };
+
+        let follower_fut = {
+            let (mut sink, mut stream) = follower_channel.split();
+
+            let a_ref = follower.new_blind_input::<u8>("a").unwrap();
+            let b_ref = follower.new_private_input::<u8>("b").unwrap();
+            let c_ref = follower.new_output::<u8>("c").unwrap();
+
+            follower.assign(&b_ref, 2u8).unwrap();
+
+            async move {
+                follower
+                    .execute(
+                        "test",
+                        adder_circ(),
+                        &[a_ref, b_ref],
+                        &[c_ref.clone()],
+                        &mut sink,
+                        &mut stream,
+                        &follower_ot_send,
+                        &follower_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                follower
+                    .decode("test", &[c_ref.clone()], &mut sink, &mut stream)
+                    .await
+                    .unwrap();
+
+                follower
+                    .decode("test", &[c_ref], &mut sink, &mut stream)
+                    .await
+            }
+        };
+
+        let (leader_result, follower_result) = tokio::join!(leader_fut, follower_fut);
+
+        assert!(matches!(
+            leader_result.unwrap_err(),
+            DEAPError::DuplicateOperationId(id) if id == "test"
+        ));
+        assert!(matches!(
+            follower_result.unwrap_err(),
+            DEAPError::DuplicateOperationId(id) if id == "test"
+        ));
+    }
+
+    #[test]
+    fn test_deap_epoch() {
+        let session_0 = DEAP::new(Role::Leader, [42u8; 32]);
+        let session_1 = DEAP::new_with_epoch(Role::Leader, [42u8; 32], 1);
+
+        assert_eq!(session_0.epoch(), 0);
+        assert_eq!(session_1.epoch(), 1);
+
+        let encodings = [&session_0, &session_1].map(|deap| {
+            let value_ref = deap.new_private_input::<[u8; 16]>("key").unwrap();
+            let (otp_ref, _) = deap.state().new_private_otp("test/0/otp", &value_ref);
+            deap.gen
+                .generate_input_encoding(&otp_ref, &deap.get_value_type(&otp_ref));
+
+            (
+                deap.gen.get_encoding(&value_ref).unwrap(),
+                deap.gen.get_encoding(&otp_ref).unwrap(),
+            )
+        });
+
+        assert_eq!(encodings[0].0.delta(), encodings[1].0.delta());
+        assert_ne!(encodings[0].0, encodings[1].0);
+        assert_ne!(encodings[0].1, encodings[1].1);
+    }
+
+    #[tokio::test]
+    async fn test_deap_negotiate() {
+        let (channel_a, channel_b) = MemoryDuplex::<GarbleMessage>::new();
+        let (mut sink_a, mut stream_a) = channel_a.split();
+        let (mut sink_b, mut stream_b) = channel_b.split();
+
+        let (a, b) = futures::join!(
+            DEAP::negotiate(&mut sink_a, &mut stream_a, None, [42u8; 32]),
+            DEAP::negotiate(&mut sink_b, &mut stream_b, None, [69u8; 32])
+        );
+
+        let (a, b) = (a.unwrap(), b.unwrap());
+
+        assert_ne!(a.role, b.role);
+
+        let (a, b) = futures::join!(
+            DEAP::negotiate(&mut sink_a, &mut stream_a, None, [42u8; 32]),
+            DEAP::negotiate(&mut sink_b, &mut stream_b, Some(Role::Leader), [69u8; 32])
+        );
+
+        assert_eq!(a.unwrap().role, Role::Follower);
+        assert_eq!(b.unwrap().role, Role::Leader);
+    }
+
+    #[tokio::test]
+    async fn test_deap_negotiate_conflicting_roles() {
+        let (channel_a, channel_b) = MemoryDuplex::<GarbleMessage>::new();
+        let (mut sink_a, mut stream_a) = channel_a.split();
+        let (mut sink_b, mut stream_b) = channel_b.split();
+
+        let (a, b) = futures::join!(
+            DEAP::negotiate(&mut sink_a, &mut stream_a, Some(Role::Leader), [42u8; 32]),
+            DEAP::negotiate(&mut sink_b, &mut stream_b, Some(Role::Leader), [69u8; 32])
+        );
+
+        assert!(matches!(a.unwrap_err(), DEAPError::RoleError(_)));
+        assert!(matches!(b.unwrap_err(), DEAPError::RoleError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_deap_circuit_mismatch() {
+        let (leader_channel, follower_channel) = MemoryDuplex::<GarbleMessage>::new();
+        let (leader_ot_send, follower_ot_recv) = ideal_ot_shared_pair();
+        let (follower_ot_send, leader_ot_recv) = ideal_ot_shared_pair();
+
+        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let sub_circ = {
+            let builder = CircuitBuilder::new();
+
+            let a = builder.add_input::<u8>();
+            let b = builder.add_input::<u8>();
+
+            let c = a.wrapping_sub(b);
+
+            builder.add_output(c);
+
+            Arc::new(builder.build().unwrap())
+        };
+
+        let leader_fut = {
+            let (mut sink, mut stream) = leader_channel.split();
+
+            let a_ref = leader.new_private_input::<u8>("a").unwrap();
+            let b_ref = leader.new_blind_input::<u8>("b").unwrap();
+            let c_ref = leader.new_output::<u8>("c").unwrap();
+
+            leader.assign(&a_ref, 1u8).unwrap();
+
+            async move {
+                leader
+                    .execute(
+                        "test",
+                        adder_circ(),
+                        &[a_ref, b_ref],
+                        &[c_ref],
+                        &mut sink,
+                        &mut stream,
+                        &leader_ot_send,
+                        &leader_ot_recv,
+                    )
+                    .await
+            }
+        };
+
+        let follower_fut = {
+            let (mut sink, mut stream) = follower_channel.split();
+
+            let a_ref = follower.new_blind_input::<u8>("a").unwrap();
+            let b_ref = follower.new_private_input::<u8>("b").unwrap();
+            let c_ref = follower.new_output::<u8>("c").unwrap();
+
+            follower.assign(&b_ref, 2u8).unwrap();
+
+            async move {
+                follower
+                    .execute(
+                        "test",
+                        sub_circ,
+                        &[a_ref, b_ref],
+                        &[c_ref],
+                        &mut sink,
+                        &mut stream,
+                        &follower_ot_send,
+                        &follower_ot_recv,
+                    )
+                    .await
+            }
+        };
+
+        let (leader_res, follower_res) = tokio::join!(leader_fut, follower_fut);
+
+        assert!(matches!(leader_res, Err(DEAPError::CircuitMismatch)));
+        assert!(matches!(follower_res, Err(DEAPError::CircuitMismatch)));
+    }
+
+    #[tokio::test]
+    async fn test_deap_decode_checked() {
+        let (leader_channel, follower_channel) = MemoryDuplex::<GarbleMessage>::new();
+        let (leader_ot_send, follower_ot_recv) = ideal_ot_shared_pair();
+        let (follower_ot_send, leader_ot_recv) = ideal_ot_shared_pair();
+
+        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let circ = adder_circ();
+
+        let leader_fut = {
+            let (mut sink, mut stream) = leader_channel.split();
+            let circ = circ.clone();
+
+            let a_ref = leader.new_private_input::<u8>("a").unwrap();
+            let b_ref = leader.new_blind_input::<u8>("b").unwrap();
+            let c_ref = leader.new_output::<u8>("c").unwrap();
+
+            leader.assign(&a_ref, 1u8).unwrap();
+
+            async move {
+                leader
+                    .execute(
+                        "test",
+                        circ,
+                        &[a_ref, b_ref],
+                        &[c_ref.clone()],
+                        &mut sink,
+                        &mut stream,
+                        &leader_ot_send,
+                        &leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let outputs = leader
+                    .decode_checked("test", &[c_ref], &mut sink, &mut stream)
+                    .await
+                    .unwrap();
+
+                leader
+                    .finalize(&mut sink, &mut stream, &leader_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let follower_fut = {
+            let (mut sink, mut stream) = follower_channel.split();
+
+            let a_ref = follower.new_blind_input::<u8>("a").unwrap();
+            let b_ref = follower.new_private_input::<u8>("b").unwrap();
+            let c_ref = follower.new_output::<u8>("c").unwrap();
+
+            follower.assign(&b_ref, 2u8).unwrap();
+
+            async move {
+                follower
+                    .execute(
+                        "test",
+                        circ,
+                        &[a_ref, b_ref],
+                        &[c_ref.clone()],
+                        &mut sink,
+                        &mut stream,
+                        &follower_ot_send,
+                        &follower_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let outputs = follower
+                    .decode_checked("test", &[c_ref], &mut sink, &mut stream)
+                    .await
+                    .unwrap();
+
+                follower
+                    .finalize(&mut sink, &mut stream, &follower_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let (leader_output, follower_output) = tokio::join!(leader_fut, follower_fut);
+
+        assert_eq!(
+            leader_output,
+            vec![(Value::U8(3), Authenticity::Authenticated)]
+        );
+        assert_eq!(
+            follower_output,
+            vec![(Value::U8(3), Authenticity::PendingFinalization)]
+        );
+    }
+
+    /// An OT wrapper which counts the number of OTs.
+    #[derive(Clone)]
+    struct CountingOT<T> {
+        inner: T,
+        count: Arc<AtomicUsize>,
+    }
+
+    impl<T> CountingOT<T> {
+        fn new(inner: T) -> Self {
+            Self {
+                inner,
+                count: Arc::new(AtomicUsize::new(0)),
+            }
+        }
+
+        fn count(&self) -> usize {
+            self.count.load(Ordering::Relaxed)
+        }
+    }
+
+    #[async_trait]
+    impl<T> OTSenderShared<[Block; 2]> for CountingOT<T>
+    where
+        T: OTSenderShared<[Block; 2]> + Send + Sync,
+    {
+        async fn send(&self, id: &str, msgs: &[[Block; 2]]) -> Result<(), OTError> {
+            self.count.fetch_add(msgs.len(), Ordering::Relaxed);
+            OTSenderShared::<[Block; 2]>::send(&self.inner, id, msgs).await
+        }
+    }
+
+    #[async_trait]
+    impl<T> OTReceiverShared<bool, Block> for CountingOT<T>
+    where
+        T: OTReceiverShared<bool, Block> + Send + Sync,
+    {
+        async fn receive(&self, id: &str, choices: &[bool]) -> Result<Vec<Block>, OTError> {
+            self.count.fetch_add(choices.len(), Ordering::Relaxed);
+            OTReceiverShared::<bool, Block>::receive(&self.inner, id, choices).await
+        }
+    }
+
+    /// Wraps a sink, counting the number of encrypted gates sent and the serialized
+    /// length of the messages.
+    fn count_sent<T>(
+        sink: T,
+        gates: Arc<AtomicUsize>,
+        bytes: Arc<AtomicUsize>,
+    ) -> impl Sink<GarbleMessage, Error = std::io::Error> + Unpin
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+    {
+        sink.with(move |msg: GarbleMessage| {
+            if let GarbleMessage::EncryptedGates(encrypted_gates) = &msg {
+                gates.fetch_add(encrypted_gates.len(), Ordering::Relaxed);
+            }
+            bytes.fetch_add(
+                bincode::serialized_size(&msg).unwrap() as usize,
+                Ordering::Relaxed,
+            );
+            futures::future::ok::<_, std::io::Error>(msg)
+        })
+    }
+
+    #[tokio::test]
+    async fn test_deap_plan() {
+        let (leader_channel, follower_channel) = MemoryDuplex::<GarbleMessage>::new();
+        let (leader_ot_send, follower_ot_recv) = ideal_ot_shared_pair();
+        let (follower_ot_send, leader_ot_recv) = ideal_ot_shared_pair();
+
+        let leader_ot_send = CountingOT::new(leader_ot_send);
+        let leader_ot_recv = CountingOT::new(leader_ot_recv);
+        let follower_ot_send = CountingOT::new(follower_ot_send);
+        let follower_ot_recv = CountingOT::new(follower_ot_recv);
+
+        let leader_gates = Arc::new(AtomicUsize::new(0));
+        let follower_gates = Arc::new(AtomicUsize::new(0));
+        let leader_bytes = Arc::new(AtomicUsize::new(0));
+        let follower_bytes = Arc::new(AtomicUsize::new(0));
+
+        let leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let leader_fut = {
+            let (sink, mut stream) = leader_channel.split();
+            let mut sink = count_sent(sink, leader_gates.clone(), leader_bytes.clone());
+
+            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader.plan_execute(&AES128, &[key_ref.clone(), msg_ref.clone()]);
+            leader.plan_decode(&[ciphertext_ref.clone()]);
+
+            leader.assign(&key_ref, [42u8; 16]).unwrap();
+
+            let leader = &leader;
+            let leader_ot_send = &leader_ot_send;
+            let leader_ot_recv = &leader_ot_recv;
+            async move {
+                leader
+                    .execute(
+                        "test",
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                        &mut sink,
+                        &mut stream,
+                        leader_ot_send,
+                        leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                leader
+                    .decode("test", &[ciphertext_ref], &mut sink, &mut stream)
+                    .await
+                    .unwrap();
+            }
+        };
+
+        let follower_fut = {
+            let (sink, mut stream) = follower_channel.split();
+            let mut sink = count_sent(sink, follower_gates.clone(), follower_bytes.clone());
+
+            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            follower.plan_execute(&AES128, &[key_ref.clone(), msg_ref.clone()]);
+            follower.plan_decode(&[ciphertext_ref.clone()]);
+
+            follower.assign(&msg_ref, [69u8; 16]).unwrap();
+
+            let follower = &follower;
+            let follower_ot_send = &follower_ot_send;
+            let follower_ot_recv = &follower_ot_recv;
+            async move {
+                follower
+                    .execute(
+                        "test",
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                        &mut sink,
+                        &mut stream,
+                        follower_ot_send,
+                        follower_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                follower
+                    .decode("test", &[ciphertext_ref], &mut sink, &mut stream)
+                    .await
+                    .unwrap();
+            }
+        };
 
-        let a = builder.add_input::<u8>();
-        let b = builder.add_input::<u8>();
+        tokio::join!(leader_fut, follower_fut);
 
-        let c = a.wrapping_add(b);
+        let leader_plan = leader.plan();
+        let follower_plan = follower.plan();
 
-        builder.add_output(c);
+        assert_eq!(leader_plan.and_gates, AES128.and_count());
+        assert_eq!(leader_plan.and_gates, leader_gates.load(Ordering::Relaxed));
+        assert_eq!(leader_plan.ots_sent, leader_ot_send.count());
+        assert_eq!(leader_plan.ots_received, leader_ot_recv.count());
+        assert_eq!(leader_plan.ots(), 256);
 
-        Arc::new(builder.build().unwrap())
+        assert_eq!(follower_plan.and_gates, AES128.and_count());
+        assert_eq!(
+            follower_plan.and_gates,
+            follower_gates.load(Ordering::Relaxed)
+        );
+        assert_eq!(follower_plan.ots_sent, follower_ot_send.count());
+        assert_eq!(follower_plan.ots_received, follower_ot_recv.count());
+        assert_eq!(follower_plan.ots(), 256);
+
+        assert_eq!(leader_plan.bytes_sent, leader_bytes.load(Ordering::Relaxed));
+        assert_eq!(
+            follower_plan.bytes_sent,
+            follower_bytes.load(Ordering::Relaxed)
+        );
     }
 
     #[tokio::test]
-    async fn test_deap() {
+    async fn test_deap_plan_bytes() {
         let (leader_channel, follower_channel) = MemoryDuplex::<GarbleMessage>::new();
         let (leader_ot_send, follower_ot_recv) = ideal_ot_shared_pair();
         let (follower_ot_send, leader_ot_recv) = ideal_ot_shared_pair();
 
-        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
-        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+        let leader_gates = Arc::new(AtomicUsize::new(0));
+        let follower_gates = Arc::new(AtomicUsize::new(0));
+        let leader_bytes = Arc::new(AtomicUsize::new(0));
+        let follower_bytes = Arc::new(AtomicUsize::new(0));
 
-        let key = [42u8; 16];
-        let msg = [69u8; 16];
+        let circ = adder_circ();
+
+        let leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let follower = DEAP::new(Role::Follower, [69u8; 32]);
 
         let leader_fut = {
-            let (mut sink, mut stream) = leader_channel.split();
+            let (sink, mut stream) = leader_channel.split();
+            let mut sink = count_sent(sink, leader_gates.clone(), leader_bytes.clone());
 
-            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
-            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
-            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+            let a_ref = leader.new_private_input::<u8>("a").unwrap();
+            let b_ref = leader.new_blind_input::<u8>("b").unwrap();
+            let c_ref = leader.new_output::<u8>("c").unwrap();
 
-            leader.assign(&key_ref, key).unwrap();
+            leader.plan_execute(&circ, &[a_ref.clone(), b_ref.clone()]);
+            leader.plan_decode(&[c_ref.clone()]);
+
+            leader.assign(&a_ref, 1u8).unwrap();
 
+            let leader = &leader;
+            let circ = circ.clone();
             async move {
                 leader
                     .execute(
                         "test",
-                        AES128.clone(),
-                        &[key_ref, msg_ref],
-                        &[ciphertext_ref.clone()],
+                        circ,
+                        &[a_ref, b_ref],
+                        &[c_ref.clone()],
                         &mut sink,
                         &mut stream,
                         &leader_ot_send,
@@ -945,36 +2269,35 @@ mod tests {
                     .await
                     .unwrap();
 
-                let outputs = leader
-                    .decode("test", &[ciphertext_ref], &mut sink, &mut stream)
-                    .await
-                    .unwrap();
-
                 leader
-                    .finalize(&mut sink, &mut stream, &leader_ot_recv)
+                    .decode("test", &[c_ref], &mut sink, &mut stream)
                     .await
                     .unwrap();
-
-                outputs
             }
         };
 
         let follower_fut = {
-            let (mut sink, mut stream) = follower_channel.split();
+            let (sink, mut stream) = follower_channel.split();
+            let mut sink = count_sent(sink, follower_gates.clone(), follower_bytes.clone());
 
-            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
-            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
-            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+            let a_ref = follower.new_blind_input::<u8>("a").unwrap();
+            let b_ref = follower.new_private_input::<u8>("b").unwrap();
+            let c_ref = follower.new_output::<u8>("c").unwrap();
 
-            follower.assign(&msg_ref, msg).unwrap();
+            follower.plan_execute(&circ, &[a_ref.clone(), b_ref.clone()]);
+            follower.plan_decode(&[c_ref.clone()]);
+
+            follower.assign(&b_ref, 2u8).unwrap();
 
+            let follower = &follower;
+            let circ = circ.clone();
             async move {
                 follower
                     .execute(
                         "test",
-                        AES128.clone(),
-                        &[key_ref, msg_ref],
-                        &[ciphertext_ref.clone()],
+                        circ,
+                        &[a_ref, b_ref],
+                        &[c_ref.clone()],
                         &mut sink,
                         &mut stream,
                         &follower_ot_send,
@@ -983,23 +2306,42 @@ mod tests {
                     .await
                     .unwrap();
 
-                let outputs = follower
-                    .decode("test", &[ciphertext_ref], &mut sink, &mut stream)
-                    .await
-                    .unwrap();
-
                 follower
-                    .finalize(&mut sink, &mut stream, &follower_ot_recv)
+                    .decode("test", &[c_ref], &mut sink, &mut stream)
                     .await
                     .unwrap();
-
-                outputs
             }
         };
 
-        let (leader_output, follower_output) = tokio::join!(leader_fut, follower_fut);
-
-        assert_eq!(leader_output, follower_output);
+        tokio::join!(leader_fut, follower_fut);
+
+        let and_count = circ.and_count();
+        assert_eq!(leader_gates.load(Ordering::Relaxed), and_count);
+        assert_eq!(follower_gates.load(Ordering::Relaxed), and_count);
+
+        // The active encoding of a `u8` is a 4 byte tag and 8 labels of 16 bytes, and a
+        // commitment to its full encoding is a 4 byte tag and 8 pairs of labels. Vectors
+        // are prefixed with their 8 byte length, and messages with a 4 byte tag.
+        //
+        // Both parties send:
+        // - the circuit hash: 4 + 32
+        // - the encoding of their input: 4 + 8 + 132
+        // - the encrypted gates in a single batch: 4 + 8 + 32 per gate
+        // - the decoding of the output: 4 + 8 + 4 + 1
+        //
+        // The leader also sends:
+        // - the commitment to the output encoding: 4 + 8 + 4 + 256
+        // - the commitment to the equality check: 4 + 32
+        //
+        // The follower also sends:
+        // - the encoding of the output: 4 + 8 + 132
+        let leader_expected = 517 + 32 * and_count;
+        let follower_expected = 353 + 32 * and_count;
+
+        assert_eq!(leader.plan().bytes_sent, leader_expected);
+        assert_eq!(follower.plan().bytes_sent, follower_expected);
+        assert_eq!(leader_bytes.load(Ordering::Relaxed), leader_expected);
+        assert_eq!(follower_bytes.load(Ordering::Relaxed), follower_expected);
     }
 
     #[tokio::test]
@@ -1338,6 +2680,119 @@ mod tests {
         assert_eq!((leader_share ^ follower_share), c);
     }
 
+    #[tokio::test]
+    async fn test_deap_decode_shared_field() {
+        let (leader_channel, follower_channel) = MemoryDuplex::<GarbleMessage>::new();
+        let (leader_ot_send, follower_ot_recv) = ideal_ot_shared_pair();
+        let (follower_ot_send, leader_ot_recv) = ideal_ot_shared_pair();
+
+        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let circ = adder_circ();
+
+        let a = 200u8;
+        let b = 100u8;
+        let c = a.wrapping_add(b);
+
+        let leader_fut = {
+            let (mut sink, mut stream) = leader_channel.split();
+            let circ = circ.clone();
+            let a_ref = leader.new_private_input::<u8>("a").unwrap();
+            let b_ref = leader.new_blind_input::<u8>("b").unwrap();
+            let c_ref = leader.new_output::<u8>("c").unwrap();
+
+            leader.assign(&a_ref, a).unwrap();
+
+            async move {
+                leader
+                    .execute(
+                        "test",
+                        circ,
+                        &[a_ref, b_ref],
+                        &[c_ref.clone()],
+                        &mut sink,
+                        &mut stream,
+                        &leader_ot_send,
+                        &leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let outputs = leader
+                    .decode_shared_field::<P256, _, _, _, _>(
+                        "test",
+                        &[c_ref],
+                        &mut sink,
+                        &mut stream,
+                        &leader_ot_send,
+                        &leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                leader
+                    .finalize(&mut sink, &mut stream, &leader_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let follower_fut = {
+            let (mut sink, mut stream) = follower_channel.split();
+
+            let a_ref = follower.new_blind_input::<u8>("a").unwrap();
+            let b_ref = follower.new_private_input::<u8>("b").unwrap();
+            let c_ref = follower.new_output::<u8>("c").unwrap();
+
+            follower.assign(&b_ref, b).unwrap();
+
+            async move {
+                follower
+                    .execute(
+                        "test",
+                        circ.clone(),
+                        &[a_ref, b_ref],
+                        &[c_ref.clone()],
+                        &mut sink,
+                        &mut stream,
+                        &follower_ot_send,
+                        &follower_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let outputs = follower
+                    .decode_shared_field::<P256, _, _, _, _>(
+                        "test",
+                        &[c_ref],
+                        &mut sink,
+                        &mut stream,
+                        &follower_ot_send,
+                        &follower_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                follower
+                    .finalize(&mut sink, &mut stream, &follower_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let (mut leader_output, mut follower_output) = tokio::join!(leader_fut, follower_fut);
+
+        let leader_share = leader_output.pop().unwrap();
+        let follower_share = follower_output.pop().unwrap();
+
+        assert_eq!(leader_share + follower_share, P256::new(c).unwrap());
+    }
+
     #[tokio::test]
     async fn test_deap_zk_pass() {
         run_zk(
@@ -1364,6 +2819,116 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_deap_execute_and_prove() {
+        let expected_ciphertext = [
+            235u8, 22, 253, 138, 102, 20, 139, 100, 252, 153, 244, 111, 84, 116, 199, 75,
+        ];
+
+        let (ciphertext, result) =
+            run_execute_and_prove([42u8; 16], [69u8; 16], expected_ciphertext).await;
+
+        assert_eq!(ciphertext, expected_ciphertext);
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_deap_execute_and_prove_wrong_input() {
+        let expected_ciphertext = [
+            235u8, 22, 253, 138, 102, 20, 139, 100, 252, 153, 244, 111, 84, 116, 199, 75,
+        ];
+
+        // The prover uses a different key than the one the verifier expects.
+        let (ciphertext, result) =
+            run_execute_and_prove([43u8; 16], [69u8; 16], expected_ciphertext).await;
+
+        assert_ne!(ciphertext, expected_ciphertext);
+        assert!(matches!(
+            result,
+            Err(DEAPError::FinalizationError(
+                FinalizationError::InvalidProof
+            ))
+        ));
+    }
+
+    // Runs execute-and-prove, returning the prover's output and the verifier's
+    // finalization result.
+    async fn run_execute_and_prove(
+        key: [u8; 16],
+        msg: [u8; 16],
+        expected_ciphertext: [u8; 16],
+    ) -> ([u8; 16], Result<Option<[u8; 32]>, DEAPError>) {
+        let (leader_channel, follower_channel) = MemoryDuplex::<GarbleMessage>::new();
+        let (_, follower_ot_recv) = ideal_ot_shared_pair();
+        let (follower_ot_send, leader_ot_recv) = ideal_ot_shared_pair();
+
+        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let leader_fut = {
+            let (mut sink, mut stream) = leader_channel.split();
+            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader.assign(&key_ref, key).unwrap();
+
+            async move {
+                let mut outputs = leader
+                    .execute_and_prove(
+                        "test0",
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref],
+                        &mut sink,
+                        &mut stream,
+                        &leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                leader
+                    .finalize(&mut sink, &mut stream, &leader_ot_recv)
+                    .await
+                    .unwrap();
+
+                let ciphertext: [u8; 16] = outputs.pop().unwrap().try_into().unwrap();
+                ciphertext
+            }
+        };
+
+        let follower_fut = {
+            let (mut sink, mut stream) = follower_channel.split();
+            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            follower.assign(&msg_ref, msg).unwrap();
+
+            async move {
+                follower
+                    .execute_and_verify(
+                        "test0",
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref],
+                        &[expected_ciphertext.into()],
+                        &mut sink,
+                        &mut stream,
+                        &follower_ot_send,
+                    )
+                    .await
+                    .unwrap();
+
+                follower
+                    .finalize(&mut sink, &mut stream, &follower_ot_recv)
+                    .await
+            }
+        };
+
+        futures::join!(leader_fut, follower_fut)
+    }
+
     async fn run_zk(key: [u8; 16], msg: [u8; 16], expected_ciphertext: [u8; 16]) {
         let (leader_channel, follower_channel) = MemoryDuplex::<GarbleMessage>::new();
         let (_, follower_ot_recv) = ideal_ot_shared_pair();
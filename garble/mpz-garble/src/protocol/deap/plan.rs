@@ -0,0 +1,162 @@
+//! Cost estimation for planned DEAP operations.
+
+use mpz_circuits::{types::ValueType, Circuit};
+use mpz_core::{hash::Hash, Block};
+use mpz_garble_core::{
+    encoding_state, msg::GarbleMessage, ChaChaEncoder, EncodedValue, Encoder, Label,
+};
+use serde::Serialize;
+
+use crate::config::{Role, Visibility};
+
+/// An estimate of the cost of a sequence of DEAP operations.
+///
+/// The estimates are from the perspective of a single party, and assume that none
+/// of the inputs have been used in a prior operation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CostPlan {
+    /// The number of AND gates garbled.
+    pub and_gates: usize,
+    /// The number of OTs in which this party acts as the sender.
+    pub ots_sent: usize,
+    /// The number of OTs in which this party acts as the receiver.
+    pub ots_received: usize,
+    /// The number of bytes sent to the peer, excluding the OTs.
+    ///
+    /// This is the total length of the messages serialized with `bincode`. Other codecs
+    /// may add framing, such as length prefixes.
+    pub bytes_sent: usize,
+}
+
+impl CostPlan {
+    /// Returns the total number of OTs consumed by this party.
+    pub fn ots(&self) -> usize {
+        self.ots_sent + self.ots_received
+    }
+
+    /// Records the cost of executing a circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The role of this party.
+    /// * `circ` - The circuit to execute.
+    /// * `batch_size` - The number of encrypted gates sent in each message.
+    /// * `inputs` - The type and visibility of each input.
+    pub(crate) fn record_execute(
+        &mut self,
+        role: Role,
+        circ: &Circuit,
+        batch_size: usize,
+        inputs: impl IntoIterator<Item = (ValueType, Visibility)>,
+    ) {
+        self.bytes_sent += serialized_size(&GarbleMessage::CircuitHash([0u8; 32]));
+
+        let mut direct_sizes = Vec::new();
+        for (typ, visibility) in inputs {
+            let len = typ.len();
+
+            match visibility {
+                // Sent directly to the peer.
+                Visibility::Public | Visibility::Private => {
+                    // The active encoding of each element of an array is sent separately.
+                    let (typ, count) = match typ {
+                        ValueType::Array(typ, count) => (*typ, count),
+                        typ => (typ, 1),
+                    };
+                    direct_sizes.push(count * serialized_size(&active_encoding(&typ)));
+                }
+                // Sent to the peer via OT.
+                Visibility::Blind => self.ots_sent += len,
+            }
+
+            // Received from the peer via OT.
+            if let Visibility::Private = visibility {
+                self.ots_received += len;
+            }
+        }
+
+        // The directly sent inputs are sent in a single message, if there are any.
+        if !direct_sizes.is_empty() {
+            self.bytes_sent +=
+                vec_message_size(GarbleMessage::ActiveValues(Vec::new()), direct_sizes);
+        }
+
+        // The encrypted gates are sent in batches. An encrypted gate is serialized as
+        // its two ciphertexts.
+        let and_count = circ.and_count();
+        self.and_gates += and_count;
+        self.bytes_sent += and_count.div_ceil(batch_size)
+            * serialized_size(&GarbleMessage::EncryptedGates(Vec::new()))
+            + and_count * serialized_size(&[Block::ZERO; 2]);
+
+        if let Role::Leader = role {
+            self.bytes_sent += vec_message_size(
+                GarbleMessage::EncodingCommitments(Vec::new()),
+                circ.outputs()
+                    .iter()
+                    .map(|output| serialized_size(&full_encoding(&output.value_type()).commit())),
+            );
+        }
+    }
+
+    /// Records the cost of decoding values.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The role of this party.
+    /// * `types` - The type of each value.
+    pub(crate) fn record_decode(&mut self, role: Role, types: impl IntoIterator<Item = ValueType>) {
+        let types: Vec<_> = types.into_iter().collect();
+
+        self.bytes_sent += vec_message_size(
+            GarbleMessage::ValueDecodings(Vec::new()),
+            types
+                .iter()
+                .map(|typ| serialized_size(&full_encoding(typ).decoding())),
+        );
+
+        self.bytes_sent += match role {
+            Role::Leader => serialized_size(&GarbleMessage::HashCommitment(Hash::from([0u8; 32]))),
+            Role::Follower => vec_message_size(
+                GarbleMessage::ActiveValues(Vec::new()),
+                types
+                    .iter()
+                    .map(|typ| serialized_size(&active_encoding(typ))),
+            ),
+        };
+    }
+}
+
+/// Returns the length of a value serialized with `bincode`.
+fn serialized_size<T: Serialize + ?Sized>(value: &T) -> usize {
+    bincode::serialized_size(value).expect("value should be serializable") as usize
+}
+
+/// Returns the serialized length of a message carrying a vector of items.
+///
+/// `bincode` serializes a vector as its length followed by its items, so this is the length
+/// of the message with an empty vector plus the length of each item.
+///
+/// # Arguments
+///
+/// * `empty` - The message with an empty vector.
+/// * `item_sizes` - The serialized length of each item.
+fn vec_message_size(empty: GarbleMessage, item_sizes: impl IntoIterator<Item = usize>) -> usize {
+    serialized_size(&empty) + item_sizes.into_iter().sum::<usize>()
+}
+
+/// Returns a full encoding of a value of the provided type, whose serialized length
+/// does not depend on the labels.
+fn full_encoding(typ: &ValueType) -> EncodedValue<encoding_state::Full> {
+    ChaChaEncoder::new([0u8; 32]).encode_by_type(0, typ)
+}
+
+/// Returns an active encoding of a value of the provided type, whose serialized length
+/// does not depend on the labels.
+fn active_encoding(typ: &ValueType) -> EncodedValue<encoding_state::Active> {
+    EncodedValue::<encoding_state::Active>::from_labels(
+        typ.clone(),
+        &vec![Label::new(Block::ZERO); typ.len()],
+    )
+    .expect("number of labels should match the type")
+}
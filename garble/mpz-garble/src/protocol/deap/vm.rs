@@ -13,6 +13,7 @@ use mpz_circuits::{
     types::{Value, ValueType},
     Circuit,
 };
+use mpz_fields::Field;
 use mpz_garble_core::{encoding_state::Active, msg::GarbleMessage, EncodedValue};
 use utils::id::NestedId;
 use utils_aio::{duplex::Duplex, mux::MuxChannel};
@@ -27,9 +28,12 @@ use crate::{
 
 use super::{
     error::{FinalizationError, PeerEncodingsError},
-    DEAPError, DEAP,
+    Authenticity, DEAPError, DEAP,
 };
 
+/// The number of values decoded at a time by [`DEAPThread::decode_into`].
+const DECODE_CHUNK_SIZE: usize = 128;
+
 type ChannelFactory = Box<dyn MuxChannel<GarbleMessage> + Send + 'static>;
 type GarbleChannel = Box<dyn Duplex<GarbleMessage>>;
 
@@ -58,6 +62,8 @@ pub struct DEAPVm<OTS, OTR> {
     threads: HashSet<NestedId>,
     /// Whether the instance has been finalized.
     finalized: bool,
+    /// Whether the vm has been shut down.
+    shutdown: bool,
 }
 
 impl<OTS, OTR> DEAPVm<OTS, OTR>
@@ -87,6 +93,7 @@ where
             deap: Some(Arc::new(DEAP::new(role, encoder_seed))),
             threads: HashSet::default(),
             finalized: false,
+            shutdown: false,
         }
     }
 
@@ -120,7 +127,7 @@ where
     type Thread = DEAPThread<OTS, OTR>;
 
     async fn new_thread(&mut self, id: &str) -> Result<DEAPThread<OTS, OTR>, VmError> {
-        if self.finalized {
+        if self.finalized || self.shutdown {
             return Err(VmError::Shutdown);
         }
 
@@ -144,6 +151,12 @@ where
             self.ot_recv.clone(),
         ))
     }
+
+    async fn shutdown(&mut self) -> Result<(), VmError> {
+        self.shutdown = true;
+
+        Ok(())
+    }
 }
 
 /// A DEAP thread.
@@ -200,6 +213,89 @@ where
     }
 }
 
+impl<OTS, OTR> DEAPThread<OTS, OTR>
+where
+    OTS: VerifiableOTSendEncoding + Send + Sync,
+    OTR: VerifiableOTReceiveEncoding + Send + Sync,
+{
+    /// Decodes the provided values, returning the plaintext values along with the
+    /// authenticity status of each value.
+    ///
+    /// The follower's values are not authenticated until the vm is finalized.
+    pub async fn decode_checked(
+        &mut self,
+        values: &[ValueRef],
+    ) -> Result<Vec<(Value, Authenticity)>, DecodeError> {
+        let deap = self.deap();
+        let authenticity = deap.authenticity();
+
+        let output = deap
+            .decode_unchecked(
+                &self.op_id.increment_in_place().to_string(),
+                values,
+                &mut self.sink,
+                &mut self.stream,
+            )
+            .map_err(DecodeError::from)
+            .await?;
+
+        Ok(output
+            .into_iter()
+            .map(|value| (value, authenticity))
+            .collect())
+    }
+
+    /// Decodes the provided values, returning additive shares of the plaintext values
+    /// over the prime field `F` to all parties.
+    ///
+    /// Each value is interpreted as a little-endian integer, which must fit into a field element.
+    pub async fn decode_shared_field<F: Field>(
+        &mut self,
+        values: &[ValueRef],
+    ) -> Result<Vec<F>, DecodeError> {
+        self.deap()
+            .decode_shared_field(
+                &self.op_id.increment_in_place().to_string(),
+                values,
+                &mut self.sink,
+                &mut self.stream,
+                &*self.ot_send,
+                &*self.ot_recv,
+            )
+            .map_err(DecodeError::from)
+            .await
+    }
+
+    /// Decodes the provided values, invoking `f` with the index and plaintext of each value
+    /// in the order of `values`.
+    ///
+    /// The values are decoded in chunks, so at most a chunk of decoded values is held in
+    /// memory at a time. Decoding stops at the first error returned by `f`.
+    pub async fn decode_into<F>(&mut self, values: &[ValueRef], mut f: F) -> Result<(), DecodeError>
+    where
+        F: FnMut(usize, Value) -> std::io::Result<()>,
+    {
+        for (chunk_idx, chunk) in values.chunks(DECODE_CHUNK_SIZE).enumerate() {
+            let decoded = self
+                .deap()
+                .decode_unchecked(
+                    &self.op_id.increment_in_place().to_string(),
+                    chunk,
+                    &mut self.sink,
+                    &mut self.stream,
+                )
+                .map_err(DecodeError::from)
+                .await?;
+
+            for (idx, value) in decoded.into_iter().enumerate() {
+                f(chunk_idx * DECODE_CHUNK_SIZE + idx, value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl<OTS, OTR> Thread for DEAPThread<OTS, OTR> {}
 
 impl<OTS, OTR> Memory for DEAPThread<OTS, OTR> {
@@ -319,6 +415,26 @@ where
             .map_err(ProveError::from)
             .await
     }
+
+    async fn execute_and_prove(
+        &mut self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<Vec<Value>, ProveError> {
+        self.deap()
+            .execute_and_prove(
+                &self.op_id.increment_in_place().to_string(),
+                circ,
+                inputs,
+                outputs,
+                &mut self.sink,
+                &mut self.stream,
+                &*self.ot_recv,
+            )
+            .map_err(ProveError::from)
+            .await
+    }
 }
 
 #[async_trait]
@@ -361,6 +477,28 @@ where
             .map_err(VerifyError::from)
             .await
     }
+
+    async fn execute_and_verify(
+        &mut self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        expected_outputs: &[Value],
+    ) -> Result<(), VerifyError> {
+        self.deap()
+            .execute_and_verify(
+                &self.op_id.increment_in_place().to_string(),
+                circ,
+                inputs,
+                outputs,
+                expected_outputs,
+                &mut self.sink,
+                &mut self.stream,
+                &*self.ot_send,
+            )
+            .map_err(VerifyError::from)
+            .await
+    }
 }
 
 #[async_trait]
@@ -371,7 +509,7 @@ where
 {
     async fn decode(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
         self.deap()
-            .decode(
+            .decode_unchecked(
                 &self.op_id.increment_in_place().to_string(),
                 values,
                 &mut self.sink,
@@ -477,9 +615,9 @@ impl<OTS, OTR> PeerEncodings for DEAPVm<OTS, OTR> {
 mod tests {
     use super::*;
 
-    use mpz_circuits::circuits::AES128;
+    use mpz_circuits::{circuits::AES128, ops::WrappingAdd, CircuitBuilder};
 
-    use crate::protocol::deap::mock::create_mock_deap_vm;
+    use crate::{internal_circuits::build_otp_circuit, protocol::deap::mock::create_mock_deap_vm};
 
     use core::{future::Future, pin::Pin};
     use mpz_ot::ideal::{IdealSharedOTReceiver, IdealSharedOTSender};
@@ -580,6 +718,184 @@ mod tests {
         follower_result.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_decode_into() {
+        let (mut leader_vm, mut follower_vm) = create_mock_deap_vm("test_vm").await;
+        let mut leader_thread = leader_vm.new_thread("test_thread").await.unwrap();
+        let mut follower_thread = follower_vm.new_thread("test_thread").await.unwrap();
+
+        let circ = build_otp_circuit(&[ValueType::new_array::<u8>(1024)]);
+
+        let a: Vec<u8> = (0..1024).map(|i| i as u8).collect();
+        let b: Vec<u8> = (0..1024).map(|i| (i * 7) as u8).collect();
+        let expected: Vec<Value> = a.iter().zip(&b).map(|(a, b)| (a ^ b).into()).collect();
+
+        let leader_fut = {
+            let circ = circ.clone();
+            let a_ref = leader_thread.new_private_input::<[u8; 1024]>("a").unwrap();
+            let b_ref = leader_thread.new_blind_input::<[u8; 1024]>("b").unwrap();
+            let c_ref = leader_thread.new_output::<[u8; 1024]>("c").unwrap();
+
+            leader_thread.assign(&a_ref, a).unwrap();
+
+            async move {
+                leader_thread
+                    .execute(circ, &[a_ref, b_ref], &[c_ref.clone()])
+                    .await
+                    .unwrap();
+
+                let elements: Vec<_> = c_ref
+                    .iter()
+                    .map(|id| ValueRef::Value { id: id.clone() })
+                    .collect();
+
+                let mut decoded = Vec::new();
+                leader_thread
+                    .decode_into(&elements, |idx, value| {
+                        decoded.push((idx, value));
+                        Ok(())
+                    })
+                    .await
+                    .unwrap();
+
+                decoded
+            }
+        };
+
+        let follower_fut = {
+            let a_ref = follower_thread.new_blind_input::<[u8; 1024]>("a").unwrap();
+            let b_ref = follower_thread
+                .new_private_input::<[u8; 1024]>("b")
+                .unwrap();
+            let c_ref = follower_thread.new_output::<[u8; 1024]>("c").unwrap();
+
+            follower_thread.assign(&b_ref, b).unwrap();
+
+            async move {
+                follower_thread
+                    .execute(circ, &[a_ref, b_ref], &[c_ref.clone()])
+                    .await
+                    .unwrap();
+
+                let elements: Vec<_> = c_ref
+                    .iter()
+                    .map(|id| ValueRef::Value { id: id.clone() })
+                    .collect();
+
+                let mut decoded = Vec::new();
+                follower_thread
+                    .decode_into(&elements, |idx, value| {
+                        decoded.push((idx, value));
+                        Ok(())
+                    })
+                    .await
+                    .unwrap();
+
+                decoded
+            }
+        };
+
+        let (leader_decoded, follower_decoded) = futures::join!(leader_fut, follower_fut);
+
+        for decoded in [leader_decoded, follower_decoded] {
+            assert_eq!(decoded.len(), 1024);
+            for (i, (idx, value)) in decoded.into_iter().enumerate() {
+                assert_eq!(idx, i);
+                assert_eq!(value, expected[i]);
+            }
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_vm_shutdown(set_up_vms: impl Future<Output = VmFixture>) {
+        let VmFixture {
+            mut leader_vm,
+            leader_fut,
+            mut follower_vm,
+            follower_fut,
+        } = set_up_vms.await;
+
+        leader_vm.shutdown().await.unwrap();
+        follower_vm.shutdown().await.unwrap();
+
+        assert!(matches!(
+            leader_vm.new_thread("new_thread").await,
+            Err(VmError::Shutdown)
+        ));
+        assert!(matches!(
+            follower_vm.new_thread("new_thread").await,
+            Err(VmError::Shutdown)
+        ));
+
+        // Threads created before shutdown can still execute and decode.
+        let (leader_result, follower_result) = futures::join!(leader_fut, follower_fut);
+
+        assert_eq!(leader_result, follower_result);
+
+        let (leader_result, follower_result) =
+            futures::join!(leader_vm.finalize(), follower_vm.finalize());
+
+        leader_result.unwrap();
+        follower_result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_execute_input_mismatch() {
+        let (mut leader_vm, _follower_vm) = create_mock_deap_vm("test_vm").await;
+        let mut leader_thread = leader_vm.new_thread("test_thread").await.unwrap();
+
+        let circ = {
+            let builder = CircuitBuilder::new();
+
+            let a = builder.add_input::<u64>();
+            let b = builder.add_input::<u64>();
+
+            let c = a.wrapping_add(b);
+
+            builder.add_output(c);
+
+            Arc::new(builder.build().unwrap())
+        };
+
+        let a_ref = leader_thread.new_private_input::<u32>("a").unwrap();
+        let b_ref = leader_thread.new_blind_input::<u64>("b").unwrap();
+        let c_ref = leader_thread.new_output::<u64>("c").unwrap();
+
+        leader_thread.assign(&a_ref, 1u32).unwrap();
+
+        let err = leader_thread
+            .execute(circ.clone(), &[a_ref.clone(), b_ref], &[c_ref.clone()])
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            ExecutionError::InputMismatch {
+                index: 0,
+                expected: ValueType::U64,
+                actual: ValueType::U32,
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "input 0 has type U32, but the circuit expects U64"
+        );
+
+        let err = leader_thread
+            .execute(circ, &[a_ref], &[c_ref])
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            ExecutionError::InputCountMismatch {
+                expected: 2,
+                actual: 1,
+            }
+        ));
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_peer_encodings(set_up_vms: impl Future<Output = VmFixture>) {
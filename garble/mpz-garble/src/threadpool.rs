@@ -2,7 +2,7 @@ use std::pin::Pin;
 
 use futures::Future;
 
-use crate::Thread;
+use crate::{Thread, VmError};
 
 /// A closure which takes a mutable reference to a thread and returns a boxed future.
 type ThreadClosure<'a, T, R> =
@@ -128,14 +128,54 @@ where
             closures: Vec::new(),
         }
     }
+
+    /// Maps the provided closure over the jobs, distributing them across the threads in the pool.
+    ///
+    /// The jobs are run concurrently and the results are returned in the same order as the jobs.
+    ///
+    /// # Order
+    ///
+    /// The order of the jobs provided _must_ be the same for all parties in the MPC.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error, in job order, if any of the jobs fail.
+    ///
+    /// # Arguments
+    ///
+    /// * `jobs` - The jobs to run.
+    /// * `f` - The closure which runs a job on a thread.
+    pub async fn map<J, R, F>(
+        &mut self,
+        jobs: impl IntoIterator<Item = J>,
+        f: F,
+    ) -> Result<Vec<R>, VmError>
+    where
+        J: Send,
+        F: for<'b> Fn(
+                &'b mut T,
+                J,
+            ) -> Pin<Box<dyn Future<Output = Result<R, VmError>> + Send + 'b>>
+            + Sync,
+    {
+        let f = &f;
+        let mut scope = self.new_scope();
+        for job in jobs {
+            scope.push(move |thread| f(thread, job));
+        }
+
+        scope.wait().await.into_iter().collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use std::sync::Arc;
+
     use crate::{protocol::deap::mock::create_mock_deap_vm, Decode, Execute, Vm, VmError};
-    use mpz_circuits::circuits::AES128;
+    use mpz_circuits::{circuits::AES128, ops::WrappingAdd, Circuit, CircuitBuilder};
 
     async fn test_fn_leader<T: Thread + Execute + Decode>(
         thread: &mut T,
@@ -174,6 +214,87 @@ mod tests {
         Ok(values.pop().unwrap().try_into().unwrap())
     }
 
+    fn adder_circ() -> Arc<Circuit> {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        let c = a.wrapping_add(b);
+
+        builder.add_output(c);
+
+        Arc::new(builder.build().unwrap())
+    }
+
+    async fn add_leader<T: Thread + Execute + Decode>(
+        thread: &mut T,
+        circ: Arc<Circuit>,
+        n: u8,
+    ) -> Result<u8, VmError> {
+        let a = thread.new_private_input::<u8>(&format!("a/{n}"))?;
+        let b = thread.new_blind_input::<u8>(&format!("b/{n}"))?;
+        let c = thread.new_output::<u8>(&format!("c/{n}"))?;
+
+        thread.assign(&a, n)?;
+
+        thread.execute(circ, &[a, b], &[c.clone()]).await?;
+
+        let mut values = thread.decode(&[c]).await?;
+
+        Ok(values.pop().unwrap().try_into().unwrap())
+    }
+
+    async fn add_follower<T: Thread + Execute + Decode>(
+        thread: &mut T,
+        circ: Arc<Circuit>,
+        n: u8,
+    ) -> Result<u8, VmError> {
+        let a = thread.new_blind_input::<u8>(&format!("a/{n}"))?;
+        let b = thread.new_private_input::<u8>(&format!("b/{n}"))?;
+        let c = thread.new_output::<u8>(&format!("c/{n}"))?;
+
+        thread.assign(&b, n)?;
+
+        thread.execute(circ, &[a, b], &[c.clone()]).await?;
+
+        let mut values = thread.decode(&[c]).await?;
+
+        Ok(values.pop().unwrap().try_into().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_thread_pool_map() {
+        let (mut leader, mut follower) = create_mock_deap_vm("test_vm").await;
+
+        let (mut leader_pool, mut follower_pool) = futures::try_join!(
+            leader.new_thread_pool("test_pool", 4),
+            follower.new_thread_pool("test_pool", 4),
+        )
+        .unwrap();
+
+        let circ = adder_circ();
+
+        let (leader_results, follower_results) = futures::try_join!(
+            leader_pool.map(0..10u8, |thread, n| Box::pin(add_leader(
+                thread,
+                circ.clone(),
+                n
+            ))),
+            follower_pool.map(0..10u8, |thread, n| Box::pin(add_follower(
+                thread,
+                circ.clone(),
+                n
+            ))),
+        )
+        .unwrap();
+
+        let expected: Vec<u8> = (0..10u8).map(|n| n + n).collect();
+
+        assert_eq!(leader_results, expected);
+        assert_eq!(follower_results, expected);
+    }
+
     #[tokio::test]
     async fn test_thread_pool() {
         let (mut leader, mut follower) = create_mock_deap_vm("test_vm").await;
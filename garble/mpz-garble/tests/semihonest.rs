@@ -1,3 +1,4 @@
+use futures::{SinkExt, TryStreamExt};
 use mpz_circuits::{circuits::AES128, types::StaticValueType};
 use mpz_garble_core::msg::GarbleMessage;
 use mpz_ot::ideal::ideal_ot_shared_pair;
@@ -109,19 +110,131 @@ async fn test_semi_honest() {
         .try_into()
         .unwrap();
 
-    let expected: [u8; 16] = {
-        use aes::{
-            cipher::{BlockEncrypt, KeyInit},
-            Aes128,
-        };
+    assert_eq!(ciphertext, aes128(key, msg))
+}
+
+#[tokio::test]
+async fn test_semi_honest_gate_stream() {
+    let (mut gen_channel, mut ev_channel) = MemoryDuplex::<GarbleMessage>::new();
+    let (ot_send, ot_recv) = ideal_ot_shared_pair();
+
+    let gen = Generator::new(
+        GeneratorConfigBuilder::default().build().unwrap(),
+        [0u8; 32],
+    );
+    let ev = Evaluator::default();
+
+    let key = [69u8; 16];
+    let msg = [42u8; 16];
+
+    let typ = <[u8; 16]>::value_type();
+
+    let gen_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let key_ref = memory
+            .new_input("key", typ.clone(), Visibility::Private)
+            .unwrap();
+        let msg_ref = memory
+            .new_input("msg", typ.clone(), Visibility::Blind)
+            .unwrap();
+        let ciphertext_ref = memory.new_output("ciphertext", typ.clone()).unwrap();
+
+        memory.assign(&key_ref, key.into()).unwrap();
+
+        gen.generate_input_encoding(&key_ref, &typ);
+        gen.generate_input_encoding(&msg_ref, &typ);
+
+        gen.setup_assigned_values(
+            "test",
+            &memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut gen_channel,
+            &ot_send,
+        )
+        .await
+        .unwrap();
+
+        let (gates, outputs) = gen.gate_stream(
+            AES128.clone(),
+            &[key_ref, msg_ref],
+            &[ciphertext_ref.clone()],
+            false,
+        );
+
+        let gates: Vec<_> = gates.try_collect().await.unwrap();
+        assert_eq!(gates.len(), AES128.and_count());
+
+        // Transfer the gates over our own transport.
+        gen_channel
+            .send(GarbleMessage::EncryptedGates(gates))
+            .await
+            .unwrap();
 
-        let mut msg = msg.into();
+        let (encoded_outputs, hash) = outputs.await.unwrap();
+        assert!(hash.is_none());
 
-        let cipher = Aes128::new_from_slice(&key).unwrap();
-        cipher.encrypt_block(&mut msg);
+        let encoding = gen.get_encoding(&ciphertext_ref).unwrap();
+        assert_eq!(encoded_outputs, vec![encoding.clone()]);
 
-        msg.into()
+        encoding
     };
 
-    assert_eq!(ciphertext, expected)
+    let ev_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let key_ref = memory
+            .new_input("key", typ.clone(), Visibility::Blind)
+            .unwrap();
+        let msg_ref = memory
+            .new_input("msg", typ.clone(), Visibility::Private)
+            .unwrap();
+        let ciphertext_ref = memory.new_output("ciphertext", typ.clone()).unwrap();
+
+        memory.assign(&msg_ref, msg.into()).unwrap();
+
+        ev.setup_assigned_values(
+            "test",
+            &memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut ev_channel,
+            &ot_recv,
+        )
+        .await
+        .unwrap();
+
+        _ = ev
+            .evaluate(
+                AES128.clone(),
+                &[key_ref, msg_ref],
+                &[ciphertext_ref.clone()],
+                &mut ev_channel,
+            )
+            .await
+            .unwrap();
+
+        ev.get_encoding(&ciphertext_ref).unwrap()
+    };
+
+    let (ciphertext_full_encoding, ciphertext_active_encoding) = tokio::join!(gen_fut, ev_fut);
+
+    let ciphertext: [u8; 16] = ciphertext_full_encoding
+        .decode(&ciphertext_active_encoding)
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    assert_eq!(ciphertext, aes128(key, msg))
+}
+
+fn aes128(key: [u8; 16], msg: [u8; 16]) -> [u8; 16] {
+    use aes::{
+        cipher::{BlockEncrypt, KeyInit},
+        Aes128,
+    };
+
+    let mut msg = msg.into();
+
+    let cipher = Aes128::new_from_slice(&key).unwrap();
+    cipher.encrypt_block(&mut msg);
+
+    msg.into()
 }
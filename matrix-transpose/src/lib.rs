@@ -36,30 +36,46 @@ use thiserror::Error;
 /// This implementation requires that the number of rows is a power of 2
 /// and that the number of columns is a multiple of 8
 pub fn transpose_bits(matrix: &mut [u8], rows: usize) -> Result<(), TransposeError> {
+    check_dimensions(matrix.len(), rows)?;
+
+    #[cfg(feature = "simd-transpose")]
+    simd::transpose_bits(matrix, rows)?;
+    #[cfg(not(feature = "simd-transpose"))]
+    unsafe {
+        scalar::transpose_unchecked(matrix, rows.trailing_zeros() as usize);
+        scalar::bitmask_shift(matrix, rows);
+    }
+    Ok(())
+}
+
+/// Checks the dimensions of a matrix with `len` cells and `rows` rows, returning the
+/// number of columns.
+///
+/// All size computations are overflow-checked, so that oversized matrices are rejected
+/// instead of wrapping around on targets with a narrow `usize`.
+fn check_dimensions(len: usize, rows: usize) -> Result<usize, TransposeError> {
     // Check that number of rows is a power of 2
-    if rows & (rows - 1) != 0 {
+    if !rows.is_power_of_two() {
         return Err(TransposeError::InvalidNumberOfRows);
     }
 
     // Check that slice is rectangular i.e. the number of cells is a multiple of the number of rows
-    if matrix.len() & (rows - 1) != 0 {
+    if len & (rows - 1) != 0 {
         return Err(TransposeError::MalformedSlice);
     }
 
+    // Check that the number of bits in the matrix is addressable
+    if len.checked_mul(8).is_none() {
+        return Err(TransposeError::MatrixTooLarge);
+    }
+
     // Check that columns is a multiple of 8
-    let columns = matrix.len() / rows;
+    let columns = len / rows;
     if columns & 7 != 0 || columns < 8 {
         return Err(TransposeError::InvalidNumberOfColumns);
     }
 
-    #[cfg(feature = "simd-transpose")]
-    simd::transpose_bits(matrix, rows)?;
-    #[cfg(not(feature = "simd-transpose"))]
-    unsafe {
-        scalar::transpose_unchecked(matrix, rows.trailing_zeros() as usize);
-        scalar::bitmask_shift(matrix, rows);
-    }
-    Ok(())
+    Ok(columns)
 }
 
 #[derive(Error, Debug, PartialEq)]
@@ -70,6 +86,8 @@ pub enum TransposeError {
     MalformedSlice,
     #[error("Number of columns must be a multiple of lane count")]
     InvalidNumberOfColumns,
+    #[error("Matrix is too large to be transposed")]
+    MatrixTooLarge,
 }
 
 #[cfg(test)]
@@ -121,6 +139,23 @@ mod tests {
         assert_eq!(naive, matrix);
     }
 
+    #[test]
+    fn test_check_dimensions() {
+        assert_eq!(check_dimensions(64 * 32, 64), Ok(32));
+        assert_eq!(
+            check_dimensions(64 * 32, 0),
+            Err(TransposeError::InvalidNumberOfRows)
+        );
+
+        // The number of bits of a matrix this size overflows `usize`, which on 32-bit
+        // targets happens for matrices of 512 MiB.
+        let len = 1 << (usize::BITS - 2);
+        assert_eq!(
+            check_dimensions(len, 1 << 16),
+            Err(TransposeError::MatrixTooLarge)
+        );
+    }
+
     #[test]
     fn test_transpose_naive() {
         let matrix = [
@@ -15,6 +15,8 @@ pub enum BuilderError {
     MissingWire(usize),
     #[error("error appending circuit: {0}")]
     AppendError(String),
+    #[error("circuit contains a cycle: gate {gate} depends on a node which is not yet produced")]
+    Cycle { gate: usize },
 }
 
 /// A circuit builder.
@@ -173,6 +175,55 @@ impl CircuitBuilder {
         self.state.borrow_mut().append(circ, builder_inputs)
     }
 
+    /// Appends an existing circuit as a gadget, wiring its inputs to the provided nodes.
+    ///
+    /// This is the same as [`append`](Self::append), except that the inputs and outputs
+    /// of the appended circuit are flat lists of nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to append
+    /// * `inputs` - The nodes to connect to the inputs of the appended circuit, in order
+    ///
+    /// # Returns
+    ///
+    /// The output nodes of the appended circuit, in order
+    pub fn append_circuit(
+        &self,
+        circ: &Circuit,
+        inputs: &[Node<Feed>],
+    ) -> Result<Vec<Node<Feed>>, BuilderError> {
+        let input_len: usize = circ.inputs().iter().map(|input| input.len()).sum();
+        if inputs.len() != input_len {
+            return Err(BuilderError::AppendError(format!(
+                "Number of input nodes does not match circuit, expected {}, got {}",
+                input_len,
+                inputs.len()
+            )));
+        }
+
+        let mut nodes = inputs;
+        let builder_inputs = circ
+            .inputs()
+            .iter()
+            .map(|input| {
+                let (input_nodes, rest) = nodes.split_at(input.len());
+                nodes = rest;
+                input
+                    .value_type()
+                    .to_bin_repr(input_nodes)
+                    .expect("input length should match")
+            })
+            .collect::<Vec<_>>();
+
+        let outputs = self.append(circ, &builder_inputs)?;
+
+        Ok(outputs
+            .iter()
+            .flat_map(|output| output.iter().copied())
+            .collect())
+    }
+
     /// Builds the circuit
     pub fn build(self) -> Result<Circuit, BuilderError> {
         self.state.into_inner().build()
@@ -431,6 +482,8 @@ impl BuilderState {
 
     /// Builds the circuit.
     pub(crate) fn build(mut self) -> Result<Circuit, BuilderError> {
+        self.check_acyclic()?;
+
         // Shift all the node ids to the left by 2 to eliminate
         // the reserved constant nodes (which should be factored out during building)
         self.inputs.iter_mut().for_each(|input| input.shift_left(2));
@@ -448,13 +501,43 @@ impl BuilderState {
             xor_count: self.xor_count,
         })
     }
+
+    /// Checks that every gate only depends on nodes which are produced before it, ie. that
+    /// the gates are ordered topologically and the circuit does not contain a cycle.
+    fn check_acyclic(&self) -> Result<(), BuilderError> {
+        let mut produced = vec![false; self.feed_id];
+
+        // Constant nodes
+        produced[0] = true;
+        produced[1] = true;
+
+        for node in self.inputs.iter().flat_map(|input| input.iter()) {
+            produced[node.id()] = true;
+        }
+
+        for (gate_idx, gate) in self.gates.iter().enumerate() {
+            if gate
+                .inputs()
+                .any(|node| !produced.get(node.id()).copied().unwrap_or(false))
+            {
+                return Err(BuilderError::Cycle { gate: gate_idx });
+            }
+
+            match produced.get_mut(gate.z().id()) {
+                Some(produced) => *produced = true,
+                None => return Err(BuilderError::Cycle { gate: gate_idx }),
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test {
     use mpz_circuits_macros::evaluate;
 
-    use crate::ops::WrappingAdd;
+    use crate::{ops::WrappingAdd, types::Bit};
 
     use super::*;
 
@@ -484,6 +567,58 @@ mod test {
         assert_eq!(output, c);
     }
 
+    #[test]
+    fn test_build_cycle() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        let c = a.wrapping_add(b);
+
+        builder.add_output(c);
+
+        {
+            let mut state = builder.state.borrow_mut();
+
+            // Wire the output of a later gate back as the input of an earlier one.
+            let x = state.add_feed();
+            let y = state.add_feed();
+            state.gates.push(Gate::Inv { x: y.into(), z: x });
+            state.gates.push(Gate::Inv { x: x.into(), z: y });
+        }
+
+        let gate_count = builder.state.borrow().gates.len();
+
+        let err = builder.build().unwrap_err();
+
+        assert!(matches!(err, BuilderError::Cycle { gate } if gate == gate_count - 2));
+    }
+
+    #[test]
+    fn test_build_acyclic() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<bool>().to_inner();
+
+        {
+            let mut state = builder.state.borrow_mut();
+
+            // Wire the gates in order, each depending on the output of the previous one.
+            let x = state.add_inv_gate(a.nodes()[0]);
+            let y = state.add_inv_gate(x);
+            state.outputs.push(Bit::new([y]).into());
+        }
+
+        let circ = builder.build().unwrap();
+
+        let mut output = circ.evaluate(&[true.into()]).unwrap();
+
+        let output: bool = output.pop().unwrap().try_into().unwrap();
+
+        assert!(output);
+    }
+
     #[test]
     fn test_append() {
         let circ = build_adder();
@@ -510,4 +645,33 @@ mod test {
         // a + (a + b) = 2a + b
         assert_eq!(d, 3u8);
     }
+
+    #[test]
+    fn test_append_circuit() {
+        let adder = build_adder();
+
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        let c = builder.add_input::<u8>();
+
+        let inputs: Vec<_> = [a, b]
+            .into_iter()
+            .flat_map(|v| v.to_inner().nodes())
+            .collect();
+        let d = builder.append_circuit(&adder, &inputs).unwrap();
+
+        let inputs: Vec<_> = d.into_iter().chain(c.to_inner().nodes()).collect();
+        let e = builder.append_circuit(&adder, &inputs).unwrap();
+
+        builder.add_output(ValueType::U8.to_bin_repr(&e).unwrap());
+
+        let circ = builder.build().unwrap();
+
+        let output = evaluate!(circ, fn(1u8, 2u8, 3u8) -> u8).unwrap();
+
+        // (a + b) + c
+        assert_eq!(output, 6u8);
+    }
 }
@@ -1,7 +1,9 @@
+use std::ops::Range;
+
 use itybity::IntoBits;
 
 use crate::{
-    components::Gate,
+    components::{Gate, GateType},
     types::{BinaryRepr, TypeError, Value},
 };
 
@@ -15,8 +17,27 @@ pub enum CircuitError {
     InvalidOutputCount(usize, usize),
     #[error(transparent)]
     TypeError(#[from] TypeError),
+    #[error("unsupported serialization version: expected {expected}, got {actual}")]
+    UnsupportedVersion { expected: u32, actual: u32 },
+    #[error("serialization error: {0}")]
+    SerializationError(String),
+    #[error("invalid circuit: {0}")]
+    InvalidCircuit(String),
 }
 
+/// The version of the binary serialization format produced by [`Circuit::to_bytes`].
+#[cfg(feature = "serde")]
+const SERIALIZATION_VERSION: u32 = 1;
+
+/// The index of a gate in [`Circuit::gates`].
+pub type GateId = usize;
+
+/// The index of an input in [`Circuit::inputs`].
+pub type InputIndex = usize;
+
+/// The index of an output in [`Circuit::outputs`].
+pub type OutputIndex = usize;
+
 /// A binary circuit.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -61,6 +82,221 @@ impl Circuit {
         self.xor_count
     }
 
+    /// Returns the number of INV gates in the circuit.
+    pub fn inv_count(&self) -> usize {
+        self.gates.len() - self.and_count - self.xor_count
+    }
+
+    /// Returns the gates of the circuit partitioned into topological layers.
+    ///
+    /// The inputs of every gate in a layer are either circuit inputs or are produced by
+    /// gates in a strictly earlier layer, so the gates within a layer can be evaluated
+    /// in parallel.
+    pub fn layers(&self) -> Vec<Vec<GateId>> {
+        // The depth of each feed, where circuit inputs have a depth of 0.
+        let mut depths = vec![0usize; self.feed_count];
+        let mut layers: Vec<Vec<GateId>> = Vec::new();
+
+        for (id, gate) in self.gates.iter().enumerate() {
+            let layer = match gate.y() {
+                Some(y) => depths[gate.x().id()].max(depths[y.id()]),
+                None => depths[gate.x().id()],
+            };
+
+            depths[gate.z().id()] = layer + 1;
+
+            if layer == layers.len() {
+                layers.push(Vec::new());
+            }
+            layers[layer].push(id);
+        }
+
+        layers
+    }
+
+    /// Returns the range of wire ids occupied by each input.
+    ///
+    /// Inputs are allocated contiguous wires when a circuit is built or parsed, so each range
+    /// has the same length as the input. The order of the bits within the range is given by
+    /// the nodes of the corresponding [`BinaryRepr`], which may differ from the order of the
+    /// wire ids, eg. for inputs which have been reversed.
+    pub fn input_wire_layout(&self) -> Vec<(InputIndex, Range<usize>)> {
+        self.inputs
+            .iter()
+            .enumerate()
+            .map(|(idx, input)| {
+                let start = input.iter().map(|node| node.id()).min().unwrap_or(0);
+                let end = input.iter().map(|node| node.id() + 1).max().unwrap_or(0);
+
+                debug_assert_eq!(end - start, input.len(), "input wires are contiguous");
+
+                (idx, start..end)
+            })
+            .collect()
+    }
+
+    /// Returns the wire id of each bit of each output, in LSB0 order.
+    ///
+    /// Unlike inputs, output wires are assigned by the gates which produce them, so they
+    /// are generally not contiguous.
+    pub fn output_wire_layout(&self) -> Vec<(OutputIndex, Vec<usize>)> {
+        self.outputs
+            .iter()
+            .enumerate()
+            .map(|(idx, output)| (idx, output.iter().map(|node| node.id()).collect()))
+            .collect()
+    }
+
+    /// Returns a hash of the structure of the circuit.
+    ///
+    /// The hash commits to the types and wiring of the inputs and outputs, as well as
+    /// every gate in the circuit. Two circuits with the same hash compute the same function
+    /// with the same interface.
+    pub fn structural_hash(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+
+        hasher.update(&(self.feed_count as u64).to_le_bytes());
+        for reprs in [&self.inputs, &self.outputs] {
+            hasher.update(&(reprs.len() as u64).to_le_bytes());
+            for repr in reprs {
+                let ty = repr.value_type().to_string();
+                hasher.update(&(ty.len() as u64).to_le_bytes());
+                hasher.update(ty.as_bytes());
+                for node in repr.iter() {
+                    hasher.update(&(node.id() as u64).to_le_bytes());
+                }
+            }
+        }
+
+        hasher.update(&(self.gates.len() as u64).to_le_bytes());
+        for gate in &self.gates {
+            let tag: u8 = match gate.gate_type() {
+                GateType::Xor => 0,
+                GateType::And => 1,
+                GateType::Inv => 2,
+            };
+            hasher.update(&[tag]);
+            hasher.update(&(gate.x().id() as u64).to_le_bytes());
+            if let Some(y) = gate.y() {
+                hasher.update(&(y.id() as u64).to_le_bytes());
+            }
+            hasher.update(&(gate.z().id() as u64).to_le_bytes());
+        }
+
+        hasher.finalize().into()
+    }
+
+    /// Serializes the circuit into a compact, versioned binary format.
+    ///
+    /// The circuit can be deserialized with [`Circuit::from_bytes`], which is much
+    /// faster than parsing a Bristol circuit.
+    #[cfg(feature = "serde")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(&(SERIALIZATION_VERSION, self)).expect("circuit should serialize")
+    }
+
+    /// Deserializes a circuit produced by [`Circuit::to_bytes`].
+    ///
+    /// The circuit is validated, see [`CircuitError::InvalidCircuit`], so that a malformed
+    /// circuit can not cause a panic during evaluation.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The serialized circuit.
+    #[cfg(feature = "serde")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CircuitError> {
+        let version: u32 = bincode::deserialize(bytes)
+            .map_err(|e| CircuitError::SerializationError(e.to_string()))?;
+
+        if version != SERIALIZATION_VERSION {
+            return Err(CircuitError::UnsupportedVersion {
+                expected: SERIALIZATION_VERSION,
+                actual: version,
+            });
+        }
+
+        let (_, circ): (u32, Circuit) = bincode::deserialize(bytes)
+            .map_err(|e| CircuitError::SerializationError(e.to_string()))?;
+
+        circ.validate()?;
+
+        Ok(circ)
+    }
+
+    /// Validates the structure of a deserialized circuit.
+    ///
+    /// Every wire must be within the feed count, every gate must only read wires which are
+    /// inputs or are produced by an earlier gate, every output must be produced, and the
+    /// gate counts must match the gates.
+    #[cfg(feature = "serde")]
+    fn validate(&self) -> Result<(), CircuitError> {
+        let mut defined = vec![false; self.feed_count];
+
+        let check = |defined: &[bool], id: usize| -> Result<(), CircuitError> {
+            match defined.get(id) {
+                Some(true) => Ok(()),
+                Some(false) => Err(CircuitError::InvalidCircuit(format!(
+                    "wire {id} is read before it is produced"
+                ))),
+                None => Err(CircuitError::InvalidCircuit(format!(
+                    "wire {id} is out of bounds for feed count {}",
+                    self.feed_count
+                ))),
+            }
+        };
+
+        for node in self.inputs.iter().flat_map(|input| input.iter()) {
+            match defined.get_mut(node.id()) {
+                Some(defined) => *defined = true,
+                None => {
+                    return Err(CircuitError::InvalidCircuit(format!(
+                        "input wire {} is out of bounds for feed count {}",
+                        node.id(),
+                        self.feed_count
+                    )))
+                }
+            }
+        }
+
+        let (mut and_count, mut xor_count) = (0, 0);
+        for gate in &self.gates {
+            check(&defined, gate.x().id())?;
+            if let Some(y) = gate.y() {
+                check(&defined, y.id())?;
+            }
+
+            match defined.get_mut(gate.z().id()) {
+                Some(defined) => *defined = true,
+                None => {
+                    return Err(CircuitError::InvalidCircuit(format!(
+                        "gate output wire {} is out of bounds for feed count {}",
+                        gate.z().id(),
+                        self.feed_count
+                    )))
+                }
+            }
+
+            match gate.gate_type() {
+                GateType::And => and_count += 1,
+                GateType::Xor => xor_count += 1,
+                GateType::Inv => {}
+            }
+        }
+
+        for node in self.outputs.iter().flat_map(|output| output.iter()) {
+            check(&defined, node.id())?;
+        }
+
+        if and_count != self.and_count || xor_count != self.xor_count {
+            return Err(CircuitError::InvalidCircuit(format!(
+                "gate counts do not match the gates: expected {} AND and {} XOR, got {} AND and {} XOR",
+                self.and_count, self.xor_count, and_count, xor_count
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Reverses the order of the inputs.
     pub fn reverse_inputs(mut self) -> Self {
         self.inputs.reverse();
@@ -183,6 +419,24 @@ impl Circuit {
     }
 }
 
+#[cfg(any(test, feature = "test-utils"))]
+impl Circuit {
+    /// Removes the last gate of the circuit, leaving the feed it drives unassigned.
+    ///
+    /// The resulting circuit is malformed, this is only meant for testing how such circuits
+    /// are handled.
+    pub fn pop_gate(&mut self) -> Option<Gate> {
+        let gate = self.gates.pop()?;
+        match gate.gate_type() {
+            GateType::Xor => self.xor_count -= 1,
+            GateType::And => self.and_count -= 1,
+            GateType::Inv => {}
+        }
+
+        Some(gate)
+    }
+}
+
 impl IntoIterator for Circuit {
     type Item = Gate;
     type IntoIter = std::vec::IntoIter<Self::Item>;
@@ -221,4 +475,170 @@ mod tests {
 
         assert_eq!(out, 3u8);
     }
+
+    #[test]
+    fn test_structural_hash() {
+        let circ = build_adder();
+
+        assert_eq!(circ.structural_hash(), build_adder().structural_hash());
+        assert_ne!(
+            circ.structural_hash(),
+            circ.clone().reverse_inputs().structural_hash()
+        );
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        builder.add_output(a ^ b);
+        let xor = builder.build().unwrap();
+
+        assert_ne!(circ.structural_hash(), xor.structural_hash());
+    }
+
+    #[test]
+    fn test_wire_layout() {
+        let circ = build_adder();
+
+        assert_eq!(circ.input_wire_layout(), vec![(0, 0..8), (1, 8..16)]);
+
+        let outputs = circ.output_wire_layout();
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].1.len(), 8);
+    }
+
+    #[test]
+    #[cfg(feature = "aes")]
+    fn test_wire_layout_aes128() {
+        use std::collections::HashSet;
+
+        use crate::circuits::AES128;
+
+        assert_eq!(AES128.input_wire_layout(), vec![(0, 0..128), (1, 128..256)]);
+
+        let outputs = AES128.output_wire_layout();
+        assert_eq!(outputs.len(), 1);
+
+        let (idx, wires) = &outputs[0];
+        assert_eq!(*idx, 0);
+        assert_eq!(wires.len(), 128);
+        assert_eq!(wires.iter().collect::<HashSet<_>>().len(), 128);
+        assert!(wires
+            .iter()
+            .all(|wire| (256..AES128.feed_count()).contains(wire)));
+        assert_eq!(
+            wires,
+            &AES128.outputs()[0]
+                .iter()
+                .map(|node| node.id())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "aes"))]
+    fn test_to_from_bytes() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        use crate::circuits::AES128;
+
+        let bytes = AES128.to_bytes();
+        let circ = Circuit::from_bytes(&bytes).unwrap();
+
+        assert_eq!(circ.structural_hash(), AES128.structural_hash());
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..4 {
+            let inputs: Vec<Value> = AES128
+                .inputs()
+                .iter()
+                .map(|input| Value::random(&mut rng, &input.value_type()))
+                .collect();
+
+            assert_eq!(
+                circ.evaluate(&inputs).unwrap(),
+                AES128.evaluate(&inputs).unwrap()
+            );
+        }
+
+        let mut bytes = bytes;
+        bytes[0] ^= 0xff;
+        assert!(matches!(
+            Circuit::from_bytes(&bytes),
+            Err(CircuitError::UnsupportedVersion { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_from_bytes_invalid() {
+        let circ = build_adder();
+
+        // Gates in reverse order read wires before they are produced.
+        let mut reversed = circ.clone();
+        reversed.gates.reverse();
+
+        // Wires beyond the feed count.
+        let mut truncated = circ.clone();
+        truncated.feed_count -= 1;
+
+        // Gate counts which do not match the gates, which would underflow `inv_count`.
+        let mut miscounted = circ.clone();
+        miscounted.and_count += 1;
+
+        for invalid in [reversed, truncated, miscounted] {
+            assert!(matches!(
+                Circuit::from_bytes(&invalid.to_bytes()),
+                Err(CircuitError::InvalidCircuit(_))
+            ));
+        }
+
+        assert!(Circuit::from_bytes(&circ.to_bytes()).is_ok());
+    }
+
+    #[test]
+    fn test_layers() {
+        let circ = build_adder();
+        let layers = circ.layers();
+
+        // The layer which produces each feed, if any.
+        let mut producers = vec![None; circ.feed_count()];
+        for (layer, gates) in layers.iter().enumerate() {
+            assert!(!gates.is_empty());
+            for &id in gates {
+                producers[circ.gates()[id].z().id()] = Some(layer);
+            }
+        }
+
+        for (layer, gates) in layers.iter().enumerate() {
+            for &id in gates {
+                let gate = &circ.gates()[id];
+                for input in std::iter::once(gate.x()).chain(gate.y()) {
+                    if layer == 0 {
+                        // The first layer only depends on the circuit inputs.
+                        assert!(producers[input.id()].is_none());
+                    } else if let Some(producer) = producers[input.id()] {
+                        assert!(producer < layer);
+                    }
+                }
+            }
+        }
+
+        let mut ids = layers.into_iter().flatten().collect::<Vec<_>>();
+        ids.sort();
+
+        assert_eq!(ids, (0..circ.gates().len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_gate_counts() {
+        let circ = build_adder();
+
+        assert_eq!(circ.and_count(), 8);
+        assert_eq!(circ.xor_count(), 36);
+        assert_eq!(circ.inv_count(), 0);
+        assert_eq!(
+            circ.and_count() + circ.xor_count() + circ.inv_count(),
+            circ.gates().len()
+        );
+    }
 }
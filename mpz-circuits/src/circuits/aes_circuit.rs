@@ -0,0 +1,264 @@
+//! AES-256 built from gates, using the S-box circuit of Boyar and Peralta.
+//!
+//! Bytes are represented as arrays of nodes in LSB0 order, and the state of the cipher as an
+//! array of 16 bytes in column-major order, as in FIPS 197.
+
+use crate::{BuilderState, Feed, Node};
+
+type Byte = [Node<Feed>; 8];
+
+/// The round constants used by the AES-256 key schedule.
+const RCON: [u8; 7] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40];
+
+/// Encrypts a block with AES-256.
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the gates to.
+/// * `key` - The key.
+/// * `msg` - The message to encrypt.
+///
+/// # Returns
+///
+/// The ciphertext.
+pub(crate) fn aes256(state: &mut BuilderState, key: [Byte; 32], msg: [Byte; 16]) -> [Byte; 16] {
+    let round_keys = expand_key(state, key);
+
+    let mut block = add_round_key(state, msg, &round_keys[0]);
+    for (round, round_key) in round_keys.iter().enumerate().skip(1) {
+        block = block.map(|byte| sbox(state, byte));
+        block = shift_rows(block);
+        // The last round omits MixColumns.
+        if round != round_keys.len() - 1 {
+            block = mix_columns(state, block);
+        }
+        block = add_round_key(state, block, round_key);
+    }
+
+    block
+}
+
+/// Expands a 256-bit key into the 15 round keys of AES-256.
+fn expand_key(state: &mut BuilderState, key: [Byte; 32]) -> Vec<[Byte; 16]> {
+    let mut words: Vec<[Byte; 4]> = key
+        .chunks_exact(4)
+        .map(|word| word.try_into().expect("word is 4 bytes"))
+        .collect();
+
+    for i in 8..60 {
+        let mut temp = words[i - 1];
+        if i % 8 == 0 {
+            temp.rotate_left(1);
+            temp = temp.map(|byte| sbox(state, byte));
+            temp[0] = xor_const(state, temp[0], RCON[i / 8 - 1]);
+        } else if i % 8 == 4 {
+            temp = temp.map(|byte| sbox(state, byte));
+        }
+
+        let prev = words[i - 8];
+        words.push(std::array::from_fn(|j| xor(state, prev[j], temp[j])));
+    }
+
+    words
+        .chunks_exact(4)
+        .map(|words| std::array::from_fn(|i| words[i / 4][i % 4]))
+        .collect()
+}
+
+fn add_round_key(state: &mut BuilderState, block: [Byte; 16], key: &[Byte; 16]) -> [Byte; 16] {
+    std::array::from_fn(|i| xor(state, block[i], key[i]))
+}
+
+fn shift_rows(block: [Byte; 16]) -> [Byte; 16] {
+    std::array::from_fn(|i| {
+        let (row, col) = (i % 4, i / 4);
+        block[row + 4 * ((col + row) % 4)]
+    })
+}
+
+fn mix_columns(state: &mut BuilderState, block: [Byte; 16]) -> [Byte; 16] {
+    // Each output byte is computed as `a_i + t + x * (a_i + a_{i+1})`, where `t` is the sum of
+    // the bytes in the column.
+    let mut out = block;
+    for (col, out_col) in block.chunks_exact(4).zip(out.chunks_exact_mut(4)) {
+        let t = xor(state, col[0], col[1]);
+        let t = xor(state, t, col[2]);
+        let t = xor(state, t, col[3]);
+
+        for (i, out_byte) in out_col.iter_mut().enumerate() {
+            let a = xor(state, col[i], col[(i + 1) % 4]);
+            let a = xtime(state, a);
+            let a = xor(state, a, t);
+            *out_byte = xor(state, a, col[i]);
+        }
+    }
+
+    out
+}
+
+/// Multiplies a byte by `x` in GF(2^8).
+fn xtime(state: &mut BuilderState, a: Byte) -> Byte {
+    [
+        a[7],
+        state.add_xor_gate(a[0], a[7]),
+        a[1],
+        state.add_xor_gate(a[2], a[7]),
+        state.add_xor_gate(a[3], a[7]),
+        a[4],
+        a[5],
+        a[6],
+    ]
+}
+
+fn xor(state: &mut BuilderState, a: Byte, b: Byte) -> Byte {
+    std::array::from_fn(|i| state.add_xor_gate(a[i], b[i]))
+}
+
+fn xor_const(state: &mut BuilderState, a: Byte, b: u8) -> Byte {
+    std::array::from_fn(|i| state.add_xor_gate(a[i], Node::new(((b >> i) & 1) as usize)))
+}
+
+/// The AES S-box.
+///
+/// This is the circuit from "A depth-16 circuit for the AES S-box" by Boyar and Peralta, where
+/// `u[0]` is the most significant bit of the input.
+fn sbox(state: &mut BuilderState, x: Byte) -> Byte {
+    let u: [Node<Feed>; 8] = std::array::from_fn(|i| x[7 - i]);
+
+    let t1 = state.add_xor_gate(u[0], u[3]);
+    let t2 = state.add_xor_gate(u[0], u[5]);
+    let t3 = state.add_xor_gate(u[0], u[6]);
+    let t4 = state.add_xor_gate(u[3], u[5]);
+    let t5 = state.add_xor_gate(u[4], u[6]);
+    let t6 = state.add_xor_gate(t1, t5);
+    let t7 = state.add_xor_gate(u[1], u[2]);
+    let t8 = state.add_xor_gate(u[7], t6);
+    let t9 = state.add_xor_gate(u[7], t7);
+    let t10 = state.add_xor_gate(t6, t7);
+    let t11 = state.add_xor_gate(u[1], u[5]);
+    let t12 = state.add_xor_gate(u[2], u[5]);
+    let t13 = state.add_xor_gate(t3, t4);
+    let t14 = state.add_xor_gate(t6, t11);
+    let t15 = state.add_xor_gate(t5, t11);
+    let t16 = state.add_xor_gate(t5, t12);
+    let t17 = state.add_xor_gate(t9, t16);
+    let t18 = state.add_xor_gate(u[3], u[7]);
+    let t19 = state.add_xor_gate(t7, t18);
+    let t20 = state.add_xor_gate(t1, t19);
+    let t21 = state.add_xor_gate(u[6], u[7]);
+    let t22 = state.add_xor_gate(t7, t21);
+    let t23 = state.add_xor_gate(t2, t22);
+    let t24 = state.add_xor_gate(t2, t10);
+    let t25 = state.add_xor_gate(t20, t17);
+    let t26 = state.add_xor_gate(t3, t16);
+    let t27 = state.add_xor_gate(t1, t12);
+
+    let m1 = state.add_and_gate(t13, t6);
+    let m2 = state.add_and_gate(t23, t8);
+    let m3 = state.add_xor_gate(t14, m1);
+    let m4 = state.add_and_gate(t19, u[7]);
+    let m5 = state.add_xor_gate(m4, m1);
+    let m6 = state.add_and_gate(t3, t16);
+    let m7 = state.add_and_gate(t22, t9);
+    let m8 = state.add_xor_gate(t26, m6);
+    let m9 = state.add_and_gate(t20, t17);
+    let m10 = state.add_xor_gate(m9, m6);
+    let m11 = state.add_and_gate(t1, t15);
+    let m12 = state.add_and_gate(t4, t27);
+    let m13 = state.add_xor_gate(m12, m11);
+    let m14 = state.add_and_gate(t2, t10);
+    let m15 = state.add_xor_gate(m14, m11);
+    let m16 = state.add_xor_gate(m3, m2);
+    let m17 = state.add_xor_gate(m5, t24);
+    let m18 = state.add_xor_gate(m8, m7);
+    let m19 = state.add_xor_gate(m10, m15);
+    let m20 = state.add_xor_gate(m16, m13);
+    let m21 = state.add_xor_gate(m17, m15);
+    let m22 = state.add_xor_gate(m18, m13);
+    let m23 = state.add_xor_gate(m19, t25);
+    let m24 = state.add_xor_gate(m22, m23);
+    let m25 = state.add_and_gate(m22, m20);
+    let m26 = state.add_xor_gate(m21, m25);
+    let m27 = state.add_xor_gate(m20, m21);
+    let m28 = state.add_xor_gate(m23, m25);
+    let m29 = state.add_and_gate(m28, m27);
+    let m30 = state.add_and_gate(m26, m24);
+    let m31 = state.add_and_gate(m20, m23);
+    let m32 = state.add_and_gate(m27, m31);
+    let m33 = state.add_xor_gate(m27, m25);
+    let m34 = state.add_and_gate(m21, m22);
+    let m35 = state.add_and_gate(m24, m34);
+    let m36 = state.add_xor_gate(m24, m25);
+    let m37 = state.add_xor_gate(m21, m29);
+    let m38 = state.add_xor_gate(m32, m33);
+    let m39 = state.add_xor_gate(m23, m30);
+    let m40 = state.add_xor_gate(m35, m36);
+    let m41 = state.add_xor_gate(m38, m40);
+    let m42 = state.add_xor_gate(m37, m39);
+    let m43 = state.add_xor_gate(m37, m38);
+    let m44 = state.add_xor_gate(m39, m40);
+    let m45 = state.add_xor_gate(m42, m41);
+    let m46 = state.add_and_gate(m44, t6);
+    let m47 = state.add_and_gate(m40, t8);
+    let m48 = state.add_and_gate(m39, u[7]);
+    let m49 = state.add_and_gate(m43, t16);
+    let m50 = state.add_and_gate(m38, t9);
+    let m51 = state.add_and_gate(m37, t17);
+    let m52 = state.add_and_gate(m42, t15);
+    let m53 = state.add_and_gate(m45, t27);
+    let m54 = state.add_and_gate(m41, t10);
+    let m55 = state.add_and_gate(m44, t13);
+    let m56 = state.add_and_gate(m40, t23);
+    let m57 = state.add_and_gate(m39, t19);
+    let m58 = state.add_and_gate(m43, t3);
+    let m59 = state.add_and_gate(m38, t22);
+    let m60 = state.add_and_gate(m37, t20);
+    let m61 = state.add_and_gate(m42, t1);
+    let m62 = state.add_and_gate(m45, t4);
+    let m63 = state.add_and_gate(m41, t2);
+
+    let l0 = state.add_xor_gate(m61, m62);
+    let l1 = state.add_xor_gate(m50, m56);
+    let l2 = state.add_xor_gate(m46, m48);
+    let l3 = state.add_xor_gate(m47, m55);
+    let l4 = state.add_xor_gate(m54, m58);
+    let l5 = state.add_xor_gate(m49, m61);
+    let l6 = state.add_xor_gate(m62, l5);
+    let l7 = state.add_xor_gate(m46, l3);
+    let l8 = state.add_xor_gate(m51, m59);
+    let l9 = state.add_xor_gate(m52, m53);
+    let l10 = state.add_xor_gate(m53, l4);
+    let l11 = state.add_xor_gate(m60, l2);
+    let l12 = state.add_xor_gate(m48, m51);
+    let l13 = state.add_xor_gate(m50, l0);
+    let l14 = state.add_xor_gate(m52, m61);
+    let l15 = state.add_xor_gate(m55, l1);
+    let l16 = state.add_xor_gate(m56, l0);
+    let l17 = state.add_xor_gate(m57, l1);
+    let l18 = state.add_xor_gate(m58, l8);
+    let l19 = state.add_xor_gate(m63, l4);
+    let l20 = state.add_xor_gate(l0, l1);
+    let l21 = state.add_xor_gate(l1, l7);
+    let l22 = state.add_xor_gate(l3, l12);
+    let l23 = state.add_xor_gate(l18, l2);
+    let l24 = state.add_xor_gate(l15, l9);
+    let l25 = state.add_xor_gate(l6, l10);
+    let l26 = state.add_xor_gate(l7, l9);
+    let l27 = state.add_xor_gate(l8, l10);
+    let l28 = state.add_xor_gate(l11, l14);
+    let l29 = state.add_xor_gate(l11, l17);
+    let s0 = state.add_xor_gate(l6, l24);
+    let s1 = state.add_xor_gate(l16, l26);
+    let s1 = state.add_inv_gate(s1);
+    let s2 = state.add_xor_gate(l19, l28);
+    let s2 = state.add_inv_gate(s2);
+    let s3 = state.add_xor_gate(l6, l21);
+    let s4 = state.add_xor_gate(l20, l22);
+    let s5 = state.add_xor_gate(l25, l29);
+    let s6 = state.add_xor_gate(l13, l27);
+    let s6 = state.add_inv_gate(s6);
+    let s7 = state.add_xor_gate(l6, l23);
+    let s7 = state.add_inv_gate(s7);
+
+    [s7, s6, s5, s4, s3, s2, s1, s0]
+}
@@ -35,18 +35,57 @@ pub fn nbyte_add_mod_trace<'a, const N: usize>(
     b.reverse();
     modulus.reverse();
 
-    // NO OPERATIONS USING CONST GENERICS YET :(
-    // Otherwise, we would just use a const generic array here with length (N * 8) + 1
+    let mut sum_reduced: [Tracer<'a, U8>; N] = nbyte_add_mod_le_trace(state, &a, &b, &modulus)
+        .try_into()
+        .unwrap();
+
+    // Reverse the array back to big-endian
+    sum_reduced.reverse();
+
+    sum_reduced
+}
+
+/// Add two numbers modulo a constant modulus.
+///
+/// This is the same as [`nbyte_add_mod_trace`], except that the numbers are encoded in
+/// little-endian order and their length does not need to be known at compile time.
+///
+/// This circuit assumes that the summands are in the range [0, modulus).
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `a` - The first number encoded as bytes in little-endian order.
+/// * `b` - The second number encoded as bytes in little-endian order.
+/// * `modulus` - The modulus encoded as bytes in little-endian order.
+///
+/// # Returns
+///
+/// (a + b) % modulus, encoded as bytes in little-endian order.
+///
+/// # Panics
+///
+/// Panics if `a`, `b` and `modulus` do not have the same length.
+pub fn nbyte_add_mod_le_trace<'a>(
+    state: &'a RefCell<BuilderState>,
+    a: &[Tracer<'a, U8>],
+    b: &[Tracer<'a, U8>],
+    modulus: &[u8],
+) -> Vec<Tracer<'a, U8>> {
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.len(), modulus.len());
+
     let mut a_bits = a
-        .into_iter()
+        .iter()
         .flat_map(|a| a.to_inner().nodes().into_iter())
         .collect::<Vec<_>>();
     let mut b_bits = b
-        .into_iter()
+        .iter()
         .flat_map(|b| b.to_inner().nodes().into_iter())
         .collect::<Vec<_>>();
     let mut modulus_bits = modulus
-        .into_iter()
+        .iter()
+        .copied()
         .flat_map(|m| m.into_iter_lsb0())
         .map(|bit| Node::new(bit as usize))
         .collect::<Vec<_>>();
@@ -66,17 +105,10 @@ pub fn nbyte_add_mod_trace<'a, const N: usize>(
     // Pop off the extra bit
     sum_reduced.pop();
 
-    let mut sum_reduced: [U8; N] = sum_reduced
+    sum_reduced
         .chunks(8)
-        .map(|chunk| U8::new(chunk.try_into().unwrap()))
-        .collect::<Vec<_>>()
-        .try_into()
-        .unwrap();
-
-    // Reverse the array back to big-endian
-    sum_reduced.reverse();
-
-    sum_reduced.map(|v| Tracer::new(state, v))
+        .map(|chunk| Tracer::new(state, U8::new(chunk.try_into().unwrap())))
+        .collect()
 }
 
 #[cfg(test)]
@@ -112,4 +144,32 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_nbyte_add_mod_le() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_array_input::<u8, 2>();
+        let b = builder.add_array_input::<u8, 2>();
+        let modulus = [239u8, 1u8];
+
+        let sum = nbyte_add_mod_le_trace(builder.state(), &a, &b, &modulus)
+            .into_iter()
+            .map(|v| v.to_inner())
+            .collect::<Vec<_>>();
+
+        builder.add_output(sum);
+
+        let circ = builder.build().unwrap();
+
+        let modulus = u16::from_le_bytes(modulus);
+        for (a, b) in [(0u16, 0u16), (1, 2), (300, 100), (494, 494)] {
+            let expected_sum = (a + b) % modulus;
+
+            let sum: [u8; 2] =
+                evaluate!(circ, fn(a.to_le_bytes(), b.to_le_bytes()) -> [u8; 2]).unwrap();
+
+            assert_eq!(u16::from_le_bytes(sum), expected_sum);
+        }
+    }
 }
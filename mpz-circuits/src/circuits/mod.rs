@@ -1,5 +1,7 @@
 //! Pre-built circuits for MPC.
 
+#[cfg(feature = "aes")]
+mod aes_circuit;
 pub mod big_num;
 
 use once_cell::sync::Lazy;
@@ -21,6 +23,14 @@ pub static AES128: Lazy<Arc<Circuit>> = Lazy::new(|| {
     Arc::new(bincode::deserialize(bytes).unwrap())
 });
 
+/// AES-256 circuit.
+///
+/// The circuit has the following signature:
+///
+/// `fn(key: [u8; 32], msg: [u8; 16]) -> [u8; 16]`
+#[cfg(feature = "aes")]
+pub static AES256: Lazy<Arc<Circuit>> = Lazy::new(|| Arc::new(build_aes256()));
+
 /// SHA-256 circuit.
 ///
 /// The circuit has the following signature:
@@ -66,6 +76,58 @@ pub fn aes128_trace<'a>(
     ciphertext.map(|value| Tracer::new(state, value.try_into().unwrap()))
 }
 
+/// AES-256 circuit trace.
+///
+/// This function is a wrapper around the AES-256 circuit that can be used to append
+/// it to other circuits.
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `key` - The key to use.
+/// * `msg` - The message to encrypt.
+///
+/// # Returns
+///
+/// The ciphertext.
+#[cfg(feature = "aes")]
+pub fn aes256_trace<'a>(
+    state: &'a RefCell<BuilderState>,
+    key: [Tracer<'a, U8>; 32],
+    msg: [Tracer<'a, U8>; 16],
+) -> [Tracer<'a, U8>; 16] {
+    let mut outputs = state
+        .borrow_mut()
+        .append(&AES256, &[key.into(), msg.into()])
+        .expect("aes 256 should append successfully");
+
+    let BinaryRepr::Array(ciphertext) = outputs.pop().unwrap() else {
+        panic!("aes 256 should have array output");
+    };
+
+    let ciphertext: [_; 16] = ciphertext.try_into().unwrap();
+
+    ciphertext.map(|value| Tracer::new(state, value.try_into().unwrap()))
+}
+
+/// Builds the AES-256 circuit, see [`AES256`].
+#[cfg(feature = "aes")]
+fn build_aes256() -> Circuit {
+    let builder = CircuitBuilder::new();
+    let key = builder.add_array_input::<u8, 32>();
+    let msg = builder.add_array_input::<u8, 16>();
+
+    let ciphertext = aes_circuit::aes256(
+        &mut builder.state().borrow_mut(),
+        key.map(|byte| byte.to_inner().nodes()),
+        msg.map(|byte| byte.to_inner().nodes()),
+    );
+
+    builder.add_output(ciphertext.map(|byte| Tracer::new(builder.state(), U8::new(byte))));
+
+    builder.build().expect("circuit is valid")
+}
+
 /// SHA-256 compression circuit trace.
 ///
 /// This function is a wrapper around the SHA256 compression circuit that can be used to append
@@ -270,6 +332,82 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "aes")]
+    fn test_aes128_gate_counts() {
+        assert_eq!(AES128.and_count(), 6400);
+        assert_eq!(AES128.xor_count(), 28176);
+        assert_eq!(AES128.inv_count(), 2087);
+    }
+
+    #[test]
+    #[cfg(feature = "aes")]
+    fn test_aes128_fan_out() {
+        use std::collections::HashSet;
+
+        let mut fan_out = vec![0usize; AES128.feed_count()];
+        for gate in AES128.gates() {
+            for input in gate.inputs() {
+                fan_out[input.id()] += 1;
+            }
+        }
+
+        let outputs: HashSet<_> = AES128
+            .outputs()
+            .iter()
+            .flat_map(|output| output.iter().map(|node| node.id()))
+            .collect();
+
+        // Every gate output is either consumed by another gate or is a circuit output.
+        for gate in AES128.gates() {
+            let z = gate.z().id();
+            assert!(fan_out[z] > 0 || outputs.contains(&z));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "aes")]
+    fn test_aes256() {
+        use aes::cipher::{BlockEncrypt, KeyInit};
+
+        fn aes_256(key: [u8; 32], msg: [u8; 16]) -> [u8; 16] {
+            use aes::Aes256;
+
+            let aes = Aes256::new_from_slice(&key).unwrap();
+            let mut ciphertext = msg.into();
+            aes.encrypt_block(&mut ciphertext);
+            ciphertext.into()
+        }
+
+        test_circ!(
+            AES256,
+            aes_256,
+            fn([0u8; 32], [69u8; 16]) -> [u8; 16]
+        );
+
+        // FIPS 197, Appendix C.3
+        let key: [u8; 32] = std::array::from_fn(|i| i as u8);
+        let msg = [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff,
+        ];
+        assert_eq!(
+            aes_256(key, msg),
+            [
+                0x8e, 0xa2, 0xb7, 0xca, 0x51, 0x67, 0x45, 0xbf, 0xea, 0xfc, 0x49, 0x90, 0x4b, 0x49,
+                0x60, 0x89
+            ]
+        );
+        test_circ!(AES256, aes_256, fn(key, msg) -> [u8; 16]);
+    }
+
+    #[test]
+    #[cfg(feature = "aes")]
+    fn test_aes256_and_count() {
+        // 14 rounds of 16 S-boxes, and 13 S-box words in the key schedule, 34 AND gates each.
+        assert_eq!(AES256.and_count(), (14 * 16 + 13 * 4) * 34);
+    }
+
     #[test]
     #[cfg(feature = "sha2")]
     fn test_sha256_compress() {
@@ -291,4 +429,19 @@ mod tests {
             test_circ!(circ, reference, fn(SHA2_INITIAL_STATE, msg.as_slice()) -> [u8; 32]);
         }
     }
+
+    #[test]
+    #[cfg(feature = "sha2")]
+    fn test_sha256_digest() {
+        use sha2::{Digest, Sha256};
+
+        // Lengths around the padding boundaries of one and two blocks.
+        for len in [1, 55, 56, 63, 64, 119, 120, 128] {
+            let msg: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let circ = build_sha256(0, len);
+            let reference = |_, msg: &[u8]| -> [u8; 32] { Sha256::digest(msg).into() };
+
+            test_circ!(circ, reference, fn(SHA2_INITIAL_STATE, msg.as_slice()) -> [u8; 32]);
+        }
+    }
 }
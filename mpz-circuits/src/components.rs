@@ -50,6 +50,11 @@ impl Gate {
         }
     }
 
+    /// Returns an iterator over the inputs of the gate.
+    pub fn inputs(&self) -> impl Iterator<Item = Node<Sink>> {
+        std::iter::once(self.x()).chain(self.y())
+    }
+
     /// Returns the z output of the gate.
     pub fn z(&self) -> Node<Feed> {
         match self {
@@ -16,10 +16,12 @@ pub mod types;
 #[doc(hidden)]
 pub use builder::BuilderState;
 pub use builder::{BuilderError, CircuitBuilder};
-pub use circuit::{Circuit, CircuitError};
+pub use circuit::{Circuit, CircuitError, GateId, InputIndex, OutputIndex};
 #[doc(hidden)]
 pub use components::{Feed, Node, Sink};
 pub use components::{Gate, GateType};
+#[cfg(feature = "parse")]
+pub use parse::{ParseError, ParseErrorReason};
 pub use tracer::Tracer;
 
 pub use once_cell;
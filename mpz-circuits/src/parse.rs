@@ -3,23 +3,69 @@ use crate::{
     types::ValueType,
     Circuit, CircuitBuilder,
 };
-use regex::{Captures, Regex};
 use std::collections::HashMap;
 
-static GATE_PATTERN: &str = r"(?P<input_count>\d+)\s(?P<output_count>\d+)\s(?P<xref>\d+)\s(?:(?P<yref>\d+)\s)?(?P<zref>\d+)\s(?P<gate>INV|AND|XOR)";
-
+/// An error that can occur when parsing a circuit.
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
+    /// An I/O error occurred while reading the circuit.
     #[error(transparent)]
     IOError(#[from] std::io::Error),
+    /// The circuit description is malformed.
+    #[error("line {line}, column {col}: {reason}")]
+    Malformed {
+        /// The line of the error, starting at 1.
+        line: usize,
+        /// The column of the error, starting at 1.
+        col: usize,
+        /// The reason the circuit description is malformed.
+        reason: ParseErrorReason,
+    },
+    /// An error occurred while building the circuit.
     #[error(transparent)]
-    ParseIntError(#[from] std::num::ParseIntError),
-    #[error("uninitialized feed: {0}")]
-    UninitializedFeed(usize),
+    BuilderError(#[from] crate::BuilderError),
+}
+
+/// The reason a circuit description is malformed.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ParseErrorReason {
+    #[error("unexpected end of file")]
+    UnexpectedEndOfFile,
+    #[error("unexpected end of line")]
+    UnexpectedEndOfLine,
+    #[error("unexpected token: {0}")]
+    UnexpectedToken(String),
+    #[error("invalid integer: {0}")]
+    InvalidInteger(String),
+    #[error("input lengths do not match: expected {expected:?}, got {actual:?}")]
+    InputMismatch {
+        expected: Vec<usize>,
+        actual: Vec<usize>,
+    },
+    #[error("output lengths do not match: expected {expected:?}, got {actual:?}")]
+    OutputMismatch {
+        expected: Vec<usize>,
+        actual: Vec<usize>,
+    },
+    #[error("circuit has {wire_count} wires, but {required} are required")]
+    InsufficientWires { wire_count: usize, required: usize },
     #[error("unsupported gate type: {0}")]
     UnsupportedGateType(String),
-    #[error(transparent)]
-    BuilderError(#[from] crate::BuilderError),
+    #[error("invalid arity for {gate:?} gate: {inputs} inputs and {outputs} outputs")]
+    InvalidArity {
+        gate: GateType,
+        inputs: usize,
+        outputs: usize,
+    },
+    #[error("wire {wire} is out of range, circuit has {wire_count} wires")]
+    WireOutOfRange { wire: usize, wire_count: usize },
+    #[error("wire {0} is used before it is defined")]
+    UndefinedWire(usize),
+    #[error("wire {0} is already defined")]
+    RedefinedWire(usize),
+    #[error("invalid gate count: expected {expected}, got {actual}")]
+    GateCountMismatch { expected: usize, actual: usize },
 }
 
 impl Circuit {
@@ -43,9 +89,70 @@ impl Circuit {
     ) -> Result<Self, ParseError> {
         let file = std::fs::read_to_string(filename)?;
 
+        Self::parse_str(&file, inputs, outputs)
+    }
+
+    /// Parses a circuit in Bristol-fashion format from a string.
+    ///
+    /// The input is not trusted, any malformed input is reported as a
+    /// [`ParseError::Malformed`] with the position of the offending token.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The circuit description.
+    /// * `inputs` - The types of the inputs to the circuit.
+    /// * `outputs` - The types of the outputs to the circuit.
+    ///
+    /// # Returns
+    ///
+    /// The parsed circuit.
+    pub fn parse_str(
+        source: &str,
+        inputs: &[ValueType],
+        outputs: &[ValueType],
+    ) -> Result<Self, ParseError> {
+        let mut lines = source
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| Line::new(idx + 1, line))
+            .filter(|line| !line.tokens.is_empty());
+        let eof = source.lines().count() + 1;
+        let mut next_line = || {
+            lines
+                .next()
+                .ok_or_else(|| malformed(eof, 1, ParseErrorReason::UnexpectedEndOfFile))
+        };
+
+        let header = next_line()?;
+        let mut tokens = header.tokens();
+        let gate_count = tokens.next_usize()?;
+        let wire_count = tokens.next_usize()?;
+        tokens.finish()?;
+
+        let input_lens = next_line()?.parse_lens(inputs, |expected, actual| {
+            ParseErrorReason::InputMismatch { expected, actual }
+        })?;
+        let output_line = next_line()?;
+        let output_lens = output_line.parse_lens(outputs, |expected, actual| {
+            ParseErrorReason::OutputMismatch { expected, actual }
+        })?;
+
+        // The inputs are assigned to the first wires of the circuit, and the outputs to the last.
+        let input_wires: usize = input_lens.iter().sum();
+        let output_wires: usize = output_lens.iter().sum();
+        let required = input_wires.max(output_wires);
+        if required > wire_count {
+            return Err(header.error(
+                1,
+                ParseErrorReason::InsufficientWires {
+                    wire_count,
+                    required,
+                },
+            ));
+        }
+
         let builder = CircuitBuilder::new();
 
-        let mut feed_ids: Vec<usize> = Vec::new();
         let mut feed_map: HashMap<usize, Node<Feed>> = HashMap::default();
 
         let mut input_len = 0;
@@ -58,60 +165,67 @@ impl Circuit {
         }
 
         let mut state = builder.state().borrow_mut();
-        let pattern = Regex::new(GATE_PATTERN).unwrap();
-        for cap in pattern.captures_iter(&file) {
+        let mut parsed_gates = 0;
+        while let Some(line) = lines.next() {
+            if parsed_gates == gate_count {
+                return Err(line.error(
+                    0,
+                    ParseErrorReason::GateCountMismatch {
+                        expected: gate_count,
+                        actual: gate_count + 1 + lines.count(),
+                    },
+                ));
+            }
+
             let UncheckedGate {
                 xref,
                 yref,
                 zref,
                 gate_type,
-            } = UncheckedGate::parse(cap)?;
-            feed_ids.push(zref);
+            } = line.parse_gate(wire_count, &feed_map)?;
 
-            match gate_type {
+            let new_x = feed_map[&xref];
+            let new_z = match gate_type {
                 GateType::Xor => {
-                    let new_x = feed_map
-                        .get(&xref)
-                        .ok_or(ParseError::UninitializedFeed(xref))?;
-                    let new_y = feed_map
-                        .get(&yref.unwrap())
-                        .ok_or(ParseError::UninitializedFeed(yref.unwrap()))?;
-                    let new_z = state.add_xor_gate(*new_x, *new_y);
-                    feed_map.insert(zref, new_z);
+                    state.add_xor_gate(new_x, feed_map[&yref.expect("arity is checked")])
                 }
                 GateType::And => {
-                    let new_x = feed_map
-                        .get(&xref)
-                        .ok_or(ParseError::UninitializedFeed(xref))?;
-                    let new_y = feed_map
-                        .get(&yref.unwrap())
-                        .ok_or(ParseError::UninitializedFeed(yref.unwrap()))?;
-                    let new_z = state.add_and_gate(*new_x, *new_y);
-                    feed_map.insert(zref, new_z);
+                    state.add_and_gate(new_x, feed_map[&yref.expect("arity is checked")])
                 }
-                GateType::Inv => {
-                    let new_x = feed_map
-                        .get(&xref)
-                        .ok_or(ParseError::UninitializedFeed(xref))?;
-                    let new_z = state.add_inv_gate(*new_x);
-                    feed_map.insert(zref, new_z);
-                }
-            }
+                GateType::Inv => state.add_inv_gate(new_x),
+            };
+            feed_map.insert(zref, new_z);
+            parsed_gates += 1;
         }
         drop(state);
-        feed_ids.sort();
 
-        for output in outputs.iter().rev() {
-            let feeds = feed_ids
-                .drain(feed_ids.len() - output.len()..)
+        if parsed_gates != gate_count {
+            return Err(malformed(
+                eof,
+                1,
+                ParseErrorReason::GateCountMismatch {
+                    expected: gate_count,
+                    actual: parsed_gates,
+                },
+            ));
+        }
+
+        let mut output_ids = wire_count - output_wires..wire_count;
+        for output in outputs {
+            let feeds = output_ids
+                .by_ref()
+                .take(output.len())
                 .map(|id| {
-                    *feed_map
+                    feed_map
                         .get(&id)
-                        .expect("Old feed should be mapped to new feed")
+                        .copied()
+                        .ok_or_else(|| output_line.error(0, ParseErrorReason::UndefinedWire(id)))
                 })
-                .collect::<Vec<Node<Feed>>>();
+                .collect::<Result<Vec<Node<Feed>>, _>>()?;
 
-            let output = output.to_bin_repr(&feeds).unwrap();
+            let output = output
+                .to_bin_repr(&feeds)
+                .expect("output length is checked");
             builder.add_output(output);
         }
 
@@ -119,39 +233,212 @@ impl Circuit {
     }
 }
 
-struct UncheckedGate {
-    xref: usize,
-    yref: Option<usize>,
-    zref: usize,
-    gate_type: GateType,
+/// Returns a malformed error at the given position.
+fn malformed(line: usize, col: usize, reason: ParseErrorReason) -> ParseError {
+    ParseError::Malformed { line, col, reason }
+}
+
+/// A non-empty line of a circuit description.
+struct Line<'a> {
+    /// The line number, starting at 1.
+    number: usize,
+    /// The column of the end of the line, starting at 1.
+    end: usize,
+    /// The tokens of the line and their columns.
+    tokens: Vec<(usize, &'a str)>,
 }
 
-impl UncheckedGate {
-    fn parse(captures: Captures) -> Result<Self, ParseError> {
-        let xref: usize = captures.name("xref").unwrap().as_str().parse()?;
-        let yref: Option<usize> = captures
-            .name("yref")
-            .map(|yref| yref.as_str().parse())
-            .transpose()?;
-        let zref: usize = captures.name("zref").unwrap().as_str().parse()?;
-        let gate_type = captures.name("gate").unwrap().as_str();
-
-        let gate_type = match gate_type {
+impl<'a> Line<'a> {
+    fn new(number: usize, line: &'a str) -> Self {
+        let mut tokens = Vec::new();
+        let mut start = None;
+        let mut col = 0;
+        for (idx, (offset, c)) in line.char_indices().enumerate() {
+            col = idx + 1;
+            match (start, c.is_whitespace()) {
+                (None, false) => start = Some((col, offset)),
+                (Some((start_col, start_offset)), true) => {
+                    tokens.push((start_col, &line[start_offset..offset]));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some((start_col, start_offset)) = start {
+            tokens.push((start_col, &line[start_offset..]));
+        }
+
+        Self {
+            number,
+            end: col + 1,
+            tokens,
+        }
+    }
+
+    /// Returns an error at the token with the given index, or at the end of the line.
+    fn error(&self, token: usize, reason: ParseErrorReason) -> ParseError {
+        let col = self
+            .tokens
+            .get(token)
+            .map(|(col, _)| *col)
+            .unwrap_or(self.end);
+        malformed(self.number, col, reason)
+    }
+
+    fn tokens(&self) -> Tokens<'_, 'a> {
+        Tokens { line: self, pos: 0 }
+    }
+
+    /// Parses a list of value lengths, checking them against the expected types.
+    fn parse_lens(
+        &self,
+        types: &[ValueType],
+        mismatch: impl FnOnce(Vec<usize>, Vec<usize>) -> ParseErrorReason,
+    ) -> Result<Vec<usize>, ParseError> {
+        let mut tokens = self.tokens();
+        let count = tokens.next_usize()?;
+
+        let mut lens = Vec::new();
+        while lens.len() < count {
+            lens.push(tokens.next_usize()?);
+        }
+        tokens.finish()?;
+
+        let expected: Vec<usize> = types.iter().map(|ty| ty.len()).collect();
+        if lens != expected {
+            return Err(self.error(0, mismatch(expected, lens)));
+        }
+
+        Ok(lens)
+    }
+
+    /// Parses a gate, checking that its wires are in range and that the circuit is acyclic.
+    fn parse_gate(
+        &self,
+        wire_count: usize,
+        defined: &HashMap<usize, Node<Feed>>,
+    ) -> Result<UncheckedGate, ParseError> {
+        let mut tokens = self.tokens();
+        let input_count = tokens.next_usize()?;
+        let output_count = tokens.next_usize()?;
+
+        // The gate type is the last token of the line.
+        let gate_pos = self.tokens.len() - 1;
+        let gate = self.tokens[gate_pos].1;
+        let gate_type = match gate {
             "XOR" => GateType::Xor,
             "AND" => GateType::And,
             "INV" => GateType::Inv,
-            _ => return Err(ParseError::UnsupportedGateType(gate_type.to_string())),
+            _ => {
+                return Err(self.error(
+                    gate_pos,
+                    ParseErrorReason::UnsupportedGateType(gate.to_string()),
+                ))
+            }
         };
 
-        Ok(Self {
-            xref,
-            yref,
+        let arity = match gate_type {
+            GateType::Xor | GateType::And => (2, 1),
+            GateType::Inv => (1, 1),
+        };
+        if (input_count, output_count) != arity {
+            return Err(self.error(
+                0,
+                ParseErrorReason::InvalidArity {
+                    gate: gate_type,
+                    inputs: input_count,
+                    outputs: output_count,
+                },
+            ));
+        }
+
+        let mut wires = Vec::with_capacity(3);
+        for _ in 0..input_count + output_count {
+            let pos = tokens.pos;
+            if pos == gate_pos {
+                return Err(self.error(pos, ParseErrorReason::UnexpectedToken(gate.to_string())));
+            }
+
+            let wire = tokens.next_usize()?;
+            if wire >= wire_count {
+                return Err(self.error(pos, ParseErrorReason::WireOutOfRange { wire, wire_count }));
+            }
+
+            wires.push((pos, wire));
+        }
+
+        if tokens.pos != gate_pos {
+            return Err(self.error(
+                tokens.pos,
+                ParseErrorReason::UnexpectedToken(self.tokens[tokens.pos].1.to_string()),
+            ));
+        }
+
+        for &(pos, wire) in &wires[..input_count] {
+            if !defined.contains_key(&wire) {
+                return Err(self.error(pos, ParseErrorReason::UndefinedWire(wire)));
+            }
+        }
+
+        let (zpos, zref) = wires[input_count];
+        if defined.contains_key(&zref) {
+            return Err(self.error(zpos, ParseErrorReason::RedefinedWire(zref)));
+        }
+
+        Ok(UncheckedGate {
+            xref: wires[0].1,
+            yref: (input_count == 2).then(|| wires[1].1),
             zref,
             gate_type,
         })
     }
 }
 
+/// A cursor over the tokens of a line.
+struct Tokens<'l, 'a> {
+    line: &'l Line<'a>,
+    pos: usize,
+}
+
+impl Tokens<'_, '_> {
+    /// Parses the next token as an integer.
+    fn next_usize(&mut self) -> Result<usize, ParseError> {
+        let Some((_, token)) = self.line.tokens.get(self.pos) else {
+            return Err(self
+                .line
+                .error(self.pos, ParseErrorReason::UnexpectedEndOfLine));
+        };
+
+        let value = token.parse().map_err(|_| {
+            self.line.error(
+                self.pos,
+                ParseErrorReason::InvalidInteger(token.to_string()),
+            )
+        })?;
+        self.pos += 1;
+
+        Ok(value)
+    }
+
+    /// Checks that there are no remaining tokens.
+    fn finish(self) -> Result<(), ParseError> {
+        match self.line.tokens.get(self.pos) {
+            Some((_, token)) => Err(self.line.error(
+                self.pos,
+                ParseErrorReason::UnexpectedToken(token.to_string()),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+struct UncheckedGate {
+    xref: usize,
+    yref: Option<usize>,
+    zref: usize,
+    gate_type: GateType,
+}
+
 #[cfg(test)]
 mod tests {
     use mpz_circuits_macros::evaluate;
@@ -172,6 +459,182 @@ mod tests {
         assert_eq!(output, 3);
     }
 
+    static SOURCE: &str = "3 5\n2 1 1\n1 1\n\n2 1 0 1 2 AND\n1 1 2 3 INV\n2 1 3 0 4 XOR\n";
+
+    fn parse_err(source: &str) -> (usize, usize, ParseErrorReason) {
+        match Circuit::parse_str(source, &[ValueType::Bit, ValueType::Bit], &[ValueType::Bit]) {
+            Err(ParseError::Malformed { line, col, reason }) => (line, col, reason),
+            res => panic!("expected malformed error, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_parse_str() {
+        let circ = Circuit::parse_str(SOURCE, &[ValueType::Bit, ValueType::Bit], &[ValueType::Bit])
+            .unwrap();
+
+        for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+            let output: bool = evaluate!(circ, fn(a, b) -> bool).unwrap();
+            assert_eq!(output, !(a & b) ^ a);
+        }
+    }
+
+    #[test]
+    fn test_parse_malformed_header() {
+        assert_eq!(parse_err(""), (1, 1, ParseErrorReason::UnexpectedEndOfFile));
+        assert_eq!(
+            parse_err("3 five\n"),
+            (1, 3, ParseErrorReason::InvalidInteger("five".to_string()))
+        );
+        assert_eq!(
+            parse_err("3\n"),
+            (1, 2, ParseErrorReason::UnexpectedEndOfLine)
+        );
+        assert_eq!(
+            parse_err("3 5 7\n"),
+            (1, 5, ParseErrorReason::UnexpectedToken("7".to_string()))
+        );
+        assert_eq!(
+            parse_err("3 5\n1 2\n1 1\n"),
+            (
+                2,
+                1,
+                ParseErrorReason::InputMismatch {
+                    expected: vec![1, 1],
+                    actual: vec![2]
+                }
+            )
+        );
+        assert_eq!(
+            parse_err("3 5\n2 1 1\n2 1 1\n"),
+            (
+                3,
+                1,
+                ParseErrorReason::OutputMismatch {
+                    expected: vec![1],
+                    actual: vec![1, 1]
+                }
+            )
+        );
+        assert_eq!(
+            parse_err("3 1\n2 1 1\n1 1\n"),
+            (
+                1,
+                3,
+                ParseErrorReason::InsufficientWires {
+                    wire_count: 1,
+                    required: 2
+                }
+            )
+        );
+        assert_eq!(
+            parse_err(&SOURCE.replacen("3 5", "99999999999999999999999 5", 1)),
+            (
+                1,
+                1,
+                ParseErrorReason::InvalidInteger("99999999999999999999999".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_malformed_gate() {
+        assert_eq!(
+            parse_err(&SOURCE.replace("2 1 0 1 2 AND", "3 1 0 1 2 AND")),
+            (
+                5,
+                1,
+                ParseErrorReason::InvalidArity {
+                    gate: GateType::And,
+                    inputs: 3,
+                    outputs: 1
+                }
+            )
+        );
+        assert_eq!(
+            parse_err(&SOURCE.replace("1 1 2 3 INV", "1 1 2 INV")),
+            (6, 7, ParseErrorReason::UnexpectedToken("INV".to_string()))
+        );
+        assert_eq!(
+            parse_err(&SOURCE.replace("1 1 2 3 INV", "1 1 2 3 4 INV")),
+            (6, 9, ParseErrorReason::UnexpectedToken("4".to_string()))
+        );
+        assert_eq!(
+            parse_err(&SOURCE.replace("AND", "OR")),
+            (
+                5,
+                11,
+                ParseErrorReason::UnsupportedGateType("OR".to_string())
+            )
+        );
+        assert_eq!(
+            parse_err(&SOURCE.replace("3 5", "2 5")),
+            (
+                7,
+                1,
+                ParseErrorReason::GateCountMismatch {
+                    expected: 2,
+                    actual: 3
+                }
+            )
+        );
+        assert_eq!(
+            parse_err(&SOURCE.replace("3 5", "4 5")),
+            (
+                8,
+                1,
+                ParseErrorReason::GateCountMismatch {
+                    expected: 4,
+                    actual: 3
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_wires() {
+        assert_eq!(
+            parse_err(&SOURCE.replace("2 1 0 1 2 AND", "2 1 0 7 2 AND")),
+            (
+                5,
+                7,
+                ParseErrorReason::WireOutOfRange {
+                    wire: 7,
+                    wire_count: 5
+                }
+            )
+        );
+        // A gate which references a wire that is defined by a later gate.
+        assert_eq!(
+            parse_err(&SOURCE.replace("2 1 0 1 2 AND", "2 1 0 3 2 AND")),
+            (5, 7, ParseErrorReason::UndefinedWire(3))
+        );
+        assert_eq!(
+            parse_err(&SOURCE.replace("1 1 2 3 INV", "1 1 2 0 INV")),
+            (6, 7, ParseErrorReason::RedefinedWire(0))
+        );
+        assert_eq!(
+            parse_err(&SOURCE.replace("2 1 3 0 4 XOR", "2 1 3 0 1 XOR")),
+            (7, 9, ParseErrorReason::RedefinedWire(1))
+        );
+        // The output wire is never defined.
+        assert_eq!(
+            parse_err(&SOURCE.replace("3 5", "3 6")),
+            (3, 1, ParseErrorReason::UndefinedWire(5))
+        );
+    }
+
+    #[test]
+    fn test_parse_truncated_does_not_panic() {
+        for idx in 0..SOURCE.len() {
+            let _ = Circuit::parse_str(
+                &SOURCE[..idx],
+                &[ValueType::Bit, ValueType::Bit],
+                &[ValueType::Bit],
+            );
+        }
+    }
+
     #[test]
     #[cfg(feature = "aes")]
     #[ignore = "expensive"]
@@ -20,6 +20,11 @@ pub enum TypeError {
         expected: ValueType,
         actual: ValueType,
     },
+    #[error("Value of type {actual} does not fit in {target}")]
+    Overflow {
+        target: &'static str,
+        actual: ValueType,
+    },
 }
 
 /// A type that can be represented in binary form.
@@ -566,6 +571,19 @@ impl Value {
         }
     }
 
+    /// Creates a new value of the provided type with all bits set to zero.
+    pub fn zero(ty: &ValueType) -> Self {
+        match ty {
+            ValueType::Bit => Value::Bit(false),
+            ValueType::U8 => Value::U8(0),
+            ValueType::U16 => Value::U16(0),
+            ValueType::U32 => Value::U32(0),
+            ValueType::U64 => Value::U64(0),
+            ValueType::U128 => Value::U128(0),
+            ValueType::Array(ty, len) => Value::Array(vec![Value::zero(ty); *len]),
+        }
+    }
+
     /// Returns the type of the value.
     pub fn value_type(&self) -> ValueType {
         match self {
@@ -578,6 +596,70 @@ impl Value {
             Value::Array(v) => ValueType::Array(Box::new(v[0].value_type()), v.len()),
         }
     }
+
+    /// Returns the value as a `u128`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not an unsigned integer.
+    pub fn as_u128(&self) -> Result<u128, TypeError> {
+        match *self {
+            Value::U8(v) => Ok(v as u128),
+            Value::U16(v) => Ok(v as u128),
+            Value::U32(v) => Ok(v as u128),
+            Value::U64(v) => Ok(v as u128),
+            Value::U128(v) => Ok(v),
+            _ => Err(TypeError::UnexpectedType {
+                expected: ValueType::U128,
+                actual: self.value_type(),
+            }),
+        }
+    }
+
+    /// Returns the value as an `i64`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not an unsigned integer, or if it does
+    /// not fit in an `i64`.
+    pub fn as_i64(&self) -> Result<i64, TypeError> {
+        let value = self.as_u128().map_err(|_| TypeError::UnexpectedType {
+            expected: ValueType::U64,
+            actual: self.value_type(),
+        })?;
+
+        i64::try_from(value).map_err(|_| TypeError::Overflow {
+            target: "i64",
+            actual: self.value_type(),
+        })
+    }
+
+    /// Returns a hash of the contents of the value.
+    ///
+    /// The hash commits to the type of the value and its bits in LSB0 order, so it is
+    /// independent of the platform endianness and of how the value was constructed. Equal
+    /// values always have equal hashes, which makes it suitable for use as a cache key.
+    ///
+    /// The type is encoded as its [`ValueType`] string, the same encoding used by
+    /// [`Circuit::structural_hash`](crate::Circuit::structural_hash).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an empty array, as its type is undefined.
+    pub fn content_hash(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"MPZ_VALUE_CONTENT_HASH");
+
+        let ty = self.value_type().to_string();
+        hasher.update(&(ty.len() as u64).to_le_bytes());
+        hasher.update(ty.as_bytes());
+
+        let bits: Vec<bool> = self.clone().into_iter_lsb0().collect();
+        hasher.update(&(bits.len() as u64).to_le_bytes());
+        hasher.update(&Vec::<u8>::from_lsb0_iter(bits));
+
+        hasher.finalize().into()
+    }
 }
 
 impl IntoBits for Value {
@@ -771,6 +853,8 @@ mod tests {
 
     use crate::CircuitBuilder;
 
+    use super::*;
+
     #[trace]
     fn to_be_bytes(a: u128) -> [u8; 16] {
         a.to_be_bytes()
@@ -781,6 +865,16 @@ mod tests {
         a.to_le_bytes()
     }
 
+    #[test]
+    fn test_value_zero() {
+        assert_eq!(Value::zero(&ValueType::U64), 0u64.into());
+        assert_eq!(Value::zero(&ValueType::new::<[u8; 16]>()), [0u8; 16].into());
+        assert_eq!(
+            Value::zero(&ValueType::new_array::<u32>(4)),
+            [0u32; 4].into()
+        );
+    }
+
     #[test]
     fn test_convert_bytes() {
         let builder = CircuitBuilder::new();
@@ -799,4 +893,101 @@ mod tests {
 
         test_circ!(circ, to_le_bytes, fn(69u128) -> [u8; 16]);
     }
+
+    #[test]
+    fn test_value_eq_construction_path() {
+        let bytes = 69u128.to_le_bytes();
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u128>();
+        let a_bytes = to_le_bytes_trace(builder.state(), a);
+        builder.add_output(a_bytes);
+        let circ = builder.build().unwrap();
+
+        // Decoded from the output bits of a circuit.
+        let decoded = circ.evaluate(&[Value::U128(69)]).unwrap().pop().unwrap();
+
+        assert_eq!(decoded, Value::from(bytes));
+        assert_eq!(Value::from(bytes.to_vec()), Value::from(&bytes[..]));
+        assert_eq!(Value::from(&bytes), decoded);
+
+        let mut other = bytes;
+        other[15] ^= 1;
+        assert_ne!(Value::from(other), decoded);
+
+        // Same bits, different type.
+        assert_ne!(Value::U128(69), decoded);
+    }
+
+    #[test]
+    fn test_value_content_hash() {
+        let bytes = 69u128.to_le_bytes();
+
+        assert_eq!(
+            Value::from(bytes).content_hash(),
+            Value::from(&bytes[..]).content_hash()
+        );
+        assert_eq!(
+            Value::U32(1).content_hash(),
+            Value::U32(u32::from_le_bytes([1, 0, 0, 0])).content_hash()
+        );
+
+        // Different values.
+        assert_ne!(Value::U32(1).content_hash(), Value::U32(2).content_hash());
+        let mut other = bytes;
+        other[15] ^= 1;
+        assert_ne!(
+            Value::from(bytes).content_hash(),
+            Value::from(other).content_hash()
+        );
+
+        // Same bits, different types.
+        assert_ne!(Value::U8(1).content_hash(), Value::U16(1).content_hash());
+        assert_ne!(
+            Value::U128(69).content_hash(),
+            Value::from(bytes).content_hash()
+        );
+        assert_ne!(
+            Value::from([1u16, 0]).content_hash(),
+            Value::U32(1).content_hash()
+        );
+        assert_ne!(
+            Value::from([0u8; 2]).content_hash(),
+            Value::Array(vec![
+                Value::Array(vec![Value::U8(0)]),
+                Value::Array(vec![Value::U8(0)])
+            ])
+            .content_hash()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_value_content_hash_empty_array() {
+        Value::Array(vec![]).content_hash();
+    }
+
+    #[test]
+    fn test_value_as_u128() {
+        assert_eq!(Value::U64(u64::MAX).as_u128().unwrap(), u64::MAX as u128);
+        assert_eq!(Value::U128(u128::MAX).as_u128().unwrap(), u128::MAX);
+
+        let err = Value::Array(vec![Value::Bit(true), Value::Bit(false)])
+            .as_u128()
+            .unwrap_err();
+
+        assert!(matches!(err, TypeError::UnexpectedType { .. }));
+    }
+
+    #[test]
+    fn test_value_as_i64() {
+        assert_eq!(Value::U32(u32::MAX).as_i64().unwrap(), u32::MAX as i64);
+        assert_eq!(Value::U64(i64::MAX as u64).as_i64().unwrap(), i64::MAX);
+
+        let err = Value::U64(u64::MAX).as_i64().unwrap_err();
+        assert!(matches!(err, TypeError::Overflow { target: "i64", .. }));
+
+        let err = Value::U128(1 << 64).as_i64().unwrap_err();
+        assert!(matches!(err, TypeError::Overflow { target: "i64", .. }));
+    }
 }
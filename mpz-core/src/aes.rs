@@ -39,6 +39,23 @@ impl FixedKeyAes {
         h1 ^ h2
     }
 
+    /// Tweakable circular correlation-robust hash function instantiated
+    /// using fixed-key AES, with an integer tweak.
+    ///
+    /// This is the hash used to garble half-gates, where the tweak is the gate id.
+    /// The tweak `i` is encoded as a 128-bit big-endian integer.
+    ///
+    /// `π(π(x) ⊕ i) ⊕ π(x)`, where `π` is instantiated using fixed-key AES.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - The block to hash.
+    /// * `tweak` - The tweak.
+    #[inline]
+    pub fn tccr_u64(&self, block: Block, tweak: u64) -> Block {
+        self.tccr(Block::new((tweak as u128).to_be_bytes()), block)
+    }
+
     /// Tweakable circular correlation-robust hash function instantiated
     /// using fixed-key AES.
     ///
@@ -211,3 +228,24 @@ fn aes_test() {
         ]
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tccr_u64() {
+        let block = Block::new(std::array::from_fn(|i| i as u8));
+
+        let h = FIXED_KEY_AES.tccr_u64(block, 42);
+
+        assert_eq!(
+            h,
+            FIXED_KEY_AES.tccr(Block::new(42u128.to_be_bytes()), block)
+        );
+        assert_eq!(
+            h,
+            Block::new([83, 182, 145, 189, 168, 111, 247, 93, 98, 203, 57, 222, 141, 169, 16, 229])
+        );
+    }
+}
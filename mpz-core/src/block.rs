@@ -37,6 +37,34 @@ impl Block {
         self.0
     }
 
+    /// Creates a block from a `u128`, where the least significant byte of the value is the
+    /// first byte of the block.
+    #[inline]
+    pub fn from_u128_le(value: u128) -> Self {
+        Self(value.to_le_bytes())
+    }
+
+    /// Creates a block from a `u128`, where the most significant byte of the value is the
+    /// first byte of the block.
+    #[inline]
+    pub fn from_u128_be(value: u128) -> Self {
+        Self(value.to_be_bytes())
+    }
+
+    /// Returns the block as a `u128`, where the first byte of the block is the least
+    /// significant byte of the value.
+    #[inline]
+    pub fn to_u128_le(self) -> u128 {
+        u128::from_le_bytes(self.0)
+    }
+
+    /// Returns the block as a `u128`, where the first byte of the block is the most
+    /// significant byte of the value.
+    #[inline]
+    pub fn to_u128_be(self) -> u128 {
+        u128::from_be_bytes(self.0)
+    }
+
     /// Generate a random block using the provided RNG
     #[inline]
     pub fn random<R: Rng + CryptoRng + ?Sized>(rng: &mut R) -> Self {
@@ -55,6 +83,17 @@ impl Block {
         (0..n).map(|_| rng.gen::<[u8; 16]>().into()).collect()
     }
 
+    /// Fills `dst` with random blocks using the provided RNG.
+    ///
+    /// The blocks are filled directly from the RNG's byte stream, so this is the in-place
+    /// counterpart to [`Block::random_vec`] for buffers which are already allocated.
+    /// `rand::Fill` can not be implemented for `[Block]` as neither the trait nor the slice
+    /// type are local to this crate.
+    #[inline]
+    pub fn random_fill<R: Rng + CryptoRng + ?Sized>(rng: &mut R, dst: &mut [Self]) {
+        rng.fill_bytes(bytemuck::cast_slice_mut(dst));
+    }
+
     /// Carry-less multiplication of two blocks, without the reduction step.
     #[inline]
     pub fn clmul(self, other: Self) -> (Self, Self) {
@@ -96,6 +135,26 @@ impl Block {
         Block::reduce_gcm(x, y)
     }
 
+    /// XORs `src` into `dst` element-wise, ie. `dst[i] ^= src[i]`.
+    ///
+    /// The XOR is performed over the underlying bytes of the slices, which the compiler
+    /// lowers to wide SIMD instructions on targets which support them, falling back to
+    /// scalar instructions otherwise. This is intended for batched label updates, such as
+    /// free-XOR in the garbled circuit evaluator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices do not have the same length.
+    #[inline]
+    pub fn xor_slices(dst: &mut [Block], src: &[Block]) {
+        assert_eq!(dst.len(), src.len());
+
+        let dst: &mut [u8] = bytemuck::cast_slice_mut(dst);
+        let src: &[u8] = bytemuck::cast_slice(src);
+
+        dst.iter_mut().zip(src).for_each(|(a, b)| *a ^= b);
+    }
+
     /// Reverses the bits of the block
     #[inline]
     pub fn reverse_bits(self) -> Self {
@@ -114,6 +173,32 @@ impl Block {
         ((self.0[0] & 1) == 1) as usize
     }
 
+    /// Returns the bit of the block at the given index, in LSB0 order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than 128.
+    #[inline]
+    pub fn get_bit(&self, i: usize) -> bool {
+        assert!(i < 128, "bit index out of range: {i}");
+        (self.0[i / 8] >> (i % 8)) & 1 == 1
+    }
+
+    /// Sets the bit of the block at the given index, in LSB0 order.
+    ///
+    /// The bit is set by masking, without branching on `v`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than 128.
+    #[inline]
+    pub fn set_bit(&mut self, i: usize, v: bool) {
+        assert!(i < 128, "bit index out of range: {i}");
+        let shift = i % 8;
+        let byte = &mut self.0[i / 8];
+        *byte = (*byte & !(1 << shift)) | ((v as u8) << shift);
+    }
+
     /// Let `x0` and `x1` be the lower and higher halves of `x`, respectively.
     /// This function compute ``sigma( x = x0 || x1 ) = x1 || (x0 xor x1)``.
     #[inline(always)]
@@ -321,6 +406,81 @@ mod tests {
         assert_eq!(Block::new(three), b);
     }
 
+    #[test]
+    fn test_sample_standard() {
+        use crate::prg::Prg;
+        use rand::SeedableRng;
+
+        let mut rng = Prg::from_seed(Block::ONES);
+        let sampled: Block = rng.gen();
+
+        let mut rng = Prg::from_seed(Block::ONES);
+        let expected = Block::random(&mut rng);
+
+        assert_eq!(sampled, expected);
+    }
+
+    #[test]
+    fn test_u128_conversions() {
+        assert_eq!(Block::from_u128_be(1).to_u128_be(), 1);
+        assert_eq!(Block::from_u128_le(1).to_u128_le(), 1);
+        assert_eq!(Block::from_u128_le(1), Block::ONE);
+
+        let value = 0x0102030405060708090a0b0c0d0e0f10u128;
+        let le = Block::from_u128_le(value);
+        let be = Block::from_u128_be(value);
+
+        assert_ne!(le, be);
+        assert_eq!(le.to_bytes()[0], 0x10);
+        assert_eq!(be.to_bytes()[0], 0x01);
+        assert_eq!(le.to_u128_be(), value.swap_bytes());
+        assert_eq!(be.to_u128_le(), value.swap_bytes());
+    }
+
+    #[test]
+    fn test_random_fill() {
+        use crate::prg::Prg;
+        use rand::SeedableRng;
+
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let mut blocks = [Block::ZERO; 4];
+        Block::random_fill(&mut rng, &mut blocks);
+
+        for (i, a) in blocks.iter().enumerate() {
+            assert_ne!(*a, Block::ZERO);
+            for b in &blocks[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_xor_slices() {
+        use crate::prg::Prg;
+        use rand::SeedableRng;
+
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        for len in [0, 1, 3, 64, 129] {
+            let a = Block::random_vec(&mut rng, len);
+            let b = Block::random_vec(&mut rng, len);
+
+            let expected: Vec<_> = a.iter().zip(&b).map(|(a, b)| *a ^ *b).collect();
+
+            let mut dst = a.clone();
+            Block::xor_slices(&mut dst, &b);
+
+            assert_eq!(dst, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_xor_slices_length_mismatch() {
+        let mut dst = vec![Block::ZERO; 2];
+        Block::xor_slices(&mut dst, &[Block::ONES]);
+    }
+
     #[test]
     fn test_lsb() {
         let a = Block::new([0; 16]);
@@ -345,6 +505,45 @@ mod tests {
         assert_eq!(a.lsb(), 1);
     }
 
+    #[test]
+    fn test_get_set_bit() {
+        use crate::prg::Prg;
+        use rand::SeedableRng;
+
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let a = Block::random(&mut rng);
+
+        let bits = a.to_lsb0_vec();
+        for (i, bit) in bits.iter().enumerate() {
+            assert_eq!(a.get_bit(i), *bit);
+        }
+
+        for i in [0, 63, 127] {
+            for v in [false, true] {
+                let mut b = a;
+                b.set_bit(i, v);
+
+                let mut expected = bits.clone();
+                expected[i] = v;
+
+                assert_eq!(b.get_bit(i), v);
+                assert_eq!(b.to_lsb0_vec(), expected);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_get_bit_out_of_range() {
+        Block::ZERO.get_bit(128);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_bit_out_of_range() {
+        Block::ZERO.set_bit(128, true);
+    }
+
     #[test]
     fn test_reverse_bits() {
         let a = Block::new([42; 16]);
@@ -6,7 +6,13 @@ use crate::{
     serialize::CanonicalSerialize,
 };
 use rand::{thread_rng, Rng};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The minimum length of a [`TruncatedCommitment`] in bytes.
+///
+/// Truncating the commitment to `N` bytes reduces its binding security to `4 * N` bits
+/// against a generic collision attack, so commitments shorter than 16 bytes are rejected.
+pub const MIN_COMMITMENT_LEN: usize = 16;
 
 /// Error associated with commitments
 #[derive(Debug, thiserror::Error)]
@@ -27,6 +33,57 @@ impl Nonce {
     }
 }
 
+/// A hash commitment truncated to the first `N` bytes of the digest.
+///
+/// `N` must be at least [`MIN_COMMITMENT_LEN`] and at most 32, which is checked at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TruncatedCommitment<const N: usize = 32>([u8; N]);
+
+impl<const N: usize> TruncatedCommitment<N> {
+    const VALID_LEN: () = {
+        assert!(
+            N >= MIN_COMMITMENT_LEN,
+            "commitment is shorter than 16 bytes"
+        );
+        assert!(N <= 32, "commitment is longer than the hash");
+    };
+
+    /// Creates a commitment by truncating the provided hash.
+    pub fn from_hash(hash: &Hash) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_LEN;
+
+        Self(
+            hash.as_bytes()[..N]
+                .try_into()
+                .expect("commitment length should not exceed hash length"),
+        )
+    }
+
+    /// Returns the commitment as a byte array
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> Serialize for TruncatedCommitment<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.as_slice().serialize(serializer)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for TruncatedCommitment<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        let len = bytes.len();
+
+        bytes
+            .try_into()
+            .map(Self)
+            .map_err(|_| serde::de::Error::invalid_length(len, &"a commitment of N bytes"))
+    }
+}
+
 /// Decommitment data for a commitment
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Decommitment<T>
@@ -68,6 +125,23 @@ where
         Ok(())
     }
 
+    /// Creates a hash commitment truncated to `N` bytes
+    pub fn commit_truncated<const N: usize>(&self) -> TruncatedCommitment<N> {
+        TruncatedCommitment::from_hash(&self.hash())
+    }
+
+    /// Verifies that the provided truncated commitment corresponds to this decommitment
+    pub fn verify_truncated<const N: usize>(
+        &self,
+        commitment: &TruncatedCommitment<N>,
+    ) -> Result<(), CommitmentError> {
+        if commitment != &self.commit_truncated() {
+            return Err(CommitmentError::InvalidDecommitment);
+        }
+
+        Ok(())
+    }
+
     /// Returns the data
     pub fn data(&self) -> &T {
         &self.data
@@ -96,6 +170,16 @@ where
 
         (decommitment, commitment)
     }
+
+    /// Creates a hash commitment to self, truncated to `N` bytes
+    ///
+    /// See [`TruncatedCommitment`] for the supported lengths.
+    fn hash_commit_truncated<const N: usize>(self) -> (Decommitment<Self>, TruncatedCommitment<N>) {
+        let decommitment = Decommitment::new(self);
+        let commitment = decommitment.commit_truncated();
+
+        (decommitment, commitment)
+    }
 }
 
 impl<T> HashCommit for T where T: serde::Serialize {}
@@ -135,4 +219,27 @@ mod test {
 
         assert!(matches!(err, CommitmentError::InvalidDecommitment));
     }
+
+    fn check_truncated_commitment<const N: usize>() {
+        let message = [0, 1, 2, 3u8];
+        let (mut decommitment, commitment) = message.hash_commit_truncated::<N>();
+
+        assert_eq!(
+            commitment.as_bytes()[..],
+            decommitment.commit().as_bytes()[..N]
+        );
+        decommitment.verify_truncated(&commitment).unwrap();
+
+        decommitment.data[0] = decommitment.data[0].wrapping_add(1);
+
+        let err = decommitment.verify_truncated(&commitment).unwrap_err();
+
+        assert!(matches!(err, CommitmentError::InvalidDecommitment));
+    }
+
+    #[test]
+    fn test_truncated_commitment() {
+        check_truncated_commitment::<16>();
+        check_truncated_commitment::<32>();
+    }
 }
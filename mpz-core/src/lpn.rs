@@ -112,8 +112,20 @@ impl<const D: usize> LpnEncoder<D> {
     }
 }
 
+/// Errors that can occur when validating LPN parameters.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum LpnParamError {
+    #[error("lpn parameters must be non-zero: n = {n}, k = {k}, t = {t}")]
+    Zero { n: usize, k: usize, t: usize },
+    #[error("secret length must be less than the output length: k = {k}, n = {n}")]
+    SecretTooLong { k: usize, n: usize },
+    #[error("noise weight must not exceed the output length: t = {t}, n = {n}")]
+    NoiseTooHeavy { t: usize, n: usize },
+}
+
 /// Lpn paramters
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct LpnParameters {
     /// The length of output vecotrs.
     pub n: usize,
@@ -130,6 +142,71 @@ impl LpnParameters {
         LpnParameters { n, k, t }
     }
 
+    /// LPN parameters with regular noise.
+    /// Derived from https://github.com/emp-toolkit/emp-ot/blob/master/emp-ot/ferret/constants.h
+    pub const REGULAR: LpnParameters = LpnParameters {
+        n: 10180608,
+        k: 124000,
+        t: 4971,
+    };
+
+    /// LPN parameters with uniform noise.
+    /// Derived from Table 2 of the [`Ferret`](https://eprint.iacr.org/2020/924.pdf) paper.
+    pub const UNIFORM: LpnParameters = LpnParameters {
+        n: 10616092,
+        k: 588160,
+        t: 1324,
+    };
+
+    /// Returns the smallest vetted preset which yields at least `target_ots` outputs per
+    /// extension, ie `n - k >= target_ots`.
+    ///
+    /// Only the [`UNIFORM`](Self::UNIFORM) and [`REGULAR`](Self::REGULAR) presets are
+    /// considered, as other parameter sets are not backed by a security analysis. Note that
+    /// the presets assume different noise distributions, so the caller must sample the error
+    /// vector accordingly.
+    ///
+    /// Returns `None` if no preset yields `target_ots` outputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_ots` - The desired number of outputs per extension.
+    pub fn recommended(target_ots: usize) -> Option<Self> {
+        let mut presets = [Self::UNIFORM, Self::REGULAR];
+        presets.sort_by_key(Self::outputs);
+
+        presets
+            .into_iter()
+            .find(|params| params.outputs() >= target_ots)
+    }
+
+    /// Returns the number of outputs per extension, ie `n - k`.
+    fn outputs(&self) -> usize {
+        self.n - self.k
+    }
+
+    /// Checks that the parameters are consistent.
+    ///
+    /// All parameters must be non-zero, the secret length `k` must be less than
+    /// the output length `n` and the noise weight `t` must not exceed `n`.
+    pub fn validate(&self) -> Result<(), LpnParamError> {
+        let LpnParameters { n, k, t } = *self;
+
+        if n == 0 || k == 0 || t == 0 {
+            return Err(LpnParamError::Zero { n, k, t });
+        }
+
+        if k >= n {
+            return Err(LpnParamError::SecretTooLong { k, n });
+        }
+
+        if t > n {
+            return Err(LpnParamError::NoiseTooHeavy { t, n });
+        }
+
+        Ok(())
+    }
+
     /// Sample a uniform error vector with HW t.
     pub fn sample_uniform_error_vector(&self) -> Vec<Block> {
         let one: Block = bytemuck::cast(1_u128);
@@ -156,7 +233,7 @@ impl LpnParameters {
 
 #[cfg(test)]
 mod tests {
-    use crate::lpn::LpnEncoder;
+    use crate::lpn::{LpnEncoder, LpnParamError, LpnParameters};
     use crate::prp::Prp;
     use crate::Block;
 
@@ -221,4 +298,57 @@ mod tests {
 
         assert_eq!(y, z);
     }
+
+    #[test]
+    fn lpn_parameters_validate_test() {
+        let params = LpnParameters {
+            n: 100,
+            k: 200,
+            t: 10,
+        };
+        assert_eq!(
+            params.validate(),
+            Err(LpnParamError::SecretTooLong { k: 200, n: 100 })
+        );
+
+        let params = LpnParameters {
+            n: 100,
+            k: 20,
+            t: 200,
+        };
+        assert_eq!(
+            params.validate(),
+            Err(LpnParamError::NoiseTooHeavy { t: 200, n: 100 })
+        );
+
+        let params = LpnParameters {
+            n: 9600,
+            k: 1220,
+            t: 600,
+        };
+        assert!(params.validate().is_ok());
+    }
+
+    #[test]
+    fn lpn_parameters_recommended_test() {
+        let presets = [LpnParameters::UNIFORM, LpnParameters::REGULAR];
+
+        for target in [0, 1, 1000, 10_000_000, 10_030_000, 10_056_608] {
+            let params = LpnParameters::recommended(target).unwrap();
+
+            assert!(presets.contains(&params));
+            assert!(params.validate().is_ok());
+            assert!(params.n - params.k >= target);
+        }
+
+        assert_eq!(
+            LpnParameters::recommended(1000),
+            Some(LpnParameters::UNIFORM)
+        );
+        assert_eq!(
+            LpnParameters::recommended(10_030_000),
+            Some(LpnParameters::REGULAR)
+        );
+        assert_eq!(LpnParameters::recommended(50_000_000), None);
+    }
 }
@@ -134,6 +134,32 @@ impl Prg {
     }
 }
 
+/// Shuffles a slice in place using the Fisher–Yates algorithm.
+///
+/// The permutation is fully determined by the state of the PRG, so two parties holding
+/// PRGs with the same seed derive the same permutation.
+pub fn prg_shuffle<T>(items: &mut [T], prg: &mut Prg) {
+    for i in (1..items.len()).rev() {
+        let j = sample_below(prg, i as u64 + 1) as usize;
+        items.swap(i, j);
+    }
+}
+
+/// Samples an integer uniformly from `0..bound` by rejection sampling over `u64`s.
+///
+/// This is used instead of `Rng::gen_range`, so that the values drawn from the PRG do not depend
+/// on the pointer width of the platform, nor on the sampling algorithm of the `rand` version.
+fn sample_below(prg: &mut Prg, bound: u64) -> u64 {
+    // 2^64 mod bound, the number of values at the top of the range which would bias the result.
+    let rem = (u64::MAX % bound + 1) % bound;
+    loop {
+        let value = prg.next_u64();
+        if value <= u64::MAX - rem {
+            return value % bound;
+        }
+    }
+}
+
 impl Default for Prg {
     #[inline(always)]
     fn default() -> Self {
@@ -148,3 +174,18 @@ fn prg_test() {
     prg.random_blocks(&mut x);
     assert_ne!(x[0], x[1]);
 }
+
+#[test]
+fn prg_shuffle_test() {
+    let seed = Block::ZERO;
+    let items: Vec<u32> = (0..10).collect();
+
+    let mut a = items.clone();
+    let mut b = items.clone();
+    prg_shuffle(&mut a, &mut Prg::from_seed(seed));
+    prg_shuffle(&mut b, &mut Prg::from_seed(seed));
+
+    assert_eq!(a, b);
+
+    assert_eq!(a, [1, 8, 5, 3, 0, 9, 4, 7, 6, 2]);
+}
@@ -0,0 +1,33 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use mpz_core::{prg::Prg, Block};
+use mpz_fields::gf2_128::Gf2_128;
+use rand::{Rng, SeedableRng};
+
+fn bench_gf2_128_scale(c: &mut Criterion) {
+    let mut rng = Prg::from_seed(Block::ZERO);
+    let k: Gf2_128 = rng.gen();
+    let elems: Vec<Gf2_128> = (0..1024).map(|_| rng.gen()).collect();
+
+    let mut group = c.benchmark_group("scale");
+
+    group.bench_function("naive", |bench| {
+        bench.iter(|| {
+            let mut elems = elems.clone();
+            elems.iter_mut().for_each(|elem| *elem = *elem * k);
+            black_box(elems);
+        });
+    });
+
+    group.bench_function("scale_slice", |bench| {
+        bench.iter(|| {
+            let mut elems = elems.clone();
+            Gf2_128::scale_slice(&mut elems, k);
+            black_box(elems);
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_gf2_128_scale);
+criterion_main!(benches);
@@ -1,14 +1,19 @@
 //! This module implements the extension field GF(2^128).
 
-use std::ops::{Add, Mul, Neg};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Add, Mul, Neg},
+};
 
 use itybity::{BitLength, FromBitIterator, GetBit, Lsb0, Msb0};
 use rand::{distributions::Standard, prelude::Distribution};
 use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
 
 use mpz_core::Block;
 
-use super::Field;
+use super::{Field, FieldId};
 
 /// A type for holding field elements of Gf(2^128).
 #[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize)]
@@ -16,6 +21,13 @@ pub struct Gf2_128(pub(crate) u128);
 
 opaque_debug::implement!(Gf2_128);
 
+impl Hash for Gf2_128 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash the canonical byte form, so that equal elements have equal hashes.
+        self.0.to_le_bytes().hash(state);
+    }
+}
+
 impl Gf2_128 {
     /// Creates a new field element from a u128,
     /// mapping the integer to the corresponding polynomial.
@@ -29,6 +41,100 @@ impl Gf2_128 {
     pub fn to_inner(self) -> u128 {
         self.0
     }
+
+    /// Returns the absolute trace of the field element, ie `a + a^2 + a^4 + ... + a^(2^127)`.
+    ///
+    /// The trace is a GF(2)-linear map, ie `trace(a + b) = trace(a) ^ trace(b)`, so it
+    /// is computed as the parity of the bits of `a` selected by the trace of each basis element.
+    pub fn trace(&self) -> bool {
+        // The bits `i` for which `trace(x^i) = 1` with respect to the reduction
+        // polynomial `x^128 + x^7 + x^2 + x + 1`, namely 121 and 127.
+        const TRACE_MASK: u128 = 0x82000000000000000000000000000000;
+
+        (self.0 & TRACE_MASK).count_ones() & 1 == 1
+    }
+
+    /// Multiplies two field elements encoded as in GHASH (NIST SP 800-38D).
+    ///
+    /// GHASH encodes the coefficient of `x^0` in the most significant bit of the first byte,
+    /// which is the bit-reflection of the representation used by [`Gf2_128`]. The product is
+    /// reduced by the GCM polynomial `x^128 + x^7 + x^2 + x + 1` and returned in the same
+    /// encoding.
+    pub fn mul_ghash(a: [u8; 16], b: [u8; 16]) -> [u8; 16] {
+        let a = Gf2_128(u128::from_be_bytes(a).reverse_bits());
+        let b = Gf2_128(u128::from_be_bytes(b).reverse_bits());
+
+        gfmul(a, b).0.reverse_bits().to_be_bytes()
+    }
+
+    /// Multiplies two field elements encoded as in POLYVAL (RFC 8452).
+    ///
+    /// POLYVAL encodes the elements in little-endian order, with the coefficient of `x^0`
+    /// in the least significant bit of the first byte, and uses the polynomial
+    /// `x^128 + x^127 + x^126 + x^121 + 1`. As specified by `dot(a, b)` in RFC 8452, the
+    /// product is multiplied by `x^-128` during the reduction.
+    pub fn mul_polyval(a: [u8; 16], b: [u8; 16]) -> [u8; 16] {
+        // The polynomial without the `x^128` term, which is also its own inverse modulo `x^128`.
+        const P: u128 = 0xc2000000000000000000000000000001;
+
+        let (lo, hi) = clmul(u128::from_le_bytes(a), u128::from_le_bytes(b));
+
+        // Montgomery reduction, `lo + q * P` is divisible by `x^128`.
+        let (q, _) = clmul(lo, P);
+        let (_, r) = clmul(q, P);
+
+        (hi ^ r ^ q).to_le_bytes()
+    }
+
+    /// Multiplies every element of the slice by the constant `c` in place.
+    ///
+    /// The products are computed with [`Block::gfmul`], which is faster than the bit-serial
+    /// multiplication of the `Mul` implementation. The result is the same as multiplying
+    /// each element by `c`.
+    pub fn scale_slice(elems: &mut [Gf2_128], c: Gf2_128) {
+        for elem in elems.iter_mut() {
+            *elem = gfmul(*elem, c);
+        }
+    }
+}
+
+impl fmt::Display for Gf2_128 {
+    /// Formats the field element as a zero-padded hex string of its polynomial coefficients.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:032x}", self.0)
+    }
+}
+
+impl fmt::LowerHex for Gf2_128 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl fmt::UpperHex for Gf2_128 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.0, f)
+    }
+}
+
+/// Carry-less multiplication of two 128-bit polynomials, returning the low and high halves.
+fn clmul(a: u128, b: u128) -> (u128, u128) {
+    let (lo, hi) = Block::new(a.to_le_bytes()).clmul(Block::new(b.to_le_bytes()));
+
+    (
+        u128::from_le_bytes(lo.to_bytes()),
+        u128::from_le_bytes(hi.to_bytes()),
+    )
+}
+
+/// Multiplies two field elements with [`Block::gfmul`].
+///
+/// The blocks hold the coefficients in little-endian order, which is the representation
+/// expected by [`Block::gfmul`].
+fn gfmul(a: Gf2_128, b: Gf2_128) -> Gf2_128 {
+    let c = Block::new(a.0.to_le_bytes()).gfmul(Block::new(b.0.to_le_bytes()));
+
+    Gf2_128(u128::from_le_bytes(c.to_bytes()))
 }
 
 impl From<Gf2_128> for Block {
@@ -99,6 +205,15 @@ impl Neg for Gf2_128 {
 
 impl Field for Gf2_128 {
     const BIT_SIZE: u32 = 128;
+    const FIELD_ID: FieldId = FieldId::Gf2_128;
+    /// The polynomial `x^128 + x^7 + x^2 + x + 1`.
+    ///
+    /// The coefficients are encoded like field elements, ie little-endian and not
+    /// bit-reflected.
+    const MODULUS_LE_BYTES: &'static [u8] = &[
+        0x87, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x01,
+    ];
 
     fn zero() -> Self {
         Self::new(0)
@@ -108,6 +223,13 @@ impl Field for Gf2_128 {
         Self::new(1)
     }
 
+    /// Checks whether the element is zero by OR-reducing its 64-bit limbs in constant time.
+    fn ct_is_zero(&self) -> Choice {
+        let limbs = (self.0 as u64) | ((self.0 >> 64) as u64);
+
+        limbs.ct_eq(&0)
+    }
+
     fn two_pow(rhs: u32) -> Self {
         Self(1 << rhs)
     }
@@ -162,7 +284,10 @@ impl FromBitIterator for Gf2_128 {
 mod tests {
     use super::Gf2_128;
     use crate::{
-        tests::{test_field_basic, test_field_bit_ops, test_field_compute_product_repeated},
+        tests::{
+            test_field_basic, test_field_bit_ops, test_field_check_vole,
+            test_field_compute_product_repeated, test_field_hash, test_field_is_zero,
+        },
         Field,
     };
     use ghash_rc::{
@@ -170,7 +295,7 @@ mod tests {
         GHash,
     };
     use mpz_core::{prg::Prg, Block};
-    use rand::SeedableRng;
+    use rand::{Rng, SeedableRng};
 
     #[test]
     fn test_gf2_128_basic() {
@@ -189,6 +314,32 @@ mod tests {
         test_field_bit_ops::<Gf2_128>();
     }
 
+    #[test]
+    fn test_gf2_128_hash() {
+        test_field_hash::<Gf2_128>();
+    }
+
+    #[test]
+    fn test_gf2_128_modulus() {
+        let modulus = Gf2_128::modulus();
+
+        // x^128 + x^7 + x^2 + x + 1
+        assert_eq!(modulus.len(), 17);
+        assert_eq!(modulus[0], 0b1000_0111);
+        assert!(modulus[1..16].iter().all(|&byte| byte == 0));
+        assert_eq!(modulus[16], 1);
+    }
+
+    #[test]
+    fn test_gf2_128_is_zero() {
+        test_field_is_zero::<Gf2_128>();
+    }
+
+    #[test]
+    fn test_gf2_128_check_vole() {
+        test_field_check_vole::<Gf2_128>();
+    }
+
     #[test]
     fn test_gf2_128_mul() {
         // Naive multiplication is the same here.
@@ -209,6 +360,112 @@ mod tests {
         assert_eq!(e * f, Gf2_128::new(0x40229a09a5ed12e7e4e10da323506d2));
     }
 
+    #[test]
+    fn test_gf2_128_mul_ghash() {
+        // Test case 2 from The Galois/Counter Mode of Operation (GCM), McGrew and Viega.
+        let h = 0x66e94bd4ef8a2c3b884cfa59ca342b2e_u128.to_be_bytes();
+        let c = 0x0388dace60b6a392f328c2b971b2fe78_u128.to_be_bytes();
+        let len = 0x00000000000000000000000000000080_u128.to_be_bytes();
+
+        let x_1 = Gf2_128::mul_ghash(c, h);
+        assert_eq!(x_1, 0x5e2ec746917062882c85b0685353deb7_u128.to_be_bytes());
+
+        let x_2 = (u128::from_be_bytes(x_1) ^ u128::from_be_bytes(len)).to_be_bytes();
+        assert_eq!(
+            Gf2_128::mul_ghash(x_2, h),
+            0xf38cbb1ad69223dcc3457ae5b6b0f885_u128.to_be_bytes()
+        );
+
+        // The GHASH encoding is the bit-reflection of the field representation.
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let a: Gf2_128 = rng.gen();
+        let b: Gf2_128 = rng.gen();
+        let expected = (a * b).0.reverse_bits().to_be_bytes();
+        assert_eq!(
+            Gf2_128::mul_ghash(
+                a.0.reverse_bits().to_be_bytes(),
+                b.0.reverse_bits().to_be_bytes()
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_gf2_128_mul_polyval() {
+        // Test vector from RFC 8452, Appendix A.
+        let h = 0x25629347589242761d31f826ba4b757b_u128.to_be_bytes();
+        let x_1 = 0x4f4f95668c83dfb6401762bb2d01a262_u128.to_be_bytes();
+        let x_2 = 0xd1a24ddd2721d006bbe45f20d3c9f362_u128.to_be_bytes();
+
+        let s_1 = Gf2_128::mul_polyval(x_1, h);
+        assert_eq!(s_1, 0xcedac64537ff50989c16011551086d77_u128.to_be_bytes());
+
+        let s_2 = Gf2_128::mul_polyval(
+            (u128::from_be_bytes(s_1) ^ u128::from_be_bytes(x_2)).to_be_bytes(),
+            h,
+        );
+        assert_eq!(s_2, 0xf7a3b47b846119fae5b7866cf5e5b77e_u128.to_be_bytes());
+    }
+
+    #[test]
+    fn test_gf2_128_scale_slice() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let c: Gf2_128 = rng.gen();
+        let elems: Vec<Gf2_128> = (0..67).map(|_| rng.gen()).collect();
+
+        let mut scaled = elems.clone();
+        Gf2_128::scale_slice(&mut scaled, c);
+
+        let expected: Vec<_> = elems.iter().map(|elem| *elem * c).collect();
+
+        assert_eq!(scaled, expected);
+    }
+
+    #[test]
+    fn test_gf2_128_trace() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        // The trace of 1 is 128 mod 2.
+        assert!(!Gf2_128::one().trace());
+        assert!(!Gf2_128::zero().trace());
+
+        for _ in 0..32 {
+            let a: Gf2_128 = rng.gen();
+            let b: Gf2_128 = rng.gen();
+
+            assert_eq!((a + b).trace(), a.trace() ^ b.trace());
+
+            // Sum of the Frobenius conjugates.
+            let mut conjugate = a;
+            let mut expected = Gf2_128::zero();
+            for _ in 0..128 {
+                expected = expected + conjugate;
+                conjugate = conjugate * conjugate;
+            }
+
+            assert!(expected == Gf2_128::zero() || expected == Gf2_128::one());
+            assert_eq!(a.trace(), expected == Gf2_128::one());
+        }
+    }
+
+    #[test]
+    fn test_gf2_128_format() {
+        let one = Gf2_128::one();
+        assert_eq!(format!("{}", one), "0x00000000000000000000000000000001");
+        assert_eq!(format!("{:x}", one), "1");
+        assert_eq!(format!("{:X}", one), "1");
+
+        let a = Gf2_128::new(0x7b5b54657374566563746f725d53475d);
+        assert_eq!(format!("{}", a), "0x7b5b54657374566563746f725d53475d");
+        assert_eq!(format!("{:x}", a), "7b5b54657374566563746f725d53475d");
+        assert_eq!(format!("{:X}", a), "7B5B54657374566563746F725D53475D");
+
+        let b = Gf2_128::new(0xabc);
+        assert_eq!(format!("{:#x}", b), "0xabc");
+        assert_eq!(format!("{:08X}", b), "00000ABC");
+    }
+
     #[test]
     // Test multiplication against RustCrypto.
     fn test_gf2_128_against_ghash_impl() {
@@ -6,14 +6,18 @@
 
 pub mod gf2_128;
 pub mod p256;
+pub mod sharing;
 
 use std::{
     fmt::Debug,
+    hash::Hash,
     ops::{Add, Mul, Neg},
 };
 
-use itybity::{BitLength, FromBitIterator, GetBit, Lsb0, Msb0};
-use rand::{distributions::Standard, prelude::Distribution, Rng};
+use itybity::{BitLength, FromBitIterator, GetBit, IntoBits, Lsb0, Msb0};
+use mpz_core::{prg::Prg, Block};
+use rand::{distributions::Standard, prelude::Distribution, Rng, SeedableRng};
+use subtle::Choice;
 
 /// A trait for finite fields.
 pub trait Field:
@@ -31,6 +35,7 @@ pub trait Field:
     + Ord
     + PartialEq
     + Eq
+    + Hash
     + FromBitIterator
     + GetBit<Lsb0>
     + GetBit<Msb0>
@@ -39,12 +44,38 @@ pub trait Field:
     /// The number of bits of a field element.
     const BIT_SIZE: u32;
 
+    /// The identifier of the field, allowing generic code to branch on the field at runtime.
+    const FIELD_ID: FieldId;
+
+    /// The modulus of the field as little-endian bytes.
+    ///
+    /// For prime fields this is the prime order of the field. For binary extension fields this is
+    /// the irreducible polynomial, where bit `i` is the coefficient of `x^i`.
+    const MODULUS_LE_BYTES: &'static [u8];
+
+    /// Returns the modulus of the field as little-endian bytes, see
+    /// [`MODULUS_LE_BYTES`](Self::MODULUS_LE_BYTES).
+    fn modulus() -> Vec<u8> {
+        Self::MODULUS_LE_BYTES.to_vec()
+    }
+
     /// Return the additive identity element.
     fn zero() -> Self;
 
     /// Return the multiplicative identity element.
     fn one() -> Self;
 
+    /// Returns whether the element is zero, in constant time.
+    fn ct_is_zero(&self) -> Choice;
+
+    /// Returns whether the element is zero.
+    ///
+    /// The check is constant-time, see [`ct_is_zero`](Self::ct_is_zero), but the returned
+    /// `bool` itself is not protected, so branching on it leaks whether the element is zero.
+    fn is_zero_bool(&self) -> bool {
+        self.ct_is_zero().into()
+    }
+
     /// Return a field element from a power of two.
     fn two_pow(rhs: u32) -> Self;
 
@@ -58,6 +89,31 @@ pub trait Field:
     fn to_be_bytes(&self) -> Vec<u8>;
 }
 
+/// Identifies a concrete [`Field`] implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FieldId {
+    /// The binary field GF(2^128), see [`Gf2_128`](gf2_128::Gf2_128).
+    Gf2_128,
+    /// The base field of the P256 curve, see [`P256`](p256::P256).
+    P256,
+}
+
+impl FieldId {
+    /// Returns the number of bits of an element of the field.
+    pub const fn bit_size(&self) -> u32 {
+        match self {
+            FieldId::Gf2_128 => 128,
+            FieldId::P256 => 256,
+        }
+    }
+
+    /// Returns the number of bytes of an element of the field.
+    pub const fn byte_size(&self) -> usize {
+        self.bit_size() as usize / 8
+    }
+}
+
 /// A trait for sampling random elements of the field.
 ///
 /// This is helpful, because we do not need to import other traits since this is a supertrait of
@@ -94,9 +150,93 @@ pub fn compute_product_repeated<T: Field>(powers: &mut Vec<T>, factor: T, count:
     }
 }
 
+/// Expands a seed into `count` uniformly distributed field elements.
+///
+/// Each element is sampled from twice as many random bits as the field size and reduced into
+/// the field, so the statistical distance from uniform is negligible even for prime fields
+/// such as P256. The output is deterministic given the seed.
+///
+/// * `seed` - The seed, for example the output of a coin toss.
+/// * `count` - The number of field elements to expand.
+pub fn expand_to_field<T: Field>(seed: Block, count: usize) -> Vec<T> {
+    let limbs = (2 * T::BIT_SIZE as usize).div_ceil(64);
+    let base = T::two_pow(64);
+
+    let mut prg = Prg::from_seed(seed);
+    (0..count)
+        .map(|_| {
+            (0..limbs).fold(T::zero(), |acc, _| {
+                acc * base + T::from_lsb0_iter(prg.gen::<u64>().into_iter_lsb0())
+            })
+        })
+        .collect()
+}
+
+/// An error indicating that the inputs to [`check_vole`] have different lengths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("length mismatch: u has length {u}, v has length {v}, w has length {w}")]
+pub struct LengthMismatch {
+    /// The length of `u`.
+    pub u: usize,
+    /// The length of `v`.
+    pub v: usize,
+    /// The length of `w`.
+    pub w: usize,
+}
+
+/// Checks that the VOLE correlation `v = u * delta + w` holds at every index.
+///
+/// Returns `true` if the inputs are empty.
+///
+/// * `delta` - The global correlation.
+/// * `u` - The sender's inputs.
+/// * `v` - The receiver's outputs.
+/// * `w` - The sender's outputs.
+pub fn check_vole<T: Field>(delta: T, u: &[T], v: &[T], w: &[T]) -> Result<bool, LengthMismatch> {
+    if u.len() != v.len() || u.len() != w.len() {
+        return Err(LengthMismatch {
+            u: u.len(),
+            v: v.len(),
+            w: w.len(),
+        });
+    }
+
+    Ok(u.iter()
+        .zip(v)
+        .zip(w)
+        .all(|((&u, &v), &w)| v == u * delta + w))
+}
+
+/// Implements the `num_traits` identity traits for a field, delegating to [`Field`].
+#[cfg(feature = "num-traits")]
+macro_rules! impl_num_traits {
+    ($ty:ty) => {
+        impl num_traits::Zero for $ty {
+            fn zero() -> Self {
+                <$ty as Field>::zero()
+            }
+
+            fn is_zero(&self) -> bool {
+                <$ty as Field>::is_zero_bool(self)
+            }
+        }
+
+        impl num_traits::One for $ty {
+            fn one() -> Self {
+                <$ty as Field>::one()
+            }
+        }
+    };
+}
+
+#[cfg(feature = "num-traits")]
+impl_num_traits!(gf2_128::Gf2_128);
+#[cfg(feature = "num-traits")]
+impl_num_traits!(p256::P256);
+
 #[cfg(test)]
 mod tests {
-    use super::{compute_product_repeated, Field};
+    use super::{check_vole, compute_product_repeated, expand_to_field, Field, LengthMismatch};
     use itybity::{GetBit, Lsb0};
     use mpz_core::{prg::Prg, Block};
     use rand::SeedableRng;
@@ -130,6 +270,69 @@ mod tests {
         assert_eq!(powers[2], powers[1] * factor);
     }
 
+    pub(crate) fn test_field_check_vole<T: Field>() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let delta = T::rand(&mut rng);
+        let u: Vec<T> = (0..16).map(|_| T::rand(&mut rng)).collect();
+        let w: Vec<T> = (0..16).map(|_| T::rand(&mut rng)).collect();
+        let mut v: Vec<T> = u.iter().zip(&w).map(|(&u, &w)| u * delta + w).collect();
+
+        assert_eq!(check_vole(delta, &u, &v, &w), Ok(true));
+
+        v[7] = v[7] + T::one();
+        assert_eq!(check_vole(delta, &u, &v, &w), Ok(false));
+
+        assert_eq!(check_vole::<T>(delta, &[], &[], &[]), Ok(true));
+
+        assert_eq!(
+            check_vole(delta, &u, &v[..15], &w),
+            Err(LengthMismatch {
+                u: 16,
+                v: 15,
+                w: 16
+            })
+        );
+    }
+
+    pub(crate) fn test_field_hash<T: Field>() {
+        use std::collections::HashSet;
+
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let a = T::rand(&mut rng);
+
+        // Construct an equal element from its bits and via arithmetic.
+        let b =
+            T::from_lsb0_iter((0..T::BIT_SIZE as usize).map(|i| GetBit::<Lsb0>::get_bit(&a, i)));
+        let c = (a + T::one()) * T::one() + -T::one();
+
+        let set = HashSet::from([a]);
+
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+        assert!(set.contains(&b));
+        assert!(set.contains(&c));
+        assert!(!set.contains(&(a + T::one())));
+    }
+
+    pub(crate) fn test_field_is_zero<T: Field>() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let elems = [T::zero(), T::one(), -T::one(), T::two_pow(T::BIT_SIZE - 1)]
+            .into_iter()
+            .chain((0..8).map(|_| T::rand(&mut rng)));
+
+        for elem in elems {
+            let expected = elem == T::zero();
+
+            assert_eq!(bool::from(elem.ct_is_zero()), expected);
+            assert_eq!(elem.is_zero_bool(), expected);
+        }
+
+        assert!(T::zero().is_zero_bool());
+        assert!(!T::one().is_zero_bool());
+    }
+
     pub(crate) fn test_field_bit_ops<T: Field>() {
         let mut a = vec![false; T::BIT_SIZE as usize];
         let mut b = vec![false; T::BIT_SIZE as usize];
@@ -146,4 +349,72 @@ mod tests {
         assert_eq!(b, T::two_pow(T::BIT_SIZE - 1));
         assert!(GetBit::<Lsb0>::get_bit(&b, (T::BIT_SIZE - 1) as usize));
     }
+
+    #[test]
+    fn test_expand_to_field() {
+        use crate::{gf2_128::Gf2_128, p256::P256};
+
+        fn check<T: Field>() {
+            let seed = Block::from([42u8; 16]);
+
+            let a = expand_to_field::<T>(seed, 100);
+            let b = expand_to_field::<T>(seed, 100);
+
+            assert_eq!(a.len(), 100);
+            assert_eq!(a, b);
+            assert_ne!(a, expand_to_field::<T>(Block::ZERO, 100));
+            assert_eq!(&expand_to_field::<T>(seed, 10)[..], &a[..10]);
+            assert!(expand_to_field::<T>(seed, 0).is_empty());
+        }
+
+        check::<Gf2_128>();
+        check::<P256>();
+    }
+
+    #[cfg(feature = "num-traits")]
+    #[test]
+    fn test_num_traits() {
+        use std::ops::Mul;
+
+        use crate::{gf2_128::Gf2_128, p256::P256};
+        use num_traits::{One, Zero};
+
+        fn product<T: Zero + One + Mul<Output = T> + Copy>(elems: &[T]) -> T {
+            elems.iter().fold(T::one(), |acc, &elem| acc * elem)
+        }
+
+        fn check<T: Field + Zero + One>() {
+            let mut rng = Prg::from_seed(Block::ZERO);
+            let elems: Vec<T> = (0..8).map(|_| T::rand(&mut rng)).collect();
+
+            let expected = elems
+                .iter()
+                .fold(<T as Field>::one(), |acc, &elem| acc * elem);
+
+            assert_eq!(product(&elems), expected);
+            assert_eq!(product::<T>(&[]), <T as Field>::one());
+            assert!(Zero::is_zero(&<T as Zero>::zero()));
+            assert!(!Zero::is_zero(&<T as One>::one()));
+        }
+
+        check::<Gf2_128>();
+        check::<P256>();
+    }
+
+    #[test]
+    fn test_field_id() {
+        use crate::{gf2_128::Gf2_128, p256::P256, FieldId};
+
+        fn check<T: Field>() {
+            assert_eq!(T::FIELD_ID.bit_size(), T::BIT_SIZE);
+            assert_eq!(T::FIELD_ID.byte_size(), T::zero().to_le_bytes().len());
+        }
+
+        check::<Gf2_128>();
+        check::<P256>();
+
+        assert_eq!(Gf2_128::FIELD_ID, FieldId::Gf2_128);
+        assert_eq!(P256::FIELD_ID, FieldId::P256);
+        assert_ne!(Gf2_128::FIELD_ID, P256::FIELD_ID);
+    }
 }
@@ -1,16 +1,21 @@
 //! This module implements the prime field of P256.
 
-use std::ops::{Add, Mul, Neg};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Add, Mul, Neg},
+};
 
 use ark_ff::{BigInt, BigInteger, Field as ArkField, FpConfig, MontBackend, One, Zero};
 use ark_secp256r1::{fq::Fq, FqConfig};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
 use itybity::{BitLength, FromBitIterator, GetBit, Lsb0, Msb0};
-use num_bigint::ToBigUint;
+use num_bigint::{BigUint, ToBigUint};
 use rand::{distributions::Standard, prelude::Distribution};
 use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 
-use super::Field;
+use super::{Field, FieldId};
 
 /// A type for holding field elements of P256.
 #[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize)]
@@ -20,11 +25,78 @@ pub struct P256(pub(crate) Fq);
 
 opaque_debug::implement!(P256);
 
+impl Hash for P256 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash the limbs of the canonical form rather than the Montgomery representation,
+        // so that equal elements have equal hashes.
+        MontBackend::<FqConfig, 4>::into_bigint(self.0)
+            .0
+            .hash(state);
+    }
+}
+
 impl P256 {
     /// Creates a new field element, returning `None` if the value is not a valid element.
     pub fn new(value: impl ToBigUint) -> Option<Self> {
         value.to_biguint().map(|input| P256(Fq::from(input)))
     }
+
+    /// Returns a square root of the field element, or `None` if the element is not a
+    /// quadratic residue.
+    pub fn sqrt(&self) -> Option<Self> {
+        ArkField::sqrt(&self.0).map(P256)
+    }
+
+    /// Returns whether the field element is a square, ie. a quadratic residue or zero.
+    pub fn is_square(&self) -> bool {
+        !self.0.legendre().is_qnr()
+    }
+
+    /// Computes `out[i] = a[i] * b[i] + c[i]` for every index.
+    ///
+    /// This is the OLE relation `y = a * b + x` evaluated over whole vectors. Each element
+    /// is reduced individually, so the result is the same as the element-wise computation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices do not all have the same length.
+    pub fn mul_add_slices(out: &mut [P256], a: &[P256], b: &[P256], c: &[P256]) {
+        assert!(
+            a.len() == out.len() && b.len() == out.len() && c.len() == out.len(),
+            "slices must have the same length"
+        );
+
+        for (((out, a), b), c) in out.iter_mut().zip(a).zip(b).zip(c) {
+            let mut y = a.0;
+            y *= &b.0;
+            y += &c.0;
+            out.0 = y;
+        }
+    }
+
+    /// Returns the canonical integer representation of the field element.
+    fn to_biguint(self) -> BigUint {
+        BigUint::from_bytes_be(&self.to_be_bytes())
+    }
+}
+
+impl fmt::Display for P256 {
+    /// Formats the field element as its canonical decimal integer.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_biguint(), f)
+    }
+}
+
+impl fmt::LowerHex for P256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.to_biguint(), f)
+    }
+}
+
+impl fmt::UpperHex for P256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.to_biguint(), f)
+    }
 }
 
 impl From<P256> for [u8; 32] {
@@ -81,6 +153,13 @@ impl Neg for P256 {
 
 impl Field for P256 {
     const BIT_SIZE: u32 = 256;
+    const FIELD_ID: FieldId = FieldId::P256;
+    /// The prime `2^256 - 2^224 + 2^192 + 2^96 - 1`.
+    const MODULUS_LE_BYTES: &'static [u8] = &[
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0xff, 0xff,
+        0xff, 0xff,
+    ];
 
     fn zero() -> Self {
         P256(<Fq as Zero>::zero())
@@ -90,6 +169,16 @@ impl Field for P256 {
         P256(<Fq as One>::one())
     }
 
+    /// Checks whether the element is zero by OR-reducing its limbs in constant time.
+    ///
+    /// Zero is represented by all-zero limbs in Montgomery form, so the limbs can be checked
+    /// directly.
+    fn ct_is_zero(&self) -> Choice {
+        let limbs = self.0 .0 .0.iter().fold(0u64, |acc, limb| acc | limb);
+
+        limbs.ct_eq(&0)
+    }
+
     fn two_pow(rhs: u32) -> Self {
         let mut out = <Fq as One>::one();
         for _ in 0..rhs {
@@ -99,8 +188,31 @@ impl Field for P256 {
         P256(out)
     }
 
+    /// Computes the multiplicative inverse in constant time.
+    ///
+    /// The inverse is computed using Fermat's little theorem, ie. `a^(p-2)`. The
+    /// exponentiation runs a fixed number of iterations, each performing a squaring and a
+    /// multiplication whose result is selected branch-free. The Montgomery multiplication is
+    /// implemented here on the limbs without data-dependent branches, rather than using the
+    /// `ark-ff` backend, which is not constant-time.
+    ///
+    /// Zero has no inverse, and is mapped to zero.
     fn inverse(self) -> Self {
-        P256(ArkField::inverse(&self.0).expect("Unable to invert field element"))
+        let base = self.0 .0 .0;
+        // One in Montgomery form.
+        let mut acc = <Fq as One>::one().0 .0;
+
+        for i in (0..256).rev() {
+            acc = ct_mont_mul(&acc, &acc);
+            let product = ct_mont_mul(&acc, &base);
+
+            let bit = Choice::from(((P_MINUS_2[i / 64] >> (i % 64)) & 1) as u8);
+            for (acc, product) in acc.iter_mut().zip(product) {
+                acc.conditional_assign(&product, bit);
+            }
+        }
+
+        P256(Fq::new_unchecked(BigInt(acc)))
     }
 
     fn to_le_bytes(&self) -> Vec<u8> {
@@ -112,6 +224,70 @@ impl Field for P256 {
     }
 }
 
+/// The limbs of the P256 prime, least significant first.
+const P: [u64; 4] = [
+    0xffffffffffffffff,
+    0x00000000ffffffff,
+    0x0000000000000000,
+    0xffffffff00000001,
+];
+
+/// The limbs of the P256 prime minus two, the exponent of the inverse.
+const P_MINUS_2: [u64; 4] = [
+    0xfffffffffffffffd,
+    0x00000000ffffffff,
+    0x0000000000000000,
+    0xffffffff00000001,
+];
+
+/// `-p^-1 mod 2^64`, which is one as the lowest limb of the prime is `2^64 - 1`.
+const P_INV: u64 = 1;
+
+/// Montgomery multiplication of two elements in Montgomery form, ie. `a * b * 2^-256 mod p`.
+///
+/// This is the CIOS method followed by a final subtraction which is selected branch-free, so
+/// the sequence of operations does not depend on the operands.
+fn ct_mont_mul(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut t = [0u64; 6];
+    for &b in b {
+        let mut carry = 0u64;
+        for (t, &a) in t.iter_mut().zip(a) {
+            let v = *t as u128 + a as u128 * b as u128 + carry as u128;
+            *t = v as u64;
+            carry = (v >> 64) as u64;
+        }
+        let v = t[4] as u128 + carry as u128;
+        t[4] = v as u64;
+        t[5] = (v >> 64) as u64;
+
+        let m = t[0].wrapping_mul(P_INV);
+        let v = t[0] as u128 + m as u128 * P[0] as u128;
+        let mut carry = (v >> 64) as u64;
+        for j in 1..4 {
+            let v = t[j] as u128 + m as u128 * P[j] as u128 + carry as u128;
+            t[j - 1] = v as u64;
+            carry = (v >> 64) as u64;
+        }
+        let v = t[4] as u128 + carry as u128;
+        t[3] = v as u64;
+        t[4] = t[5] + (v >> 64) as u64;
+    }
+
+    // The result is less than `2p`, subtract `p` unless that underflows.
+    let mut reduced = [0u64; 4];
+    let mut borrow = 0u64;
+    for ((reduced, &t), &p) in reduced.iter_mut().zip(&t).zip(&P) {
+        let (diff, borrow_0) = t.overflowing_sub(p);
+        let (diff, borrow_1) = diff.overflowing_sub(borrow);
+        *reduced = diff;
+        borrow = (borrow_0 | borrow_1) as u64;
+    }
+    let (_, underflow) = t[4].overflowing_sub(borrow);
+    let underflow = Choice::from(underflow as u8);
+
+    std::array::from_fn(|j| u64::conditional_select(&reduced[j], &t[j], underflow))
+}
+
 impl BitLength for P256 {
     const BITS: usize = 256;
 }
@@ -140,11 +316,16 @@ impl FromBitIterator for P256 {
 
 #[cfg(test)]
 mod tests {
+    use ark_ff::PrimeField;
+
     use super::*;
     use mpz_core::{prg::Prg, Block};
     use rand::{Rng, SeedableRng};
 
-    use crate::tests::{test_field_basic, test_field_bit_ops, test_field_compute_product_repeated};
+    use crate::tests::{
+        test_field_basic, test_field_bit_ops, test_field_check_vole,
+        test_field_compute_product_repeated, test_field_hash, test_field_is_zero,
+    };
 
     #[test]
     fn test_p256_basic() {
@@ -163,6 +344,156 @@ mod tests {
         test_field_bit_ops::<P256>();
     }
 
+    #[test]
+    fn test_p256_hash() {
+        test_field_hash::<P256>();
+    }
+
+    #[test]
+    fn test_p256_modulus() {
+        let modulus: [u8; 32] = P256::modulus().try_into().unwrap();
+
+        assert_eq!(BigInt::to_bytes_le(&Fq::MODULUS), modulus);
+
+        // The modulus is out of range, but the modulus minus one is in range.
+        assert!(P256::try_from(modulus).is_err());
+
+        let mut max = modulus;
+        max[0] -= 1;
+
+        assert_eq!(P256::try_from(max).unwrap(), -P256::one());
+    }
+
+    #[test]
+    fn test_p256_is_zero() {
+        test_field_is_zero::<P256>();
+    }
+
+    #[test]
+    fn test_p256_check_vole() {
+        test_field_check_vole::<P256>();
+    }
+
+    #[test]
+    fn test_p256_mul_add_slices() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        for len in [0, 1, 7, 64] {
+            let a: Vec<P256> = (0..len).map(|_| rng.gen()).collect();
+            let b: Vec<P256> = (0..len).map(|_| rng.gen()).collect();
+            let c: Vec<P256> = (0..len).map(|_| rng.gen()).collect();
+
+            let mut out = vec![P256::zero(); len];
+            P256::mul_add_slices(&mut out, &a, &b, &c);
+
+            let expected: Vec<P256> = a
+                .iter()
+                .zip(&b)
+                .zip(&c)
+                .map(|((a, b), c)| *a * *b + *c)
+                .collect();
+
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "slices must have the same length")]
+    fn test_p256_mul_add_slices_length_mismatch() {
+        let mut out = vec![P256::zero(); 2];
+        P256::mul_add_slices(
+            &mut out,
+            &[P256::one(); 2],
+            &[P256::one(); 2],
+            &[P256::one()],
+        );
+    }
+
+    #[test]
+    fn test_p256_inverse() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        for _ in 0..32 {
+            let a = P256(rng.gen());
+            if a == P256::zero() {
+                continue;
+            }
+
+            let expected = P256(ArkField::inverse(&a.0).unwrap());
+
+            assert_eq!(a.inverse(), expected);
+            assert_eq!(a * a.inverse(), P256::one());
+        }
+    }
+
+    #[test]
+    fn test_p256_inverse_edge_cases() {
+        assert_eq!(P256::zero().inverse(), P256::zero());
+        assert_eq!(P256::one().inverse(), P256::one());
+
+        // p - 1 is its own inverse.
+        assert_eq!((-P256::one()).inverse(), -P256::one());
+
+        let two = P256::one() + P256::one();
+        assert_eq!(two * two.inverse(), P256::one());
+    }
+
+    #[test]
+    fn test_p256_sqrt() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        for _ in 0..32 {
+            let a = P256(rng.gen());
+
+            match a.sqrt() {
+                Some(root) => {
+                    assert!(a.is_square());
+                    assert_eq!(root * root, a);
+                }
+                None => assert!(!a.is_square()),
+            }
+
+            let square = a * a;
+            assert!(square.is_square());
+            let root = square.sqrt().unwrap();
+            assert!(root == a || root == -a);
+        }
+
+        let one = P256::one().sqrt().unwrap();
+        assert!(one == P256::one() || one == -P256::one());
+
+        assert_eq!(P256::zero().sqrt(), Some(P256::zero()));
+        assert!(P256::zero().is_square());
+
+        // p = 3 mod 4, so -1 is not a quadratic residue.
+        let minus_one = -P256::one();
+        assert_eq!(minus_one.sqrt(), None);
+        assert!(!minus_one.is_square());
+    }
+
+    #[test]
+    fn test_p256_format() {
+        let one = P256::one();
+        assert_eq!(format!("{}", one), "1");
+        assert_eq!(format!("{:x}", one), "1");
+        assert_eq!(format!("{:X}", one), "1");
+
+        let a = P256::new(0xdeadbeefu32).unwrap();
+        assert_eq!(format!("{}", a), "3735928559");
+        assert_eq!(format!("{:x}", a), "deadbeef");
+        assert_eq!(format!("{:#X}", a), "0xDEADBEEF");
+
+        let minus_one = -P256::one();
+        assert_eq!(
+            format!("{}", minus_one),
+            "115792089210356248762697446949407573530086143415290314195533631308867097853950"
+        );
+        assert_eq!(
+            format!("{:x}", minus_one),
+            "ffffffff00000001000000000000000000000000fffffffffffffffffffffffe"
+        );
+    }
+
     #[test]
     fn test_p256_serialize() {
         let mut rng = Prg::from_seed(Block::ZERO);
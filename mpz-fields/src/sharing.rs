@@ -0,0 +1,97 @@
+//! Additive secret sharing over a field.
+
+use rand::Rng;
+
+use crate::Field;
+
+/// Splits a secret into `n` additive shares, which sum to the secret.
+///
+/// The first `n - 1` shares are sampled uniformly at random, and the last share is chosen such
+/// that the shares sum to the secret. Hence, any `n - 1` of the shares are uniformly distributed
+/// and reveal nothing about the secret.
+///
+/// * `secret` - The secret to share.
+/// * `n` - The number of shares.
+/// * `rng` - The random number generator used to sample the shares.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+pub fn additive_share<T: Field, R: Rng + ?Sized>(secret: T, n: usize, rng: &mut R) -> Vec<T> {
+    assert!(n > 0, "number of shares must be at least 1");
+
+    let mut shares: Vec<T> = (0..n - 1).map(|_| T::rand(rng)).collect();
+    let sum = reconstruct(&shares);
+    shares.push(secret + -sum);
+
+    shares
+}
+
+/// Reconstructs a secret from its additive shares, by summing them.
+///
+/// Returns zero if there are no shares.
+///
+/// * `shares` - The shares of the secret.
+pub fn reconstruct<T: Field>(shares: &[T]) -> T {
+    shares.iter().fold(T::zero(), |acc, &share| acc + share)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2_128::Gf2_128, p256::P256};
+    use mpz_core::{prg::Prg, Block};
+    use rand::SeedableRng;
+
+    fn test_sharing<T: Field>() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        for n in [1, 2, 5] {
+            let secret = T::rand(&mut rng);
+            let shares = additive_share(secret, n, &mut rng);
+
+            assert_eq!(shares.len(), n);
+            assert_eq!(reconstruct(&shares), secret);
+        }
+
+        assert_eq!(reconstruct::<T>(&[]), T::zero());
+    }
+
+    fn test_sharing_deterministic<T: Field>() {
+        let secret = T::rand(&mut Prg::from_seed(Block::ONES));
+
+        let shares = additive_share(secret, 5, &mut Prg::from_seed(Block::ZERO));
+
+        // All but the last share are sampled directly from the rng.
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let expected: Vec<T> = (0..4).map(|_| T::rand(&mut rng)).collect();
+
+        assert_eq!(&shares[..4], &expected[..]);
+        assert_eq!(
+            shares,
+            additive_share(secret, 5, &mut Prg::from_seed(Block::ZERO))
+        );
+        assert_ne!(
+            shares,
+            additive_share(secret, 5, &mut Prg::from_seed(Block::ONES))
+        );
+    }
+
+    #[test]
+    fn test_sharing_gf2_128() {
+        test_sharing::<Gf2_128>();
+        test_sharing_deterministic::<Gf2_128>();
+    }
+
+    #[test]
+    fn test_sharing_p256() {
+        test_sharing::<P256>();
+        test_sharing_deterministic::<P256>();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sharing_zero_shares() {
+        additive_share(P256::one(), 0, &mut Prg::from_seed(Block::ZERO));
+    }
+}
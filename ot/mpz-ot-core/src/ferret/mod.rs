@@ -19,21 +19,11 @@ pub const CUCKOO_HASH_NUM: usize = 3;
 /// Trial numbers in Cuckoo hash insertion.
 pub const CUCKOO_TRIAL_NUM: usize = 100;
 
-/// LPN parameters with regular noise.
-/// Derived from https://github.com/emp-toolkit/emp-ot/blob/master/emp-ot/ferret/constants.h
-pub const LPN_PARAMETERS_REGULAR: LpnParameters = LpnParameters {
-    n: 10180608,
-    k: 124000,
-    t: 4971,
-};
-
-/// LPN parameters with uniform noise.
-/// Derived from Table 2.
-pub const LPN_PARAMETERS_UNIFORM: LpnParameters = LpnParameters {
-    n: 10616092,
-    k: 588160,
-    t: 1324,
-};
+/// LPN parameters with regular noise, see [`LpnParameters::REGULAR`].
+pub const LPN_PARAMETERS_REGULAR: LpnParameters = LpnParameters::REGULAR;
+
+/// LPN parameters with uniform noise, see [`LpnParameters::UNIFORM`].
+pub const LPN_PARAMETERS_UNIFORM: LpnParameters = LpnParameters::UNIFORM;
 
 /// The type of Lpn parameters.
 pub enum LpnType {
@@ -25,6 +25,8 @@ pub struct IdealCOT {
     prg: Prg,
 }
 
+opaque_debug::implement!(IdealCOT);
+
 impl IdealCOT {
     /// Initiate the functionality
     pub fn new() -> Self {
@@ -1,19 +1,88 @@
+use std::{fmt, sync::Arc};
+
 use derive_builder::Builder;
 
+use super::{MIN_SSP, SSP};
+
+/// A hook which is invoked at the boundary of a protocol flight.
+///
+/// The hook is called with the name of the flight and its size in bytes. The size is the
+/// serialized length of the flight's message, except for the setup flight, which reports the
+/// size of the seeds transferred by the base OT. Both parties report the same size for a flight.
+#[derive(Clone)]
+pub struct FlightHook(Arc<dyn Fn(&str, usize) + Send + Sync>);
+
+impl FlightHook {
+    /// Creates a new flight hook.
+    pub fn new(f: impl Fn(&str, usize) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Invokes the hook.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the flight.
+    /// * `bytes` - The size of the flight's payload in bytes.
+    pub fn call(&self, name: &str, bytes: usize) {
+        (self.0)(name, bytes)
+    }
+}
+
+impl fmt::Debug for FlightHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FlightHook")
+    }
+}
+
 /// KOS15 sender configuration.
-#[derive(Debug, Default, Clone, Builder)]
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct SenderConfig {
     /// Enables committed sender functionality.
     #[builder(setter(custom), default = "false")]
     sender_commit: bool,
+    /// The statistical security parameter.
+    ///
+    /// Must match the receiver's statistical security parameter.
+    #[builder(default = "SSP")]
+    ssp: usize,
+    /// Hook invoked when a flight is sent.
+    #[builder(setter(custom), default)]
+    on_flight_sent: Option<FlightHook>,
+    /// Hook invoked when a flight is received.
+    #[builder(setter(custom), default)]
+    on_flight_received: Option<FlightHook>,
 }
 
 impl SenderConfigBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_ssp(self.ssp)
+    }
+
     /// Enables committed sender functionality.
     pub fn sender_commit(&mut self) -> &mut Self {
         self.sender_commit = Some(true);
         self
     }
+
+    /// Sets a hook which is invoked when a flight is sent.
+    pub fn on_flight_sent(
+        &mut self,
+        hook: impl Fn(&str, usize) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.on_flight_sent = Some(Some(FlightHook::new(hook)));
+        self
+    }
+
+    /// Sets a hook which is invoked when a flight is received.
+    pub fn on_flight_received(
+        &mut self,
+        hook: impl Fn(&str, usize) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.on_flight_received = Some(Some(FlightHook::new(hook)));
+        self
+    }
 }
 
 impl SenderConfig {
@@ -26,22 +95,77 @@ impl SenderConfig {
     pub fn sender_commit(&self) -> bool {
         self.sender_commit
     }
+
+    /// Returns the statistical security parameter.
+    pub fn ssp(&self) -> usize {
+        self.ssp
+    }
+
+    /// Returns the hook invoked when a flight is sent.
+    pub fn on_flight_sent(&self) -> Option<&FlightHook> {
+        self.on_flight_sent.as_ref()
+    }
+
+    /// Returns the hook invoked when a flight is received.
+    pub fn on_flight_received(&self) -> Option<&FlightHook> {
+        self.on_flight_received.as_ref()
+    }
+}
+
+impl Default for SenderConfig {
+    fn default() -> Self {
+        SenderConfigBuilder::default().build().unwrap()
+    }
 }
 
 /// KOS15 receiver configuration.
-#[derive(Debug, Default, Clone, Builder)]
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct ReceiverConfig {
     /// Enables committed sender functionality.
     #[builder(setter(custom), default = "false")]
     sender_commit: bool,
+    /// The statistical security parameter.
+    ///
+    /// Must match the sender's statistical security parameter.
+    #[builder(default = "SSP")]
+    ssp: usize,
+    /// Hook invoked when a flight is sent.
+    #[builder(setter(custom), default)]
+    on_flight_sent: Option<FlightHook>,
+    /// Hook invoked when a flight is received.
+    #[builder(setter(custom), default)]
+    on_flight_received: Option<FlightHook>,
 }
 
 impl ReceiverConfigBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_ssp(self.ssp)
+    }
+
     /// Enables committed sender functionality.
     pub fn sender_commit(&mut self) -> &mut Self {
         self.sender_commit = Some(true);
         self
     }
+
+    /// Sets a hook which is invoked when a flight is sent.
+    pub fn on_flight_sent(
+        &mut self,
+        hook: impl Fn(&str, usize) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.on_flight_sent = Some(Some(FlightHook::new(hook)));
+        self
+    }
+
+    /// Sets a hook which is invoked when a flight is received.
+    pub fn on_flight_received(
+        &mut self,
+        hook: impl Fn(&str, usize) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.on_flight_received = Some(Some(FlightHook::new(hook)));
+        self
+    }
 }
 
 impl ReceiverConfig {
@@ -54,4 +178,35 @@ impl ReceiverConfig {
     pub fn sender_commit(&self) -> bool {
         self.sender_commit
     }
+
+    /// Returns the statistical security parameter.
+    pub fn ssp(&self) -> usize {
+        self.ssp
+    }
+
+    /// Returns the hook invoked when a flight is sent.
+    pub fn on_flight_sent(&self) -> Option<&FlightHook> {
+        self.on_flight_sent.as_ref()
+    }
+
+    /// Returns the hook invoked when a flight is received.
+    pub fn on_flight_received(&self) -> Option<&FlightHook> {
+        self.on_flight_received.as_ref()
+    }
+}
+
+impl Default for ReceiverConfig {
+    fn default() -> Self {
+        ReceiverConfigBuilder::default().build().unwrap()
+    }
+}
+
+/// Checks that the statistical security parameter is not below the safe floor.
+fn validate_ssp(ssp: Option<usize>) -> Result<(), String> {
+    match ssp {
+        Some(ssp) if ssp < MIN_SSP => Err(format!(
+            "statistical security parameter must be at least {MIN_SSP}, got {ssp}"
+        )),
+        _ => Ok(()),
+    }
 }
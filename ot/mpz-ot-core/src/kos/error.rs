@@ -16,6 +16,9 @@ pub enum SenderError {
     ConsistencyCheckFailed,
     #[error("not enough OTs are setup: expected {0}, actual {1}")]
     InsufficientSetup(usize, usize),
+    #[cfg(feature = "debug-checks")]
+    #[error("debug check failed: {0}")]
+    DebugCheckFailed(String),
 }
 
 /// Errors that can occur when using the KOS15 receiver.
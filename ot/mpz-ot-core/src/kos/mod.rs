@@ -5,9 +5,10 @@ mod error;
 pub mod msgs;
 mod receiver;
 mod sender;
+mod transcript;
 
 pub use config::{
-    ReceiverConfig, ReceiverConfigBuilder, ReceiverConfigBuilderError, SenderConfig,
+    FlightHook, ReceiverConfig, ReceiverConfigBuilder, ReceiverConfigBuilderError, SenderConfig,
     SenderConfigBuilder, SenderConfigBuilderError,
 };
 pub use error::{ReceiverError, ReceiverVerifyError, SenderError};
@@ -15,11 +16,14 @@ use rand_chacha::ChaCha20Rng;
 use rand_core::SeedableRng;
 pub use receiver::{state as receiver_state, PayloadRecord, Receiver, ReceiverKeys};
 pub use sender::{state as sender_state, Sender, SenderKeys};
+pub use transcript::{dump_transcript, KosTranscript};
 
 /// Computational security parameter
 pub const CSP: usize = 128;
 /// Statistical security parameter
 pub const SSP: usize = 128;
+/// Minimum statistical security parameter accepted by the configuration builders.
+pub const MIN_SSP: usize = 40;
 /// Rng to use for secret sharing the IKNP matrix.
 pub(crate) type Rng = ChaCha20Rng;
 /// Rng seed type
@@ -30,9 +34,15 @@ pub(crate) type Aes128Ctr = ctr::Ctr64LE<aes::Aes128>;
 
 /// Pads the number of OTs to accommodate for the KOS extension check and
 /// the extension matrix transpose optimization.
-pub fn pad_ot_count(mut count: usize) -> usize {
+pub fn pad_ot_count(count: usize) -> usize {
+    pad_ot_count_with_ssp(count, SSP)
+}
+
+/// Pads the number of OTs to accommodate for the KOS extension check with the
+/// given statistical security parameter, and the extension matrix transpose optimization.
+pub fn pad_ot_count_with_ssp(mut count: usize, ssp: usize) -> usize {
     // Add OTs for the KOS extension check.
-    count += CSP + SSP;
+    count += CSP + ssp;
     // Round up the OTs to extend to the nearest multiple of 64 (matrix transpose optimization).
     (count + 63) & !63
 }
@@ -138,6 +148,141 @@ mod tests {
         assert_eq!(received, expected);
     }
 
+    #[cfg(feature = "debug-checks")]
+    #[rstest]
+    fn test_kos_extension_debug_check(
+        delta: Block,
+        sender_seeds: [Block; CSP],
+        receiver_seeds: [[Block; 2]; CSP],
+        choices: Vec<bool>,
+    ) {
+        let sender = Sender::new(SenderConfig::default());
+        let receiver = Receiver::new(ReceiverConfig::default());
+
+        let mut sender = sender.setup(delta, sender_seeds);
+        let mut receiver = receiver.setup(receiver_seeds);
+
+        let receiver_setup = receiver.extend(choices.len() + 256).unwrap();
+        sender.extend(choices.len() + 256, receiver_setup).unwrap();
+
+        let sample = receiver.debug_sample(8);
+        sender.debug_check(&sample).unwrap();
+
+        let mut bad_sample = sample.clone();
+        bad_sample.choices[0] = !bad_sample.choices[0];
+
+        assert!(matches!(
+            sender.debug_check(&bad_sample),
+            Err(SenderError::DebugCheckFailed(_))
+        ));
+    }
+
+    #[rstest]
+    #[case::min(MIN_SSP)]
+    #[case::default(SSP)]
+    fn test_kos_extension_ssp(
+        delta: Block,
+        sender_seeds: [Block; CSP],
+        receiver_seeds: [[Block; 2]; CSP],
+        chi_seed: Block,
+        choices: Vec<bool>,
+        data: Vec<[Block; 2]>,
+        expected: Vec<Block>,
+        #[case] ssp: usize,
+    ) {
+        let sender = Sender::new(SenderConfig::builder().ssp(ssp).build().unwrap());
+        let receiver = Receiver::new(ReceiverConfig::builder().ssp(ssp).build().unwrap());
+
+        let mut sender = sender.setup(delta, sender_seeds);
+        let mut receiver = receiver.setup(receiver_seeds);
+
+        let count = pad_ot_count_with_ssp(choices.len(), ssp);
+        let receiver_setup = receiver.extend(count).unwrap();
+        sender.extend(count, receiver_setup).unwrap();
+
+        let receiver_check = receiver.check(chi_seed).unwrap();
+        sender.check(chi_seed, receiver_check).unwrap();
+
+        let mut receiver_keys = receiver.keys(choices.len()).unwrap();
+        let derandomize = receiver_keys.derandomize(&choices).unwrap();
+
+        let mut sender_keys = sender.keys(data.len()).unwrap();
+        sender_keys.derandomize(derandomize).unwrap();
+        let payload = sender_keys.encrypt_blocks(&data).unwrap();
+
+        let received = receiver_keys.decrypt_blocks(payload).unwrap();
+
+        assert_eq!(received, expected);
+    }
+
+    #[test]
+    fn test_kos_config_rejects_low_ssp() {
+        assert!(SenderConfig::builder().ssp(1).build().is_err());
+        assert!(ReceiverConfig::builder().ssp(1).build().is_err());
+        assert!(SenderConfig::builder().ssp(MIN_SSP - 1).build().is_err());
+        assert!(ReceiverConfig::builder().ssp(MIN_SSP - 1).build().is_err());
+    }
+
+    #[rstest]
+    fn test_kos_transcript(
+        delta: Block,
+        sender_seeds: [Block; CSP],
+        receiver_seeds: [[Block; 2]; CSP],
+        chi_seed: Block,
+        choices: Vec<bool>,
+        data: Vec<[Block; 2]>,
+        expected: Vec<Block>,
+    ) {
+        let choices_seed = Block::ONES;
+
+        let transcript = dump_transcript(
+            delta,
+            receiver_seeds,
+            choices_seed,
+            chi_seed,
+            &choices,
+            &data,
+        );
+
+        let sender = Sender::new(SenderConfig::default());
+        let receiver = Receiver::new(ReceiverConfig::default());
+
+        let mut sender = sender.setup(delta, sender_seeds);
+        let mut receiver = receiver.setup(receiver_seeds);
+
+        let mut seed = RngSeed::default();
+        seed.iter_mut()
+            .zip(choices_seed.to_bytes().into_iter().cycle())
+            .for_each(|(s, c)| *s = c);
+        let mut rng = ChaCha20Rng::from_seed(seed);
+
+        let count = pad_ot_count(choices.len());
+        let receiver_setup = receiver.extend_with_rng(count, &mut rng).unwrap();
+        assert_eq!(receiver_setup, transcript.extend);
+        sender.extend(count, receiver_setup).unwrap();
+
+        let receiver_check = receiver.check(chi_seed).unwrap();
+        assert_eq!(receiver_check, transcript.check);
+        sender.check(chi_seed, receiver_check).unwrap();
+
+        let mut receiver_keys = receiver.keys(choices.len()).unwrap();
+        assert_eq!(receiver_keys.keys(), transcript.receiver_keys);
+        let derandomize = receiver_keys.derandomize(&choices).unwrap();
+        assert_eq!(derandomize, transcript.derandomize);
+
+        let mut sender_keys = sender.keys(data.len()).unwrap();
+        assert_eq!(sender_keys.keys(), transcript.sender_keys);
+        sender_keys.derandomize(derandomize).unwrap();
+        let payload = sender_keys.encrypt_blocks(&data).unwrap();
+        assert_eq!(payload, transcript.payload);
+
+        let received = receiver_keys.decrypt_blocks(payload).unwrap();
+
+        assert_eq!(received, transcript.received);
+        assert_eq!(received, expected);
+        assert_eq!(transcript.sender_seeds, sender_seeds.to_vec());
+    }
+
     #[rstest]
     fn test_kos_extension_bytes(
         delta: Block,
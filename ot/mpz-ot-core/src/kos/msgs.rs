@@ -26,6 +26,8 @@ pub enum Message<BaseMsg> {
     CointossCommit(SenderCommitment),
     CointossReceiverPayload(CointossReceiverPayload),
     CointossSenderPayload(CointossSenderPayload),
+    #[cfg(feature = "debug-checks")]
+    DebugSample(DebugSample),
 }
 
 impl<BaseMsg> From<MessageError<BaseMsg>> for std::io::Error {
@@ -78,6 +80,22 @@ impl Iterator for ExtendChunks {
     }
 }
 
+/// A sample of the receiver's unchecked correlations, which is only sent when the `debug-checks`
+/// feature is enabled.
+///
+/// The message is only part of the protocol with the feature, so both peers must enable it.
+/// The sample reveals the receiver's choices for the sampled OTs.
+#[cfg(feature = "debug-checks")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DebugSample {
+    /// The indices of the sampled OTs, relative to the unchecked OTs.
+    pub indices: Vec<usize>,
+    /// The receiver's `t` for each sampled OT.
+    pub ts: Vec<Block>,
+    /// The receiver's choice for each sampled OT.
+    pub choices: Vec<bool>,
+}
+
 /// Values for the correlation check sent by the receiver.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -7,7 +7,7 @@ use crate::{
     kos::{
         error::ReceiverVerifyError,
         msgs::{Check, Ciphertexts, Extend, SenderPayload},
-        Aes128Ctr, ReceiverConfig, ReceiverError, Rng, RngSeed, CSP, SSP,
+        Aes128Ctr, ReceiverConfig, ReceiverError, Rng, RngSeed, CSP,
     },
     msgs::Derandomize,
 };
@@ -20,6 +20,10 @@ use cipher::{KeyIvInit, StreamCipher};
 use rand::{thread_rng, Rng as _, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "debug-checks")]
+use crate::kos::msgs::DebugSample;
 
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
@@ -136,6 +140,17 @@ impl Receiver<state::Extension> {
     ///
     /// * `count` - The number of OTs to extend (must be a multiple of 64).
     pub fn extend(&mut self, count: usize) -> Result<Extend, ReceiverError> {
+        self.extend_with_rng(count, &mut thread_rng())
+    }
+
+    /// Perform the IKNP OT extension, sampling the random choices from the provided rng.
+    ///
+    /// See [`Receiver::extend`].
+    pub(crate) fn extend_with_rng<R: RngCore + ?Sized>(
+        &mut self,
+        count: usize,
+        rng: &mut R,
+    ) -> Result<Extend, ReceiverError> {
         if self.state.extended {
             return Err(ReceiverError::InvalidState(
                 "extending more than once is currently disabled".to_string(),
@@ -149,7 +164,6 @@ impl Receiver<state::Extension> {
         const NROWS: usize = CSP;
         let row_width = count / 8;
 
-        let mut rng = thread_rng();
         // x₁,...,xₗ bits in Figure 3, step 1.
         let choices = (0..row_width)
             .flat_map(|_| rng.gen::<u8>().into_iter_lsb0())
@@ -202,6 +216,36 @@ impl Receiver<state::Extension> {
         Ok(Extend { us })
     }
 
+    /// Samples `count` of the unchecked OTs, so the sender can assert their correlations.
+    ///
+    /// The OTs are sampled from those which are sacrificed by [`check`](Self::check), but the
+    /// sample still reveals the receiver's choices for them, so this must never be used in
+    /// production.
+    #[cfg(feature = "debug-checks")]
+    pub fn debug_sample(&self, count: usize) -> DebugSample {
+        let len = self.state.unchecked_ts.len();
+        let start = len.saturating_sub(CSP + self.config.ssp());
+
+        let mut rng = thread_rng();
+        let indices: Vec<_> = if start < len {
+            (0..count).map(|_| rng.gen_range(start..len)).collect()
+        } else {
+            Vec::new()
+        };
+
+        DebugSample {
+            ts: indices
+                .iter()
+                .map(|&i| self.state.unchecked_ts[i])
+                .collect(),
+            choices: indices
+                .iter()
+                .map(|&i| self.state.unchecked_choices[i])
+                .collect(),
+            indices,
+        }
+    }
+
     /// Performs the correlation check for all outstanding OTS.
     ///
     /// See section 3.1 of the paper for more details.
@@ -222,9 +266,9 @@ impl Receiver<state::Extension> {
     /// * `chi_seed` - The seed used to generate the consistency check weights.
     pub fn check(&mut self, chi_seed: Block) -> Result<Check, ReceiverError> {
         // Make sure we have enough sacrificial OTs to perform the consistency check.
-        if self.state.unchecked_ts.len() < CSP + SSP {
+        if self.state.unchecked_ts.len() < CSP + self.config.ssp() {
             return Err(ReceiverError::InsufficientSetup(
-                CSP + SSP,
+                CSP + self.config.ssp(),
                 self.state.unchecked_ts.len(),
             ));
         }
@@ -279,7 +323,7 @@ impl Receiver<state::Extension> {
         }
 
         // Strip off the rows sacrificed for the consistency check.
-        let nrows = unchecked_ts.len() - (CSP + SSP);
+        let nrows = unchecked_ts.len() - (CSP + self.config.ssp());
         unchecked_ts.truncate(nrows);
         unchecked_choices.truncate(nrows);
 
@@ -416,6 +460,16 @@ impl Receiver<state::Verify> {
 /// Returned by the [`Receiver::keys`] method, used in cases where the receiver
 /// wishes to reserve a set of keys for a transfer, but hasn't yet received the
 /// payload.
+///
+/// # Persistence
+///
+/// The keys can be serialized to persist them between an offline and an online phase. The
+/// serialized keys reveal the receiver's choices, so they must be kept confidential. They must
+/// also only ever be used for a single transfer, as reusing them breaks the security of the OT.
+///
+/// The tape used for verifiable OT is not serialized, so transfers performed with restored keys
+/// are not recorded for verification.
+#[derive(Serialize, Deserialize)]
 pub struct ReceiverKeys {
     /// Transfer ID
     id: u32,
@@ -428,8 +482,10 @@ pub struct ReceiverKeys {
     choices: Vec<bool>,
 
     /// Receiver `ts`
+    #[serde(skip)]
     ts: Option<Vec<Block>>,
     /// Receiver tape
+    #[serde(skip)]
     tape: Option<Arc<Mutex<Tape>>>,
 }
 
@@ -441,6 +497,11 @@ impl ReceiverKeys {
         self.id
     }
 
+    /// Returns the decryption keys.
+    pub(crate) fn keys(&self) -> &[Block] {
+        &self.keys
+    }
+
     /// Derandomizes the receiver's choices.
     pub fn derandomize(&mut self, choices: &[bool]) -> Result<Derandomize, ReceiverError> {
         if choices.len() != self.choices.len() {
@@ -467,7 +528,21 @@ impl ReceiverKeys {
     }
 
     /// Decrypts the sender's payload.
-    pub fn decrypt_blocks(mut self, payload: SenderPayload) -> Result<Vec<Block>, ReceiverError> {
+    pub fn decrypt_blocks(self, payload: SenderPayload) -> Result<Vec<Block>, ReceiverError> {
+        let mut out = Vec::with_capacity(self.keys.len());
+        self.decrypt_blocks_into(payload, &mut out)?;
+
+        Ok(out)
+    }
+
+    /// Decrypts the sender's payload, writing the messages into the provided buffer.
+    ///
+    /// The buffer is cleared first, so its allocation is reused if it has sufficient capacity.
+    pub fn decrypt_blocks_into(
+        mut self,
+        payload: SenderPayload,
+        out: &mut Vec<Block>,
+    ) -> Result<(), ReceiverError> {
         let SenderPayload { id, ciphertexts } = payload;
 
         let Ciphertexts::Blocks { ciphertexts } = ciphertexts else {
@@ -507,13 +582,16 @@ impl ReceiverKeys {
             );
         }
 
-        Ok(self
-            .keys
-            .into_iter()
-            .zip(self.choices)
-            .zip(ciphertexts.chunks(2))
-            .map(|((key, c), ct)| if c { key ^ ct[1] } else { key ^ ct[0] })
-            .collect())
+        out.clear();
+        out.extend(
+            self.keys
+                .into_iter()
+                .zip(self.choices)
+                .zip(ciphertexts.chunks(2))
+                .map(|((key, c), ct)| if c { key ^ ct[1] } else { key ^ ct[0] }),
+        );
+
+        Ok(())
     }
 
     /// Decrypts the sender's payload.
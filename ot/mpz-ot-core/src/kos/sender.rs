@@ -2,7 +2,7 @@ use crate::{
     kos::{
         extension_matrix_size,
         msgs::{Check, Ciphertexts, Extend, SenderPayload},
-        Aes128Ctr, Rng, RngSeed, SenderConfig, SenderError, CSP, SSP,
+        Aes128Ctr, Rng, RngSeed, SenderConfig, SenderError, CSP,
     },
     msgs::Derandomize,
 };
@@ -10,11 +10,15 @@ use crate::{
 use cipher::{KeyIvInit, StreamCipher};
 use itybity::ToBits;
 use mpz_core::{aes::FIXED_KEY_AES, Block};
+use serde::{Deserialize, Serialize};
 
 use rand::{Rng as _, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 use rand_core::RngCore;
 
+#[cfg(feature = "debug-checks")]
+use crate::kos::msgs::DebugSample;
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "rayon")] {
         use itybity::ToParallelBits;
@@ -176,6 +180,47 @@ impl Sender<state::Extension> {
         Ok(())
     }
 
+    /// Checks that the receiver's sampled OTs are correlated with the unchecked OTs of the
+    /// sender, ie. that `q = t ^ (choice & delta)` holds for each of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SenderError::DebugCheckFailed`] if the sample is malformed or any of the
+    /// sampled correlations do not hold.
+    #[cfg(feature = "debug-checks")]
+    pub fn debug_check(&self, sample: &DebugSample) -> Result<(), SenderError> {
+        let DebugSample {
+            indices,
+            ts,
+            choices,
+        } = sample;
+
+        if indices.len() != ts.len() || indices.len() != choices.len() {
+            return Err(SenderError::DebugCheckFailed(
+                "malformed sample".to_string(),
+            ));
+        }
+
+        for ((&i, &t), &choice) in indices.iter().zip(ts).zip(choices) {
+            let Some(&q) = self.state.unchecked_qs.get(i) else {
+                return Err(SenderError::DebugCheckFailed(format!(
+                    "sampled index {i} out of range, {} unchecked OTs",
+                    self.state.unchecked_qs.len()
+                )));
+            };
+
+            let expected = if choice { t ^ self.state.delta } else { t };
+
+            if q != expected {
+                return Err(SenderError::DebugCheckFailed(format!(
+                    "correlation mismatch at unchecked index {i}, choice: {choice}"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Performs the correlation check for all outstanding OTS.
     ///
     /// See section 3.1 of the paper for more details.
@@ -197,9 +242,9 @@ impl Sender<state::Extension> {
     /// * `receiver_check` - The receiver's consistency check message.
     pub fn check(&mut self, chi_seed: Block, receiver_check: Check) -> Result<(), SenderError> {
         // Make sure we have enough sacrificial OTs to perform the consistency check.
-        if self.state.unchecked_qs.len() < CSP + SSP {
+        if self.state.unchecked_qs.len() < CSP + self.config.ssp() {
             return Err(SenderError::InsufficientSetup(
-                CSP + SSP,
+                CSP + self.config.ssp(),
                 self.state.unchecked_qs.len(),
             ));
         }
@@ -252,7 +297,7 @@ impl Sender<state::Extension> {
         }
 
         // Strip off the rows sacrificed for the consistency check.
-        let nrows = unchecked_qs.len() - (CSP + SSP);
+        let nrows = unchecked_qs.len() - (CSP + self.config.ssp());
         unchecked_qs.truncate(nrows);
 
         // Figure 7, "Randomization"
@@ -309,6 +354,14 @@ impl Sender<state::Extension> {
 /// Returned by the [`Sender::keys`] method, used in cases where the sender
 /// wishes to reserve a set of keys for use later, while still being able to process
 /// other payloads.
+///
+/// # Persistence
+///
+/// The keys can be serialized to persist them between an offline and an online phase. The
+/// serialized keys are as sensitive as the messages they will be used to encrypt, so they must be
+/// kept confidential. They must also only ever be used for a single transfer, as reusing them
+/// breaks the security of the OT.
+#[derive(Serialize, Deserialize)]
 pub struct SenderKeys {
     /// Transfer ID
     id: u32,
@@ -324,6 +377,11 @@ impl SenderKeys {
         self.id
     }
 
+    /// Returns the encryption keys.
+    pub(crate) fn keys(&self) -> &[[Block; 2]] {
+        &self.keys
+    }
+
     /// Applies Beaver derandomization to correct the receiver's choices made during extension.
     pub fn derandomize(&mut self, derandomize: Derandomize) -> Result<(), SenderError> {
         if derandomize.id != self.id {
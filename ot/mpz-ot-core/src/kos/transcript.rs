@@ -0,0 +1,149 @@
+//! Deterministic transcripts of the KOS15 protocol.
+//!
+//! These are intended for conformance testing against other implementations, which can replay
+//! the protocol using the same seeds and compare the intermediate values.
+
+use itybity::ToBits;
+use mpz_core::Block;
+use rand_core::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    kos::{
+        msgs::{Check, Extend, SenderPayload},
+        pad_ot_count, Receiver, ReceiverConfig, Rng, RngSeed, Sender, SenderConfig, CSP,
+    },
+    msgs::Derandomize,
+};
+
+/// A full transcript of a single KOS15 extension and transfer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KosTranscript {
+    /// The sender's base OT choice bits.
+    pub delta: Block,
+    /// The receiver's base OT seeds.
+    pub receiver_seeds: Vec<[Block; 2]>,
+    /// The sender's base OT seeds, chosen by `delta`.
+    pub sender_seeds: Vec<Block>,
+    /// The seed used by the receiver to sample its random choices.
+    pub choices_seed: Block,
+    /// The seed used to generate the consistency check weights.
+    pub chi_seed: Block,
+    /// The number of OTs extended, including the OTs sacrificed for the consistency check.
+    pub count: usize,
+    /// The receiver's extension message, ie. the extension matrix.
+    pub extend: Extend,
+    /// The receiver's consistency check message.
+    pub check: Check,
+    /// The sender's random OT keys.
+    pub sender_keys: Vec<[Block; 2]>,
+    /// The receiver's random OT keys.
+    pub receiver_keys: Vec<Block>,
+    /// The receiver's derandomization message.
+    pub derandomize: Derandomize,
+    /// The sender's encrypted payload.
+    pub payload: SenderPayload,
+    /// The messages received by the receiver.
+    pub received: Vec<Block>,
+}
+
+/// Runs the KOS15 protocol with fixed seeds and returns a transcript of all the intermediate values.
+///
+/// # Arguments
+///
+/// * `delta` - The sender's base OT choice bits.
+/// * `receiver_seeds` - The receiver's base OT seeds.
+/// * `choices_seed` - The seed used by the receiver to sample its random choices.
+/// * `chi_seed` - The seed used to generate the consistency check weights.
+/// * `choices` - The receiver's choices.
+/// * `data` - The sender's messages.
+///
+/// # Panics
+///
+/// Panics if `choices` and `data` do not have the same length.
+pub fn dump_transcript(
+    delta: Block,
+    receiver_seeds: [[Block; 2]; CSP],
+    choices_seed: Block,
+    chi_seed: Block,
+    choices: &[bool],
+    data: &[[Block; 2]],
+) -> KosTranscript {
+    assert_eq!(
+        choices.len(),
+        data.len(),
+        "choices and data must have the same length"
+    );
+
+    let sender_seeds: [Block; CSP] = delta
+        .iter_lsb0()
+        .zip(receiver_seeds)
+        .map(|(b, seeds)| if b { seeds[1] } else { seeds[0] })
+        .collect::<Vec<_>>()
+        .try_into()
+        .expect("delta has CSP bits");
+
+    let mut sender = Sender::new(SenderConfig::default()).setup(delta, sender_seeds);
+    let mut receiver = Receiver::new(ReceiverConfig::default()).setup(receiver_seeds);
+
+    // Stretch the Block-sized seed to a 32-byte seed.
+    let mut seed = RngSeed::default();
+    seed.iter_mut()
+        .zip(choices_seed.to_bytes().into_iter().cycle())
+        .for_each(|(s, c)| *s = c);
+    let mut rng = Rng::from_seed(seed);
+
+    let count = pad_ot_count(choices.len());
+
+    let extend = receiver
+        .extend_with_rng(count, &mut rng)
+        .expect("count is a multiple of 64");
+    sender
+        .extend(count, extend.clone())
+        .expect("extension message is well formed");
+
+    let check = receiver
+        .check(chi_seed)
+        .expect("enough OTs are extended for the check");
+    sender
+        .check(chi_seed, check.clone())
+        .expect("receiver is honest");
+
+    let mut receiver_keys = receiver
+        .keys(choices.len())
+        .expect("enough OTs are extended");
+    let mut sender_keys = sender.keys(data.len()).expect("enough OTs are extended");
+
+    let receiver_keys_ = receiver_keys.keys().to_vec();
+    let sender_keys_ = sender_keys.keys().to_vec();
+
+    let derandomize = receiver_keys
+        .derandomize(choices)
+        .expect("choices have the correct length");
+    sender_keys
+        .derandomize(derandomize.clone())
+        .expect("derandomization is consistent");
+
+    let payload = sender_keys
+        .encrypt_blocks(data)
+        .expect("data has the correct length");
+    let received = receiver_keys
+        .decrypt_blocks(payload.clone())
+        .expect("payload is well formed");
+
+    KosTranscript {
+        delta,
+        receiver_seeds: receiver_seeds.to_vec(),
+        sender_seeds: sender_seeds.to_vec(),
+        choices_seed,
+        chi_seed,
+        count,
+        extend,
+        check,
+        sender_keys: sender_keys_,
+        receiver_keys: receiver_keys_,
+        derandomize,
+        payload,
+        received,
+    }
+}
@@ -10,6 +10,8 @@ use crate::{
 pub enum SenderActorError {
     #[error(transparent)]
     Io(#[from] std::io::Error),
+    #[error("peer aborted the session")]
+    PeerAbort,
     #[error(transparent)]
     SenderError(#[from] SenderError),
     #[error("actor channel error: {0}")]
@@ -79,6 +81,8 @@ impl From<futures::channel::oneshot::Canceled> for SenderError {
 pub enum ReceiverActorError {
     #[error(transparent)]
     Io(#[from] std::io::Error),
+    #[error("peer aborted the session")]
+    PeerAbort,
     #[error(transparent)]
     ReceiverError(#[from] ReceiverError),
     #[error("received unexpected transfer id: {0}")]
@@ -3,7 +3,12 @@ pub mod msgs;
 mod receiver;
 mod sender;
 
-use futures::{SinkExt, StreamExt};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{task::noop_waker_ref, Sink, SinkExt, StreamExt};
 use utils_aio::{sink::IoSink, stream::IoStream};
 
 use crate::kos::msgs::Message as KosMessage;
@@ -12,6 +17,25 @@ pub use error::{ReceiverActorError, SenderActorError};
 pub use receiver::{ReceiverActor, SharedReceiver};
 pub use sender::{SenderActor, SharedSender};
 
+/// Attempts to send a message without blocking.
+///
+/// This is best-effort, the message is dropped if the sink is not immediately ready
+/// to accept it. Returns `true` if the message was sent.
+pub(crate) fn try_send<Si: IoSink<T> + Unpin, T>(sink: &mut Si, msg: T) -> bool {
+    let mut cx = Context::from_waker(noop_waker_ref());
+    let mut sink = Pin::new(sink);
+
+    if !matches!(sink.as_mut().poll_ready(&mut cx), Poll::Ready(Ok(()))) {
+        return false;
+    }
+
+    if sink.as_mut().start_send(msg).is_err() {
+        return false;
+    }
+
+    !matches!(sink.poll_flush(&mut cx), Poll::Ready(Err(_)))
+}
+
 /// Converts a sink of KOS actor messages into a sink of KOS messages.
 pub(crate) fn into_kos_sink<'a, Si: IoSink<msgs::Message<T>> + Send + Unpin, T: Send + 'a>(
     sink: &'a mut Si,
@@ -147,6 +171,21 @@ mod tests {
         assert_eq!(received_data, expected_data);
     }
 
+    #[tokio::test]
+    async fn test_kos_actor_drop_aborts() {
+        let (sender_actor, mut receiver_actor) =
+            setup(SenderConfig::default(), ReceiverConfig::default(), 128).await;
+
+        let receiver_task = tokio::spawn(async move { receiver_actor.run().await });
+
+        // Drop the sender mid-session.
+        drop(sender_actor);
+
+        let err = receiver_task.await.unwrap().unwrap_err();
+
+        assert!(matches!(err, ReceiverActorError::PeerAbort));
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_kos_actor_verifiable_receiver(data: Vec<[Block; 2]>, choices: Vec<bool>) {
@@ -36,6 +36,7 @@ pub enum ActorMessage {
     TransferRequest(TransferRequest),
     TransferPayload(TransferPayload),
     Reveal,
+    Abort,
 }
 
 /// A message indicating that a transfer with the provided id is expected.
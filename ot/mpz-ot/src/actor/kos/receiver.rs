@@ -22,7 +22,7 @@ use mpz_ot_core::kos::{msgs::SenderPayload, PayloadRecord};
 use crate::actor::kos::{
     into_kos_sink, into_kos_stream,
     msgs::{ActorMessage, Message, TransferPayload, TransferRequest},
-    ReceiverActorError,
+    try_send, ReceiverActorError,
 };
 
 /// Commands that can be sent to a [`ReceiverActor`].
@@ -70,7 +70,11 @@ struct State {
 }
 
 /// KOS receiver actor.
-pub struct ReceiverActor<BaseOT, Si, St> {
+pub struct ReceiverActor<BaseOT, Si, St>
+where
+    BaseOT: ProtocolMessage,
+    Si: IoSink<Message<BaseOT::Msg>> + Unpin,
+{
     /// A sink to send messages to the KOS sender actor.
     sink: Si,
     /// A stream to receive messages from the KOS sender actor.
@@ -84,6 +88,9 @@ pub struct ReceiverActor<BaseOT, Si, St> {
     command_sender: mpsc::UnboundedSender<Command>,
     /// Used to receive commands to this actor.
     commands: mpsc::UnboundedReceiver<Command>,
+
+    /// Whether the actor was shut down gracefully.
+    complete: bool,
 }
 
 impl<BaseOT, Si, St> ReceiverActor<BaseOT, Si, St>
@@ -104,6 +111,7 @@ where
             state: Default::default(),
             command_sender,
             commands,
+            complete: false,
         }
     }
 
@@ -135,6 +143,7 @@ where
                 cmd = self.commands.select_next_some() => {
                     if let Command::Shutdown(Shutdown { caller_response }) = cmd {
                         _ = caller_response.send(Ok(()));
+                        self.complete = true;
                         return Ok(());
                     }
 
@@ -280,6 +289,7 @@ where
             ActorMessage::Reveal => {
                 self.start_verification().await?;
             }
+            ActorMessage::Abort => return Err(ReceiverActorError::PeerAbort),
             msg => {
                 return Err(std::io::Error::new(
                     std::io::ErrorKind::InvalidData,
@@ -292,6 +302,22 @@ where
     }
 }
 
+impl<BaseOT, Si, St> Drop for ReceiverActor<BaseOT, Si, St>
+where
+    BaseOT: ProtocolMessage,
+    Si: IoSink<Message<BaseOT::Msg>> + Unpin,
+{
+    /// Notifies the peer if the actor is dropped before being shut down.
+    ///
+    /// This is best-effort, the abort message is only sent if the sink is ready
+    /// to accept it without blocking.
+    fn drop(&mut self) {
+        if !self.complete {
+            _ = try_send(&mut self.sink, ActorMessage::Abort.into());
+        }
+    }
+}
+
 /// KOS Shared Receiver controller.
 #[derive(Debug, Clone)]
 pub struct SharedReceiver {
@@ -15,6 +15,7 @@ use crate::{
     actor::kos::{
         into_kos_sink, into_kos_stream,
         msgs::{ActorMessage, Message, TransferPayload, TransferRequest},
+        try_send,
     },
     kos::{Sender, SenderError, SenderKeys},
     CommittedOTReceiver, CommittedOTSenderShared, OTError, OTReceiver, OTSenderShared, OTSetup,
@@ -56,7 +57,11 @@ struct State {
 opaque_debug::implement!(State);
 
 /// KOS sender actor.
-pub struct SenderActor<BaseOT, Si, St> {
+pub struct SenderActor<BaseOT, Si, St>
+where
+    BaseOT: ProtocolMessage,
+    Si: IoSink<Message<BaseOT::Msg>> + Unpin,
+{
     /// A sink to send messages to the KOS receiver actor.
     sink: Si,
     /// A stream to receive messages from the KOS receiver actor.
@@ -70,6 +75,9 @@ pub struct SenderActor<BaseOT, Si, St> {
     command_sender: mpsc::UnboundedSender<Command>,
     /// Used to receive commands to this actor.
     commands: mpsc::UnboundedReceiver<Command>,
+
+    /// Whether the actor was shut down gracefully.
+    complete: bool,
 }
 
 impl<BaseOT, Si, St> SenderActor<BaseOT, Si, St>
@@ -88,6 +96,7 @@ where
             state: Default::default(),
             command_sender: buffer_sender,
             commands: buffer_receiver,
+            complete: false,
         }
     }
 
@@ -138,6 +147,7 @@ where
                 cmd = self.commands.select_next_some() => {
                     if let Command::Shutdown(Shutdown { caller_response }) = cmd {
                         _ = caller_response.send(Ok(()));
+                        self.complete = true;
                         return Ok(());
                     }
 
@@ -209,6 +219,7 @@ where
                     self.state.pending_keys.insert(id, keys);
                 }
             }
+            ActorMessage::Abort => return Err(SenderActorError::PeerAbort),
             msg => {
                 return Err(std::io::Error::new(
                     std::io::ErrorKind::InvalidData,
@@ -245,6 +256,22 @@ where
     }
 }
 
+impl<BaseOT, Si, St> Drop for SenderActor<BaseOT, Si, St>
+where
+    BaseOT: ProtocolMessage,
+    Si: IoSink<Message<BaseOT::Msg>> + Unpin,
+{
+    /// Notifies the peer if the actor is dropped before being shut down.
+    ///
+    /// This is best-effort, the abort message is only sent if the sink is ready
+    /// to accept it without blocking.
+    fn drop(&mut self) {
+        if !self.complete {
+            _ = try_send(&mut self.sink, ActorMessage::Abort.into());
+        }
+    }
+}
+
 /// KOS Shared Sender controller
 #[derive(Clone)]
 pub struct SharedSender {
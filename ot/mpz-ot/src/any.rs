@@ -0,0 +1,415 @@
+//! Oblivious transfer backends which can be selected at runtime.
+
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use futures_util::{SinkExt, StreamExt};
+use mpz_core::{Block, ProtocolMessage};
+use utils_aio::{sink::IoSink, stream::IoStream};
+
+use crate::{chou_orlandi, kos, OTError, OTReceiver, OTSender, OTSetup};
+
+/// An oblivious transfer backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtBackend {
+    /// The Chou-Orlandi base OT protocol.
+    ChouOrlandi,
+    /// The KOS15 OT extension protocol, using Chou-Orlandi as the base OT.
+    Kos,
+}
+
+/// A message of any of the [`OtBackend`]s.
+#[derive(Debug, Clone, EnumTryAsInner)]
+#[derive_err(Debug)]
+#[allow(missing_docs)]
+pub enum AnyOtMessage {
+    ChouOrlandi(chou_orlandi::msgs::Message),
+    Kos(kos::msgs::Message<chou_orlandi::msgs::Message>),
+}
+
+impl From<AnyOtMessageError> for std::io::Error {
+    fn from(err: AnyOtMessageError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string())
+    }
+}
+
+/// An oblivious transfer sender which dispatches to the selected [`OtBackend`].
+///
+/// For [`OtBackend::Kos`], the OTs must be extended once with
+/// [`AnyOtSender::extend`] after setup, and each transfer consumes the OTs it uses.
+#[derive(Debug)]
+#[allow(missing_docs)]
+pub enum AnyOtSender {
+    ChouOrlandi(chou_orlandi::Sender),
+    Kos(kos::Sender<chou_orlandi::Receiver>),
+}
+
+impl AnyOtSender {
+    /// Creates a new sender for the given backend, using the default configurations.
+    ///
+    /// # Arguments
+    ///
+    /// * `backend` - The backend to use.
+    pub fn new(backend: OtBackend) -> Self {
+        match backend {
+            OtBackend::ChouOrlandi => Self::ChouOrlandi(chou_orlandi::Sender::new(
+                chou_orlandi::SenderConfig::default(),
+            )),
+            OtBackend::Kos => Self::Kos(kos::Sender::new(
+                kos::SenderConfig::default(),
+                chou_orlandi::Receiver::new(chou_orlandi::ReceiverConfig::default()),
+            )),
+        }
+    }
+
+    /// Returns the backend of the sender.
+    pub fn backend(&self) -> OtBackend {
+        match self {
+            Self::ChouOrlandi(_) => OtBackend::ChouOrlandi,
+            Self::Kos(_) => OtBackend::Kos,
+        }
+    }
+
+    /// Extends the OTs available for transfer.
+    ///
+    /// For [`OtBackend::Kos`] this performs the OT extension, which can only be done once.
+    /// For [`OtBackend::ChouOrlandi`] this does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The IO sink to the receiver.
+    /// * `stream` - The IO stream from the receiver.
+    /// * `count` - The total number of OTs to extend.
+    pub async fn extend<
+        Si: IoSink<AnyOtMessage> + Send + Unpin,
+        St: IoStream<AnyOtMessage> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        count: usize,
+    ) -> Result<(), OTError> {
+        match self {
+            Self::ChouOrlandi(_) => Ok(()),
+            Self::Kos(sender) => {
+                sender
+                    .extend(
+                        &mut into_backend_sink(sink, AnyOtMessage::Kos),
+                        &mut into_backend_stream(stream, AnyOtMessage::try_into_kos),
+                        count,
+                    )
+                    .await?;
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// An oblivious transfer receiver which dispatches to the selected [`OtBackend`].
+///
+/// For [`OtBackend::Kos`], the OTs must be extended once with
+/// [`AnyOtReceiver::extend`] after setup, and each transfer consumes the OTs it uses.
+#[derive(Debug)]
+#[allow(missing_docs)]
+pub enum AnyOtReceiver {
+    ChouOrlandi(chou_orlandi::Receiver),
+    Kos(kos::Receiver<chou_orlandi::Sender>),
+}
+
+impl AnyOtReceiver {
+    /// Creates a new receiver for the given backend, using the default configurations.
+    ///
+    /// # Arguments
+    ///
+    /// * `backend` - The backend to use.
+    pub fn new(backend: OtBackend) -> Self {
+        match backend {
+            OtBackend::ChouOrlandi => Self::ChouOrlandi(chou_orlandi::Receiver::new(
+                chou_orlandi::ReceiverConfig::default(),
+            )),
+            OtBackend::Kos => Self::Kos(kos::Receiver::new(
+                kos::ReceiverConfig::default(),
+                chou_orlandi::Sender::new(chou_orlandi::SenderConfig::default()),
+            )),
+        }
+    }
+
+    /// Returns the backend of the receiver.
+    pub fn backend(&self) -> OtBackend {
+        match self {
+            Self::ChouOrlandi(_) => OtBackend::ChouOrlandi,
+            Self::Kos(_) => OtBackend::Kos,
+        }
+    }
+
+    /// Extends the OTs available for transfer.
+    ///
+    /// For [`OtBackend::Kos`] this performs the OT extension, which can only be done once.
+    /// For [`OtBackend::ChouOrlandi`] this does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The IO sink to the sender.
+    /// * `stream` - The IO stream from the sender.
+    /// * `count` - The total number of OTs to extend.
+    pub async fn extend<
+        Si: IoSink<AnyOtMessage> + Send + Unpin,
+        St: IoStream<AnyOtMessage> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        count: usize,
+    ) -> Result<(), OTError> {
+        match self {
+            Self::ChouOrlandi(_) => Ok(()),
+            Self::Kos(receiver) => {
+                receiver
+                    .extend(
+                        &mut into_backend_sink(sink, AnyOtMessage::Kos),
+                        &mut into_backend_stream(stream, AnyOtMessage::try_into_kos),
+                        count,
+                    )
+                    .await?;
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Converts a sink of [`AnyOtMessage`]s into a sink of backend messages.
+fn into_backend_sink<'a, Si: IoSink<AnyOtMessage> + Send + Unpin, T: Send + 'a>(
+    sink: &'a mut Si,
+    wrap: fn(T) -> AnyOtMessage,
+) -> impl IoSink<T> + Send + Unpin + 'a {
+    Box::pin(SinkExt::with(sink, move |msg| async move { Ok(wrap(msg)) }))
+}
+
+/// Converts a stream of [`AnyOtMessage`]s into a stream of backend messages.
+fn into_backend_stream<'a, St: IoStream<AnyOtMessage> + Send + Unpin, T: Send + 'a>(
+    stream: &'a mut St,
+    unwrap: fn(AnyOtMessage) -> Result<T, AnyOtMessageError>,
+) -> impl IoStream<T> + Send + Unpin + 'a {
+    StreamExt::map(stream, move |msg| match msg {
+        Ok(msg) => unwrap(msg).map_err(From::from),
+        Err(err) => Err(err),
+    })
+}
+
+impl ProtocolMessage for AnyOtSender {
+    type Msg = AnyOtMessage;
+}
+
+impl ProtocolMessage for AnyOtReceiver {
+    type Msg = AnyOtMessage;
+}
+
+#[async_trait]
+impl OTSetup for AnyOtSender {
+    async fn setup<
+        Si: IoSink<AnyOtMessage> + Send + Unpin,
+        St: IoStream<AnyOtMessage> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), OTError> {
+        match self {
+            Self::ChouOrlandi(sender) => {
+                sender
+                    .setup(
+                        &mut into_backend_sink(sink, AnyOtMessage::ChouOrlandi),
+                        &mut into_backend_stream(stream, AnyOtMessage::try_into_chou_orlandi),
+                    )
+                    .await
+            }
+            Self::Kos(sender) => {
+                sender
+                    .setup(
+                        &mut into_backend_sink(sink, AnyOtMessage::Kos),
+                        &mut into_backend_stream(stream, AnyOtMessage::try_into_kos),
+                    )
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl OTSender<[Block; 2]> for AnyOtSender {
+    async fn send<
+        Si: IoSink<AnyOtMessage> + Send + Unpin,
+        St: IoStream<AnyOtMessage> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        msgs: &[[Block; 2]],
+    ) -> Result<(), OTError> {
+        match self {
+            Self::ChouOrlandi(sender) => {
+                sender
+                    .send(
+                        &mut into_backend_sink(sink, AnyOtMessage::ChouOrlandi),
+                        &mut into_backend_stream(stream, AnyOtMessage::try_into_chou_orlandi),
+                        msgs,
+                    )
+                    .await
+            }
+            Self::Kos(sender) => {
+                sender
+                    .send(
+                        &mut into_backend_sink(sink, AnyOtMessage::Kos),
+                        &mut into_backend_stream(stream, AnyOtMessage::try_into_kos),
+                        msgs,
+                    )
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl OTSetup for AnyOtReceiver {
+    async fn setup<
+        Si: IoSink<AnyOtMessage> + Send + Unpin,
+        St: IoStream<AnyOtMessage> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), OTError> {
+        match self {
+            Self::ChouOrlandi(receiver) => {
+                receiver
+                    .setup(
+                        &mut into_backend_sink(sink, AnyOtMessage::ChouOrlandi),
+                        &mut into_backend_stream(stream, AnyOtMessage::try_into_chou_orlandi),
+                    )
+                    .await
+            }
+            Self::Kos(receiver) => {
+                receiver
+                    .setup(
+                        &mut into_backend_sink(sink, AnyOtMessage::Kos),
+                        &mut into_backend_stream(stream, AnyOtMessage::try_into_kos),
+                    )
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl OTReceiver<bool, Block> for AnyOtReceiver {
+    async fn receive<
+        Si: IoSink<AnyOtMessage> + Send + Unpin,
+        St: IoStream<AnyOtMessage> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        choices: &[bool],
+    ) -> Result<Vec<Block>, OTError> {
+        match self {
+            Self::ChouOrlandi(receiver) => {
+                receiver
+                    .receive(
+                        &mut into_backend_sink(sink, AnyOtMessage::ChouOrlandi),
+                        &mut into_backend_stream(stream, AnyOtMessage::try_into_chou_orlandi),
+                        choices,
+                    )
+                    .await
+            }
+            Self::Kos(receiver) => {
+                receiver
+                    .receive(
+                        &mut into_backend_sink(sink, AnyOtMessage::Kos),
+                        &mut into_backend_stream(stream, AnyOtMessage::try_into_kos),
+                        choices,
+                    )
+                    .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::Rng;
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+    use utils_aio::duplex::MemoryDuplex;
+
+    async fn transfer(
+        backend: OtBackend,
+        data: &[[Block; 2]],
+        choices: &[bool],
+    ) -> Vec<Vec<Block>> {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let mut sender = AnyOtSender::new(backend);
+        let mut receiver = AnyOtReceiver::new(backend);
+
+        assert_eq!(sender.backend(), backend);
+        assert_eq!(receiver.backend(), backend);
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.setup(&mut sender_sink, &mut sender_stream),
+            receiver.setup(&mut receiver_sink, &mut receiver_stream)
+        );
+
+        sender_res.unwrap();
+        receiver_res.unwrap();
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.extend(&mut sender_sink, &mut sender_stream, data.len()),
+            receiver.extend(&mut receiver_sink, &mut receiver_stream, choices.len())
+        );
+
+        sender_res.unwrap();
+        receiver_res.unwrap();
+
+        // Transfers in two batches, which both consume from the same extension.
+        let mut received = Vec::new();
+        for (data, choices) in data.chunks(64).zip(choices.chunks(64)) {
+            let (sender_res, receiver_res) = tokio::join!(
+                sender.send(&mut sender_sink, &mut sender_stream, data),
+                receiver.receive(&mut receiver_sink, &mut receiver_stream, choices)
+            );
+
+            sender_res.unwrap();
+            received.push(receiver_res.unwrap());
+        }
+
+        received
+    }
+
+    #[tokio::test]
+    async fn test_any_ot_backends() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let data: Vec<[Block; 2]> = (0..128)
+            .map(|_| [rng.gen::<[u8; 16]>().into(), rng.gen::<[u8; 16]>().into()])
+            .collect();
+        let choices: Vec<bool> = (0..128).map(|_| rng.gen()).collect();
+
+        let expected: Vec<Block> = data
+            .iter()
+            .zip(&choices)
+            .map(|([zero, one], choice)| if *choice { *one } else { *zero })
+            .collect();
+
+        let co = transfer(OtBackend::ChouOrlandi, &data, &choices).await;
+        let kos = transfer(OtBackend::Kos, &data, &choices).await;
+
+        assert_eq!(co.len(), 2);
+        assert_eq!(co.concat(), expected);
+        assert_eq!(kos, co);
+    }
+}
@@ -108,6 +108,9 @@ mod error;
 mod receiver;
 mod sender;
 
+/// The number of OTs processed between progress reports.
+pub(crate) const PROGRESS_CHUNK_SIZE: usize = 128;
+
 pub use error::{ReceiverError, SenderError};
 pub use receiver::Receiver;
 pub use sender::Sender;
@@ -128,7 +131,10 @@ mod tests {
     use rand_core::SeedableRng;
     use utils_aio::{duplex::MemoryDuplex, sink::IoSink, stream::IoStream};
 
-    use crate::{CommittedOTReceiver, OTReceiver, OTSender, OTSetup, VerifiableOTSender};
+    use crate::{
+        tests::FlushOnlySink, CommittedOTReceiver, OTReceiver, OTSender, OTSetup,
+        VerifiableOTSender,
+    };
 
     use super::*;
     use rstest::*;
@@ -208,6 +214,49 @@ mod tests {
         assert_eq!(received, expected);
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_chou_orlandi_flush(data: Vec<[Block; 2]>, choices: Vec<bool>) {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (sender_sink, mut sender_stream) = sender_channel.split();
+        let (receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        // Messages are only delivered once the sinks are flushed.
+        let mut sender_sink = FlushOnlySink::new(sender_sink);
+        let mut receiver_sink = FlushOnlySink::new(receiver_sink);
+
+        let (mut sender, mut receiver) = setup(
+            SenderConfig::builder().receiver_commit().build().unwrap(),
+            ReceiverConfig::builder().receiver_commit().build().unwrap(),
+            &mut sender_sink,
+            &mut sender_stream,
+            &mut receiver_sink,
+            &mut receiver_stream,
+        )
+        .await;
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send(&mut sender_sink, &mut sender_stream, &data),
+            receiver.receive(&mut receiver_sink, &mut receiver_stream, &choices)
+        );
+
+        sender_res.unwrap();
+        let received = receiver_res.unwrap();
+
+        let expected = choose(data.iter().copied(), choices.iter_lsb0()).collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.verify_choices(&mut sender_sink, &mut sender_stream),
+            receiver.reveal_choices(&mut receiver_sink, &mut receiver_stream)
+        );
+
+        assert_eq!(sender_res.unwrap(), choices);
+        receiver_res.unwrap();
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_chou_orlandi_committed_receiver(data: Vec<[Block; 2]>, choices: Vec<bool>) {
@@ -244,4 +293,129 @@ mod tests {
 
         assert_eq!(verified_choices, choices);
     }
+
+    #[tokio::test]
+    async fn test_chou_orlandi_progress() {
+        let count = PROGRESS_CHUNK_SIZE * 2 + 3;
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let data: Vec<[Block; 2]> = (0..count)
+            .map(|_| [rng.gen::<[u8; 16]>().into(), rng.gen::<[u8; 16]>().into()])
+            .collect();
+        let choices: Vec<bool> = (0..count).map(|_| rng.gen()).collect();
+
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let (mut sender, mut receiver) = setup(
+            SenderConfig::default(),
+            ReceiverConfig::default(),
+            &mut sender_sink,
+            &mut sender_stream,
+            &mut receiver_sink,
+            &mut receiver_stream,
+        )
+        .await;
+
+        let mut sender_progress = Vec::new();
+        let mut receiver_progress = Vec::new();
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send_with_progress(
+                &mut sender_sink,
+                &mut sender_stream,
+                &data,
+                |done, total| { sender_progress.push((done, total)) }
+            ),
+            receiver.receive_with_progress(
+                &mut receiver_sink,
+                &mut receiver_stream,
+                &choices,
+                |done, total| receiver_progress.push((done, total))
+            )
+        );
+
+        sender_res.unwrap();
+        let received = receiver_res.unwrap();
+
+        let expected = choose(data.iter().copied(), choices.iter().copied()).collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+        assert_eq!(
+            sender_progress,
+            vec![
+                (PROGRESS_CHUNK_SIZE, count),
+                (PROGRESS_CHUNK_SIZE * 2, count),
+                (count, count)
+            ]
+        );
+        assert_eq!(receiver_progress, sender_progress);
+    }
+
+    #[tokio::test]
+    async fn test_chou_orlandi_progress_mixed() {
+        let count = PROGRESS_CHUNK_SIZE + 3;
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let data: Vec<[Block; 2]> = (0..count)
+            .map(|_| [rng.gen::<[u8; 16]>().into(), rng.gen::<[u8; 16]>().into()])
+            .collect();
+        let choices: Vec<bool> = (0..count).map(|_| rng.gen()).collect();
+        let expected = choose(data.iter().copied(), choices.iter().copied()).collect::<Vec<_>>();
+
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let (mut sender, mut receiver) = setup(
+            SenderConfig::default(),
+            ReceiverConfig::default(),
+            &mut sender_sink,
+            &mut sender_stream,
+            &mut receiver_sink,
+            &mut receiver_stream,
+        )
+        .await;
+
+        let mut sender_progress = Vec::new();
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send_with_progress(
+                &mut sender_sink,
+                &mut sender_stream,
+                &data,
+                |done, total| sender_progress.push((done, total))
+            ),
+            receiver.receive(&mut receiver_sink, &mut receiver_stream, &choices)
+        );
+
+        sender_res.unwrap();
+        let received: Vec<Block> = receiver_res.unwrap();
+
+        assert_eq!(received, expected);
+        assert_eq!(
+            sender_progress,
+            vec![(PROGRESS_CHUNK_SIZE, count), (count, count)]
+        );
+
+        let mut receiver_progress = Vec::new();
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send(&mut sender_sink, &mut sender_stream, &data),
+            receiver.receive_with_progress(
+                &mut receiver_sink,
+                &mut receiver_stream,
+                &choices,
+                |done, total| receiver_progress.push((done, total))
+            )
+        );
+
+        sender_res.unwrap();
+        let received = receiver_res.unwrap();
+
+        assert_eq!(received, expected);
+        assert_eq!(
+            receiver_progress,
+            vec![(PROGRESS_CHUNK_SIZE, count), (count, count)]
+        );
+    }
 }
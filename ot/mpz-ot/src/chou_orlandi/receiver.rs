@@ -4,7 +4,8 @@ use futures::SinkExt;
 use itybity::BitIterable;
 use mpz_core::{cointoss, Block, ProtocolMessage};
 use mpz_ot_core::chou_orlandi::{
-    msgs::Message, receiver_state as state, Receiver as ReceiverCore, ReceiverConfig,
+    msgs::{Message, ReceiverPayload},
+    receiver_state as state, Receiver as ReceiverCore, ReceiverConfig,
 };
 
 use enum_try_as_inner::EnumTryAsInner;
@@ -17,7 +18,7 @@ use utils_aio::{
 
 use crate::{CommittedOTReceiver, OTError, OTReceiver, OTSetup};
 
-use super::ReceiverError;
+use super::{ReceiverError, PROGRESS_CHUNK_SIZE};
 
 #[derive(Debug, EnumTryAsInner)]
 #[derive_err(Debug)]
@@ -66,6 +67,73 @@ impl Receiver {
             cointoss_payload: None,
         }
     }
+
+    /// Obliviously receives the chosen messages from the sender, reporting progress as the
+    /// base OTs are processed.
+    ///
+    /// The blinded choices are computed in chunks, after each of which `progress` is invoked
+    /// with the number of OTs done so far and the total number of OTs. The messages exchanged
+    /// are the same as for [`receive`](OTReceiver::receive), so the sender may use either
+    /// [`Sender::send_with_progress`](super::Sender::send_with_progress) or
+    /// [`send`](crate::OTSender::send).
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to.
+    /// * `stream` - The stream to receive messages from.
+    /// * `choices` - The choice bits.
+    /// * `progress` - The progress callback.
+    pub async fn receive_with_progress<
+        Si: IoSink<Message> + Send + Unpin,
+        St: IoStream<Message> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        choices: &[bool],
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<Vec<Block>, OTError> {
+        let mut receiver = std::mem::replace(&mut self.state, State::Error)
+            .try_into_setup()
+            .map_err(ReceiverError::from)?;
+
+        let mut blinded_choices = Vec::with_capacity(choices.len());
+        for chunk in choices.chunks(PROGRESS_CHUNK_SIZE) {
+            let chunk = chunk.to_vec();
+            let payload;
+            (receiver, payload) = Backend::spawn(move || {
+                let payload = receiver.receive_random(&chunk);
+                (receiver, payload)
+            })
+            .await;
+
+            blinded_choices.extend(payload.blinded_choices);
+            progress(blinded_choices.len(), choices.len());
+        }
+
+        sink.send(Message::ReceiverPayload(ReceiverPayload {
+            blinded_choices,
+        }))
+        .await?;
+
+        let sender_payload = stream
+            .expect_next()
+            .await?
+            .try_into_sender_payload()
+            .map_err(ReceiverError::from)?;
+
+        let (receiver, data) = Backend::spawn(move || {
+            let data = receiver.receive(sender_payload);
+            (receiver, data)
+        })
+        .await;
+
+        let data = data.map_err(ReceiverError::from)?;
+
+        self.state = State::Setup(receiver);
+
+        Ok(data)
+    }
 }
 
 #[async_trait]
@@ -1,10 +1,14 @@
-use crate::{chou_orlandi::SenderError, OTError, OTSender, OTSetup, VerifiableOTSender};
+use crate::{
+    chou_orlandi::{SenderError, PROGRESS_CHUNK_SIZE},
+    OTError, OTSender, OTSetup, VerifiableOTSender,
+};
 
 use async_trait::async_trait;
 use futures_util::SinkExt;
 use mpz_core::{cointoss, Block, ProtocolMessage};
 use mpz_ot_core::chou_orlandi::{
-    msgs::Message, sender_state as state, Sender as SenderCore, SenderConfig,
+    msgs::{Message, ReceiverPayload, SenderPayload},
+    sender_state as state, Sender as SenderCore, SenderConfig, SenderError as SenderCoreError,
 };
 use rand::{thread_rng, Rng};
 use utils_aio::{
@@ -58,6 +62,76 @@ impl Sender {
             cointoss_receiver: None,
         }
     }
+
+    /// Obliviously transfers the messages to the receiver, reporting progress as the base OTs
+    /// are processed.
+    ///
+    /// The messages are encrypted in chunks, after each of which `progress` is invoked with
+    /// the number of OTs done so far and the total number of OTs. The messages exchanged are
+    /// the same as for [`send`](OTSender::send), so the receiver may use either
+    /// [`Receiver::receive_with_progress`](super::Receiver::receive_with_progress) or
+    /// [`receive`](crate::OTReceiver::receive).
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to.
+    /// * `stream` - The stream to receive messages from.
+    /// * `input` - The messages to transfer.
+    /// * `progress` - The progress callback.
+    pub async fn send_with_progress<
+        Si: IoSink<Message> + Send + Unpin,
+        St: IoStream<Message> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        input: &[[Block; 2]],
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<(), OTError> {
+        let mut sender = std::mem::replace(&mut self.state, State::Error)
+            .try_into_setup()
+            .map_err(SenderError::from)?;
+
+        let ReceiverPayload { blinded_choices } = stream
+            .expect_next()
+            .await?
+            .try_into_receiver_payload()
+            .map_err(SenderError::from)?;
+
+        if input.len() != blinded_choices.len() {
+            return Err(SenderError::from(SenderCoreError::CountMismatch(
+                input.len(),
+                blinded_choices.len(),
+            )))?;
+        }
+
+        let mut payload = Vec::with_capacity(input.len());
+        for (inputs, blinded_choices) in input
+            .chunks(PROGRESS_CHUNK_SIZE)
+            .zip(blinded_choices.chunks(PROGRESS_CHUNK_SIZE))
+        {
+            let inputs = inputs.to_vec();
+            let receiver_payload = ReceiverPayload {
+                blinded_choices: blinded_choices.to_vec(),
+            };
+            let chunk_payload;
+            (sender, chunk_payload) = Backend::spawn(move || {
+                let payload = sender.send(&inputs, receiver_payload);
+                (sender, payload)
+            })
+            .await;
+
+            payload.extend(chunk_payload.map_err(SenderError::from)?.payload);
+            progress(payload.len(), input.len());
+        }
+
+        sink.send(Message::SenderPayload(SenderPayload { payload }))
+            .await?;
+
+        self.state = State::Setup(sender);
+
+        Ok(())
+    }
 }
 
 #[async_trait]
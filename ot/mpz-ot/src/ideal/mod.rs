@@ -13,4 +13,5 @@ pub use shared::{
     ideal_random_ot_shared_pair, IdealSharedCOTReceiver, IdealSharedCOTSender,
     IdealSharedOTReceiver, IdealSharedOTSender, IdealSharedRandomCOTReceiver,
     IdealSharedRandomCOTSender, IdealSharedRandomOTReceiver, IdealSharedRandomOTSender,
+    RecordingIdealOT, Transfer,
 };
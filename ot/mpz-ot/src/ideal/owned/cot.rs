@@ -1,20 +1,33 @@
-use crate::{COTReceiver, COTSender, OTError, OTSetup};
+use crate::{COTReceiver, COTSender, OTError, OTSetup, RandomCOTReceiver, RandomCOTSender};
 use async_trait::async_trait;
 use futures::{channel::mpsc, StreamExt};
 use mpz_core::{Block, ProtocolMessage};
+use mpz_ot_core::ideal::ideal_cot::{CotMsgForReceiver, CotMsgForSender, IdealCOT};
 use utils_aio::{sink::IoSink, stream::IoStream};
 
+/// Payload sent from the ideal COT sender to the receiver.
+#[derive(Debug)]
+enum Payload<T> {
+    /// Correlated messages chosen by the sender.
+    Correlated(Vec<[T; 2]>),
+    /// Random correlated messages output by the ideal functionality.
+    Random(CotMsgForReceiver),
+}
+
 /// Ideal OT sender.
+///
+/// Random correlated OTs are produced by the [`IdealCOT`] functionality.
 #[derive(Debug)]
 pub struct IdealCOTSender<T = Block> {
-    sender: mpsc::Sender<Vec<[T; 2]>>,
+    sender: mpsc::Sender<Payload<T>>,
     delta: Block,
+    cot: IdealCOT,
 }
 
 /// Ideal OT receiver.
 #[derive(Debug)]
 pub struct IdealCOTReceiver<T = Block> {
-    receiver: mpsc::Receiver<Vec<[T; 2]>>,
+    receiver: mpsc::Receiver<Payload<T>>,
 }
 
 impl<T> ProtocolMessage for IdealCOTSender<T> {
@@ -32,7 +45,11 @@ pub fn ideal_cot_pair<T: Send + Sync + 'static>(
     let (sender, receiver) = mpsc::channel(10);
 
     (
-        IdealCOTSender { sender, delta },
+        IdealCOTSender {
+            sender,
+            delta,
+            cot: IdealCOT::new_with_delta(delta),
+        },
         IdealCOTReceiver { receiver },
     )
 }
@@ -60,17 +77,38 @@ impl COTSender<Block> for IdealCOTSender<Block> {
         msgs: &[Block],
     ) -> Result<(), OTError> {
         self.sender
-            .try_send(
+            .try_send(Payload::Correlated(
                 msgs.iter()
                     .map(|msg| [*msg, *msg ^ self.delta])
                     .collect::<Vec<_>>(),
-            )
+            ))
             .expect("IdealCOTSender should be able to send");
 
         Ok(())
     }
 }
 
+#[async_trait]
+impl RandomCOTSender<Block> for IdealCOTSender<Block> {
+    async fn send_random_correlated<
+        Si: IoSink<()> + Send + Unpin,
+        St: IoStream<()> + Send + Unpin,
+    >(
+        &mut self,
+        _sink: &mut Si,
+        _stream: &mut St,
+        count: usize,
+    ) -> Result<Vec<Block>, OTError> {
+        let (CotMsgForSender { qs }, receiver_msg) = self.cot.extend(count);
+
+        self.sender
+            .try_send(Payload::Random(receiver_msg))
+            .expect("IdealCOTSender should be able to send");
+
+        Ok(qs)
+    }
+}
+
 #[async_trait]
 impl<T> OTSetup for IdealCOTReceiver<T>
 where
@@ -93,11 +131,14 @@ impl COTReceiver<bool, Block> for IdealCOTReceiver<Block> {
         _stream: &mut St,
         choices: &[bool],
     ) -> Result<Vec<Block>, OTError> {
-        let payload = self
+        let Payload::Correlated(payload) = self
             .receiver
             .next()
             .await
-            .expect("IdealCOTSender should send a value");
+            .expect("IdealCOTSender should send a value")
+        else {
+            panic!("IdealCOTSender should send correlated messages");
+        };
 
         Ok(payload
             .into_iter()
@@ -114,6 +155,36 @@ impl COTReceiver<bool, Block> for IdealCOTReceiver<Block> {
     }
 }
 
+#[async_trait]
+impl RandomCOTReceiver<bool, Block> for IdealCOTReceiver<Block> {
+    async fn receive_random_correlated<
+        Si: IoSink<()> + Send + Unpin,
+        St: IoStream<()> + Send + Unpin,
+    >(
+        &mut self,
+        _sink: &mut Si,
+        _stream: &mut St,
+        count: usize,
+    ) -> Result<(Vec<bool>, Vec<Block>), OTError> {
+        let Payload::Random(CotMsgForReceiver { rs, ts }) = self
+            .receiver
+            .next()
+            .await
+            .expect("IdealCOTSender should send a value")
+        else {
+            panic!("IdealCOTSender should send random correlated messages");
+        };
+
+        assert_eq!(
+            rs.len(),
+            count,
+            "IdealCOTSender should send {count} messages"
+        );
+
+        Ok((rs, ts))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use itybity::IntoBits;
@@ -156,4 +227,35 @@ mod tests {
 
         assert_eq!(received, expected);
     }
+
+    // Test that the sender and receiver can be used to produce random correlated values
+    #[tokio::test]
+    async fn test_ideal_random_cot_owned() {
+        let (send_channel, recv_channel) = MemoryDuplex::<()>::new();
+
+        let (mut send_sink, mut send_stream) = send_channel.split();
+        let (mut recv_sink, mut recv_stream) = recv_channel.split();
+
+        let delta = Block::from([42u8; 16]);
+        let (mut sender, mut receiver) = ideal_cot_pair::<Block>(delta);
+
+        let low = sender
+            .send_random_correlated(&mut send_sink, &mut send_stream, 128)
+            .await
+            .unwrap();
+
+        let (choices, received) = receiver
+            .receive_random_correlated(&mut recv_sink, &mut recv_stream, 128)
+            .await
+            .unwrap();
+
+        assert_eq!(received.len(), 128);
+
+        // The receiver gets `m0` or `m1 = m0 ^ delta` according to its random choice.
+        for ((m0, c), received) in low.into_iter().zip(choices).zip(received) {
+            let expected = if c { m0 ^ delta } else { m0 };
+
+            assert_eq!(received, expected);
+        }
+    }
 }
@@ -72,6 +72,8 @@ impl<T> OTSender<[T; 2]> for IdealOTSender<T>
 where
     T: Send + Sync + Clone + 'static,
 {
+    const IS_IDEAL: bool = true;
+
     async fn send<Si: IoSink<()> + Send + Unpin, St: IoStream<()> + Send + Unpin>(
         &mut self,
         _sink: &mut Si,
@@ -107,6 +109,8 @@ impl<T> OTReceiver<bool, T> for IdealOTReceiver<T>
 where
     T: Send + Sync + 'static,
 {
+    const IS_IDEAL: bool = true;
+
     async fn receive<Si: IoSink<()> + Send + Unpin, St: IoStream<()> + Send + Unpin>(
         &mut self,
         _sink: &mut Si,
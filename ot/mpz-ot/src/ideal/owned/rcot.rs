@@ -140,6 +140,43 @@ impl RandomCOTReceiver<bool, Block> for IdealRandomCOTReceiver<Block> {
 
         Ok((choices, payload))
     }
+
+    async fn receive_random_correlated_packed<
+        Si: IoSink<()> + Send + Unpin,
+        St: IoStream<()> + Send + Unpin,
+    >(
+        &mut self,
+        _sink: &mut Si,
+        _stream: &mut St,
+        count: usize,
+    ) -> Result<(Vec<u8>, Vec<Block>), OTError> {
+        let payload = self
+            .receiver
+            .next()
+            .await
+            .expect("IdealRandomCOTSender should send a value");
+
+        assert_eq!(payload.len(), count);
+
+        // The choices are packed as they are sampled, in the same order as
+        // `receive_random_correlated`.
+        let mut choices = vec![0u8; count.div_ceil(8)];
+        let payload = payload
+            .into_iter()
+            .enumerate()
+            .map(|(i, [low, high])| {
+                let c: bool = self.rng.gen();
+                choices[i / 8] |= (c as u8) << (i % 8);
+                if c {
+                    high
+                } else {
+                    low
+                }
+            })
+            .collect();
+
+        Ok((choices, payload))
+    }
 }
 
 #[cfg(test)]
@@ -178,4 +215,45 @@ mod tests {
 
         assert_eq!(received, expected);
     }
+
+    #[tokio::test]
+    async fn test_ideal_random_cot_owned_packed() {
+        use itybity::IntoBitIterator;
+
+        let seed = [0u8; 32];
+        let delta = Block::from([42u8; 16]);
+        let count = 20;
+
+        let (mut sender, mut receiver) = ideal_random_cot_pair::<Block>(seed, delta);
+        let (send_channel, recv_channel) = MemoryDuplex::<()>::new();
+        let (mut send_sink, mut send_stream) = send_channel.split();
+        let (mut recv_sink, mut recv_stream) = recv_channel.split();
+
+        sender
+            .send_random_correlated(&mut send_sink, &mut send_stream, count)
+            .await
+            .unwrap();
+        let (choices, expected) = receiver
+            .receive_random_correlated(&mut recv_sink, &mut recv_stream, count)
+            .await
+            .unwrap();
+
+        let (mut sender, mut receiver) = ideal_random_cot_pair::<Block>(seed, delta);
+
+        sender
+            .send_random_correlated(&mut send_sink, &mut send_stream, count)
+            .await
+            .unwrap();
+        let (packed, received) = receiver
+            .receive_random_correlated_packed(&mut recv_sink, &mut recv_stream, count)
+            .await
+            .unwrap();
+
+        assert_eq!(packed.len(), count.div_ceil(8));
+        assert_eq!(
+            packed.into_iter_lsb0().take(count).collect::<Vec<_>>(),
+            choices
+        );
+        assert_eq!(received, expected);
+    }
 }
@@ -1,6 +1,7 @@
 mod cot;
 mod ot;
 mod rcot;
+mod recording;
 mod rot;
 
 pub use cot::{ideal_cot_shared_pair, IdealSharedCOTReceiver, IdealSharedCOTSender};
@@ -8,6 +9,7 @@ pub use ot::{ideal_ot_shared_pair, IdealSharedOTReceiver, IdealSharedOTSender};
 pub use rcot::{
     ideal_random_cot_shared_pair, IdealSharedRandomCOTReceiver, IdealSharedRandomCOTSender,
 };
+pub use recording::{RecordingIdealOT, Transfer};
 
 pub use rot::{
     ideal_random_ot_shared_pair, IdealSharedRandomOTReceiver, IdealSharedRandomOTSender,
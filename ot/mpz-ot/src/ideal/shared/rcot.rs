@@ -148,4 +148,29 @@ mod tests {
 
         assert_eq!(received, expected);
     }
+
+    #[tokio::test]
+    async fn test_ideal_random_cot_shared_packed() {
+        use itybity::IntoBitIterator;
+
+        let delta = Block::from([42u8; 16]);
+        let (sender, receiver) = ideal_random_cot_shared_pair([0u8; 32], delta);
+
+        let values = sender.send_random_correlated("", 10).await.unwrap();
+
+        let (packed, received) = receiver
+            .receive_random_correlated_packed("", 10)
+            .await
+            .unwrap();
+
+        assert_eq!(packed.len(), 2);
+
+        let expected = values
+            .into_iter()
+            .zip(packed.into_iter_lsb0())
+            .map(|(v, c)| if c { v ^ delta } else { v })
+            .collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+    }
 }
@@ -0,0 +1,195 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use futures::channel::oneshot;
+
+use crate::{
+    CommittedOTSenderShared, OTError, OTReceiverShared, OTSenderShared, VerifiableOTReceiverShared,
+};
+
+/// A transfer recorded by [`RecordingIdealOT`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer<T> {
+    /// The id of the transfer.
+    pub id: String,
+    /// The receiver's choices.
+    pub choices: Vec<bool>,
+    /// The sender's message pairs.
+    pub sent: Vec<[T; 2]>,
+    /// The messages received by the receiver.
+    pub received: Vec<T>,
+}
+
+/// An ideal oblivious transfer which records every transfer.
+///
+/// The same instance acts as both the sender and the receiver, so a clone
+/// should be provided to each party.
+#[derive(Debug)]
+pub struct RecordingIdealOT<T> {
+    state: Arc<Mutex<State<T>>>,
+}
+
+#[derive(Debug)]
+#[allow(clippy::type_complexity)]
+struct State<T> {
+    /// Messages sent prior to the receiver requesting them.
+    sent: HashMap<String, Vec<[T; 2]>>,
+    /// Receivers waiting for the sender's messages.
+    waiting: HashMap<String, (Vec<bool>, oneshot::Sender<Vec<T>>)>,
+    /// The completed transfers, in order of completion.
+    transfers: Vec<Transfer<T>>,
+}
+
+impl<T> Clone for RecordingIdealOT<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> Default for RecordingIdealOT<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> RecordingIdealOT<T> {
+    /// Creates a new recording ideal OT.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                sent: HashMap::new(),
+                waiting: HashMap::new(),
+                transfers: Vec::new(),
+            })),
+        }
+    }
+}
+
+impl<T: Clone> RecordingIdealOT<T> {
+    /// Returns the completed transfers, in order of completion.
+    pub fn transfers(&self) -> Vec<Transfer<T>> {
+        self.state.lock().unwrap().transfers.clone()
+    }
+}
+
+impl<T: Copy> State<T> {
+    /// Completes a transfer, returning the messages chosen by the receiver.
+    fn transfer(&mut self, id: &str, choices: Vec<bool>, sent: Vec<[T; 2]>) -> Vec<T> {
+        let received: Vec<T> = sent
+            .iter()
+            .zip(&choices)
+            .map(|(msgs, c)| msgs[*c as usize])
+            .collect();
+
+        self.transfers.push(Transfer {
+            id: id.to_string(),
+            choices,
+            sent,
+            received: received.clone(),
+        });
+
+        received
+    }
+}
+
+#[async_trait]
+impl<T: Copy + Send + Sync + 'static> OTSenderShared<[T; 2]> for RecordingIdealOT<T> {
+    async fn send(&self, id: &str, msgs: &[[T; 2]]) -> Result<(), OTError> {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some((choices, sender)) = state.waiting.remove(id) {
+            let received = state.transfer(id, choices, msgs.to_vec());
+            sender
+                .send(received)
+                .expect("RecordingIdealOT receiver should be waiting");
+        } else {
+            state.sent.insert(id.to_string(), msgs.to_vec());
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T: Copy + Send + Sync + 'static> CommittedOTSenderShared<[T; 2]> for RecordingIdealOT<T> {
+    async fn reveal(&self) -> Result<(), OTError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T: Copy + Send + Sync + 'static> OTReceiverShared<bool, T> for RecordingIdealOT<T> {
+    async fn receive(&self, id: &str, choices: &[bool]) -> Result<Vec<T>, OTError> {
+        let receiver = {
+            let mut state = self.state.lock().unwrap();
+
+            if let Some(sent) = state.sent.remove(id) {
+                return Ok(state.transfer(id, choices.to_vec(), sent));
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            state
+                .waiting
+                .insert(id.to_string(), (choices.to_vec(), sender));
+
+            receiver
+        };
+
+        Ok(receiver.await.unwrap())
+    }
+}
+
+#[async_trait]
+impl<T: Copy + Send + Sync + 'static> VerifiableOTReceiverShared<bool, T, [T; 2]>
+    for RecordingIdealOT<T>
+{
+    async fn verify(&self, _id: &str, _msgs: &[[T; 2]]) -> Result<(), OTError> {
+        // Ideal OT is always honest
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_recording_ideal_ot() {
+        let ot = RecordingIdealOT::<u8>::new();
+
+        // Sender first.
+        ot.send("0", &[[0, 1], [2, 3]]).await.unwrap();
+        let received = ot.receive("0", &[false, true]).await.unwrap();
+        assert_eq!(received, vec![0, 3]);
+
+        // Receiver first.
+        let (received, _) = futures::join!(
+            ot.receive("1", &[true, false]),
+            ot.send("1", &[[4, 5], [6, 7]])
+        );
+        assert_eq!(received.unwrap(), vec![5, 6]);
+
+        assert_eq!(
+            ot.transfers(),
+            vec![
+                Transfer {
+                    id: "0".to_string(),
+                    choices: vec![false, true],
+                    sent: vec![[0, 1], [2, 3]],
+                    received: vec![0, 3],
+                },
+                Transfer {
+                    id: "1".to_string(),
+                    choices: vec![true, false],
+                    sent: vec![[4, 5], [6, 7]],
+                    received: vec![5, 6],
+                },
+            ]
+        );
+    }
+}
@@ -70,6 +70,10 @@ pub enum ReceiverError {
     ConfigError(String),
     #[error(transparent)]
     VerifyError(#[from] ReceiverVerifyError),
+    #[error("duplicate transfer id: {0}")]
+    DuplicateTransferId(usize),
+    #[error("unknown transfer id: {0}")]
+    UnknownTransferId(usize),
     #[error("{0}")]
     Other(String),
 }
@@ -1,20 +1,33 @@
 //! An implementation of the [`KOS15`](https://eprint.iacr.org/2015/546.pdf) oblivious transfer extension protocol.
+//!
+//! # Debug checks
+//!
+//! With the `debug-checks` feature enabled, the receiver samples a few of the OTs produced by each
+//! extension, and the sender checks that `q = t ^ (choice & delta)` holds for them, returning an
+//! error on a mismatch. The sample is sent as an additional message, so both peers must enable the
+//! feature, otherwise the protocol desyncs. This reveals some of the receiver's choices and costs
+//! performance, so the feature must not be enabled in production.
 
 mod error;
+mod precompute;
 mod receiver;
 mod sender;
 
 pub use error::{ReceiverError, ReceiverVerifyError, SenderError};
 use futures_util::{SinkExt, StreamExt};
+use mpz_core::Block;
+pub use precompute::{PrecomputedReceiver, PrecomputedSender};
 pub use receiver::Receiver;
 pub use sender::Sender;
 
 pub(crate) use receiver::StateError as ReceiverStateError;
 pub(crate) use sender::StateError as SenderStateError;
 
+use mpz_ot_core::kos::CSP;
 pub use mpz_ot_core::kos::{
-    msgs, PayloadRecord, ReceiverConfig, ReceiverConfigBuilder, ReceiverConfigBuilderError,
-    ReceiverKeys, SenderConfig, SenderConfigBuilder, SenderConfigBuilderError, SenderKeys,
+    msgs, FlightHook, PayloadRecord, ReceiverConfig, ReceiverConfigBuilder,
+    ReceiverConfigBuilderError, ReceiverKeys, SenderConfig, SenderConfigBuilder,
+    SenderConfigBuilderError, SenderKeys,
 };
 use utils_aio::{sink::IoSink, stream::IoStream};
 
@@ -28,6 +41,96 @@ cfg_if::cfg_if! {
     }
 }
 
+/// The number of OTs sampled by the receiver for the debug check of each extension.
+#[cfg(feature = "debug-checks")]
+pub(crate) const DEBUG_CHECK_SAMPLES: usize = 8;
+
+/// Hooks which are invoked at the boundaries of protocol flights.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct FlightHooks {
+    sent: Option<FlightHook>,
+    received: Option<FlightHook>,
+}
+
+impl FlightHooks {
+    pub(crate) fn new(sent: Option<&FlightHook>, received: Option<&FlightHook>) -> Self {
+        Self {
+            sent: sent.cloned(),
+            received: received.cloned(),
+        }
+    }
+
+    /// Invokes the hook for a sent flight, if set.
+    pub(crate) fn sent(&self, name: &str, bytes: usize) {
+        if let Some(hook) = &self.sent {
+            hook.call(name, bytes);
+        }
+    }
+
+    /// Invokes the hook for a received flight, if set.
+    pub(crate) fn received(&self, name: &str, bytes: usize) {
+        if let Some(hook) = &self.received {
+            hook.call(name, bytes);
+        }
+    }
+}
+
+/// Checks that the base OT is not one of the insecure implementations in [`crate::ideal`],
+/// unless compiled for tests.
+///
+/// # Panics
+///
+/// Panics in debug builds if the base OT is ideal. Release builds log a warning instead.
+///
+/// # Arguments
+///
+/// * `role` - The KOS role using the base OT.
+/// * `is_ideal` - Whether the base OT is ideal.
+/// * `name` - The type name of the base OT.
+fn check_base(role: &str, is_ideal: bool, name: &str) {
+    debug_assert!(
+        !is_ideal || cfg!(test),
+        "KOS {} must not use the ideal base OT {} outside of tests",
+        role,
+        name
+    );
+
+    #[cfg(not(test))]
+    if is_ideal {
+        tracing::warn!(
+            "KOS {} is using the ideal base OT {}, which is insecure",
+            role,
+            name
+        );
+    }
+}
+
+/// The size of the setup flight in bytes.
+///
+/// The setup flight consists of the base OT messages, which are opaque to KOS, so both
+/// parties report the size of the seeds transferred by the base OT instead.
+pub(crate) const SETUP_FLIGHT_SIZE: usize = CSP * 2 * Block::LEN;
+
+/// Returns the serialized size of a chunk of the extension matrix in bytes.
+pub(crate) fn extend_size(extend: &msgs::Extend) -> usize {
+    bincode::serialized_size(extend).expect("extend should serialize") as usize
+}
+
+/// Returns the serialized size of the consistency check in bytes.
+pub(crate) fn check_size(check: &msgs::Check) -> usize {
+    bincode::serialized_size(check).expect("check should serialize") as usize
+}
+
+/// Returns the serialized size of the receiver's derandomization in bytes.
+pub(crate) fn derandomize_size(derandomize: &msgs::Derandomize) -> usize {
+    bincode::serialized_size(derandomize).expect("derandomize should serialize") as usize
+}
+
+/// Returns the serialized size of the sender's payload in bytes.
+pub(crate) fn payload_size(payload: &msgs::SenderPayload) -> usize {
+    bincode::serialized_size(payload).expect("payload should serialize") as usize
+}
+
 /// Converts a sink of KOS messages into a sink of base OT messages.
 pub(crate) fn into_base_sink<'a, Si: IoSink<msgs::Message<T>> + Send + Unpin, T: Send + 'a>(
     sink: &'a mut Si,
@@ -51,6 +154,7 @@ pub(crate) fn into_base_stream<'a, St: IoStream<msgs::Message<T>> + Send + Unpin
 mod tests {
     use super::*;
     use rstest::*;
+    use std::sync::{Arc, Mutex};
 
     use itybity::ToBits;
     use mpz_core::Block;
@@ -62,7 +166,9 @@ mod tests {
 
     use crate::{
         ideal::{ideal_ot_pair, IdealOTReceiver, IdealOTSender},
-        OTReceiver, OTSender, OTSetup, RandomOTReceiver, RandomOTSender, VerifiableOTReceiver,
+        tests::FlushOnlySink,
+        OTError, OTReceiver, OTSender, OTSetup, RandomOTReceiver, RandomOTSender,
+        VerifiableOTReceiver,
     };
 
     #[fixture]
@@ -126,6 +232,29 @@ mod tests {
         (sender, receiver)
     }
 
+    #[test]
+    fn test_kos_base_is_ideal() {
+        let (base_sender, base_receiver) = ideal_ot_pair::<Block>();
+
+        let sender = Sender::new(SenderConfig::default(), base_receiver);
+        let receiver = Receiver::new(ReceiverConfig::default(), base_sender);
+
+        assert!(sender.base_is_ideal());
+        assert!(receiver.base_is_ideal());
+
+        let sender = Sender::new(
+            SenderConfig::default(),
+            crate::chou_orlandi::Receiver::new(crate::chou_orlandi::ReceiverConfig::default()),
+        );
+        let receiver = Receiver::new(
+            ReceiverConfig::default(),
+            crate::chou_orlandi::Sender::new(crate::chou_orlandi::SenderConfig::default()),
+        );
+
+        assert!(!sender.base_is_ideal());
+        assert!(!receiver.base_is_ideal());
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_kos(data: Vec<[Block; 2]>, choices: Vec<bool>) {
@@ -158,6 +287,258 @@ mod tests {
         assert_eq!(received, expected);
     }
 
+    #[cfg(feature = "debug-checks")]
+    #[rstest]
+    #[tokio::test]
+    async fn test_kos_debug_checks(data: Vec<[Block; 2]>, choices: Vec<bool>) {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        // The sender checks the sampled correlations during extension.
+        let (mut sender, mut receiver) = setup(
+            SenderConfig::default(),
+            ReceiverConfig::default(),
+            &mut sender_sink,
+            &mut sender_stream,
+            &mut receiver_sink,
+            &mut receiver_stream,
+            data.len(),
+        )
+        .await;
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send(&mut sender_sink, &mut sender_stream, &data),
+            receiver.receive(&mut receiver_sink, &mut receiver_stream, &choices)
+        );
+
+        sender_res.unwrap();
+        let received: Vec<Block> = receiver_res.unwrap();
+
+        let expected = choose(data.iter().copied(), choices.iter_lsb0()).collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_kos_flush(data: Vec<[Block; 2]>, choices: Vec<bool>) {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (sender_sink, mut sender_stream) = sender_channel.split();
+        let (receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        // Messages are only delivered once the sinks are flushed.
+        let mut sender_sink = FlushOnlySink::new(sender_sink);
+        let mut receiver_sink = FlushOnlySink::new(receiver_sink);
+
+        let (mut sender, mut receiver) = setup(
+            SenderConfig::default(),
+            ReceiverConfig::default(),
+            &mut sender_sink,
+            &mut sender_stream,
+            &mut receiver_sink,
+            &mut receiver_stream,
+            data.len(),
+        )
+        .await;
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send(&mut sender_sink, &mut sender_stream, &data),
+            receiver.receive(&mut receiver_sink, &mut receiver_stream, &choices)
+        );
+
+        sender_res.unwrap();
+        let received: Vec<Block> = receiver_res.unwrap();
+
+        let expected = choose(data.iter().copied(), choices.iter_lsb0()).collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_kos_precompute(data: Vec<[Block; 2]>, choices: Vec<bool>) {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let (mut sender, mut receiver) = setup(
+            SenderConfig::default(),
+            ReceiverConfig::default(),
+            &mut sender_sink,
+            &mut sender_stream,
+            &mut receiver_sink,
+            &mut receiver_stream,
+            data.len(),
+        )
+        .await;
+
+        // Offline phase
+        let (sender_keys, receiver_keys) = tokio::join!(
+            sender.precompute(&mut sender_sink, &mut sender_stream, data.len()),
+            receiver.precompute(&mut receiver_sink, &mut receiver_stream, data.len())
+        );
+
+        let sender_keys = bincode::serialize(&sender_keys.unwrap()).unwrap();
+        let receiver_keys = bincode::serialize(&receiver_keys.unwrap()).unwrap();
+
+        // Online phase
+        let sender = PrecomputedSender::from_precomputed(
+            bincode::deserialize::<SenderKeys>(&sender_keys).unwrap(),
+        );
+        let receiver = PrecomputedReceiver::from_precomputed(
+            bincode::deserialize::<ReceiverKeys>(&receiver_keys).unwrap(),
+        );
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send(&mut sender_sink, &mut sender_stream, &data),
+            receiver.receive(&mut receiver_sink, &mut receiver_stream, &choices)
+        );
+
+        sender_res.unwrap();
+        let received = receiver_res.unwrap();
+
+        let expected = choose(data.iter().copied(), choices.iter_lsb0()).collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_kos_flight_hooks(data: Vec<[Block; 2]>, choices: Vec<bool>) {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let sender_sent = Arc::new(Mutex::new(Vec::new()));
+        let sender_received = Arc::new(Mutex::new(Vec::new()));
+        let receiver_sent = Arc::new(Mutex::new(Vec::new()));
+        let receiver_received = Arc::new(Mutex::new(Vec::new()));
+
+        let record = |flights: &Arc<Mutex<Vec<(String, usize)>>>| {
+            let flights = flights.clone();
+            move |name: &str, bytes: usize| flights.lock().unwrap().push((name.to_string(), bytes))
+        };
+
+        let receiver_config = ReceiverConfig::builder()
+            .on_flight_sent(record(&receiver_sent))
+            .on_flight_received(record(&receiver_received))
+            .build()
+            .unwrap();
+        let sender_config = SenderConfig::builder()
+            .on_flight_sent(record(&sender_sent))
+            .on_flight_received(record(&sender_received))
+            .build()
+            .unwrap();
+
+        let (mut sender, mut receiver) = setup(
+            sender_config,
+            receiver_config,
+            &mut sender_sink,
+            &mut sender_stream,
+            &mut receiver_sink,
+            &mut receiver_stream,
+            data.len(),
+        )
+        .await;
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send(&mut sender_sink, &mut sender_stream, &data),
+            receiver.receive(&mut receiver_sink, &mut receiver_stream, &choices)
+        );
+
+        sender_res.unwrap();
+        let _: Vec<Block> = receiver_res.unwrap();
+
+        let sender_sent = sender_sent.lock().unwrap().clone();
+        let sender_received = sender_received.lock().unwrap().clone();
+        let receiver_sent = receiver_sent.lock().unwrap().clone();
+        let receiver_received = receiver_received.lock().unwrap().clone();
+
+        // Both sides report the same sizes for each flight.
+        assert_eq!(receiver_sent, sender_received);
+        assert_eq!(sender_sent, receiver_received);
+
+        let check_bytes = receiver_sent
+            .iter()
+            .find_map(|(name, bytes)| (name == "check").then_some(*bytes))
+            .unwrap();
+        assert_eq!(check_bytes, 3 * Block::LEN);
+
+        let expected = ["setup", "extend", "check", "transfer"];
+        let mut flights: Vec<_> = receiver_sent.into_iter().map(|(name, _)| name).collect();
+        flights.dedup();
+
+        assert_eq!(flights, expected);
+        assert_eq!(
+            sender_sent
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            ["transfer"]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_kos_receive_into(data: Vec<[Block; 2]>, choices: Vec<bool>) {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let lens = [128, 64, 32];
+
+        let (mut sender, mut receiver) = setup(
+            SenderConfig::default(),
+            ReceiverConfig::default(),
+            &mut sender_sink,
+            &mut sender_stream,
+            &mut receiver_sink,
+            &mut receiver_stream,
+            2 * lens.iter().sum::<usize>(),
+        )
+        .await;
+
+        let mut buffer: Vec<Block> = Vec::new();
+        let mut buffer_ptr = None;
+        for len in lens {
+            let data = &data[..len];
+            let choices = &choices[..len];
+
+            let (sender_res, receiver_res) = tokio::join!(
+                sender.send(&mut sender_sink, &mut sender_stream, data),
+                receiver.receive(&mut receiver_sink, &mut receiver_stream, choices)
+            );
+
+            sender_res.unwrap();
+            let received: Vec<Block> = receiver_res.unwrap();
+
+            let (sender_res, receiver_res) = tokio::join!(
+                sender.send(&mut sender_sink, &mut sender_stream, data),
+                receiver.receive_into(
+                    &mut receiver_sink,
+                    &mut receiver_stream,
+                    choices,
+                    &mut buffer
+                )
+            );
+
+            sender_res.unwrap();
+            receiver_res.unwrap();
+
+            assert_eq!(buffer, received);
+
+            // The lengths are decreasing, so the buffer is never reallocated.
+            let ptr = *buffer_ptr.get_or_insert(buffer.as_ptr());
+            assert_eq!(buffer.as_ptr(), ptr);
+        }
+    }
+
     #[tokio::test]
     async fn test_kos_random() {
         let (sender_channel, receiver_channel) = MemoryDuplex::new();
@@ -274,4 +655,92 @@ mod tests {
         sender_res.unwrap();
         receiver_res.unwrap();
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_kos_verify_with_id(data: Vec<[Block; 2]>, choices: Vec<bool>) {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let (mut sender, mut receiver) = setup(
+            SenderConfig::builder().sender_commit().build().unwrap(),
+            ReceiverConfig::builder().sender_commit().build().unwrap(),
+            &mut sender_sink,
+            &mut sender_stream,
+            &mut receiver_sink,
+            &mut receiver_stream,
+            data.len(),
+        )
+        .await;
+
+        let ids = [10, 20, 30];
+        for (id, (data, choices)) in ids.iter().zip(data.chunks(32).zip(choices.chunks(32))) {
+            let (sender_res, receiver_res) = tokio::join!(
+                sender.send_with_id(&mut sender_sink, &mut sender_stream, *id, data),
+                receiver.receive_with_id(&mut receiver_sink, &mut receiver_stream, *id, choices)
+            );
+
+            sender_res.unwrap();
+            let received: Vec<Block> = receiver_res.unwrap();
+
+            let expected =
+                choose(data.iter().copied(), choices.iter().copied()).collect::<Vec<_>>();
+
+            assert_eq!(received, expected);
+        }
+
+        for (transfer_id, id) in ids.iter().enumerate() {
+            assert_eq!(receiver.transfer_id(*id), Some(transfer_id as u32));
+        }
+
+        // Tagging another transfer with an existing id fails.
+        let err = receiver
+            .receive_with_id::<Block, _, _>(&mut receiver_sink, &mut receiver_stream, 10, &choices)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            OTError::ReceiverError(e)
+                if matches!(
+                    e.downcast_ref::<ReceiverError>(),
+                    Some(ReceiverError::DuplicateTransferId(10))
+                )
+        ));
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.reveal(&mut sender_sink, &mut sender_stream),
+            receiver.verify_with_id(&mut receiver_sink, &mut receiver_stream, 20, &data[32..64])
+        );
+
+        sender_res.unwrap();
+        receiver_res.unwrap();
+
+        // The messages of another transfer do not verify against this id.
+        receiver
+            .verify_with_id(&mut receiver_sink, &mut receiver_stream, 10, &data[64..96])
+            .await
+            .unwrap_err();
+
+        // Verifying by transfer id is unaffected by the tags.
+        receiver
+            .verify(&mut receiver_sink, &mut receiver_stream, 2, &data[64..96])
+            .await
+            .unwrap();
+
+        // Unknown ids are rejected.
+        let err = receiver
+            .verify_with_id(&mut receiver_sink, &mut receiver_stream, 40, &data[..32])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            OTError::ReceiverError(e)
+                if matches!(
+                    e.downcast_ref::<ReceiverError>(),
+                    Some(ReceiverError::UnknownTransferId(40))
+                )
+        ));
+    }
 }
@@ -0,0 +1,113 @@
+//! Transfers using OTs which were precomputed in an offline phase.
+//!
+//! The keys returned by [`Sender::precompute`](super::Sender::precompute) and
+//! [`Receiver::precompute`](super::Receiver::precompute) are serializable, so they can be
+//! persisted, eg. to disk, and restored for a transfer in a later online phase.
+//!
+//! # Security
+//!
+//! The persisted keys must be kept confidential: the sender's keys are as sensitive as the
+//! messages they encrypt, and the receiver's keys reveal its choices. Each set of keys must only
+//! be used for a single transfer, which is why the types in this module are consumed by it.
+
+use futures::SinkExt;
+use itybity::IntoBitIterator;
+use mpz_core::Block;
+use mpz_ot_core::kos::{msgs::Message, ReceiverKeys, SenderKeys};
+use utils_aio::{
+    non_blocking_backend::{Backend, NonBlockingBackend},
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use super::{ReceiverError, SenderError};
+
+/// KOS sender for a single transfer using precomputed OTs.
+pub struct PrecomputedSender {
+    keys: SenderKeys,
+}
+
+opaque_debug::implement!(PrecomputedSender);
+
+impl PrecomputedSender {
+    /// Creates a new sender from keys which were precomputed with
+    /// [`Sender::precompute`](super::Sender::precompute).
+    pub fn from_precomputed(keys: SenderKeys) -> Self {
+        Self { keys }
+    }
+
+    /// Sends messages to the receiver, consuming the keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The IO sink to the receiver.
+    /// * `stream` - The IO stream from the receiver.
+    /// * `msgs` - The messages to send.
+    pub async fn send<
+        T: Send,
+        Si: IoSink<Message<T>> + Send + Unpin,
+        St: IoStream<Message<T>> + Send + Unpin,
+    >(
+        self,
+        sink: &mut Si,
+        stream: &mut St,
+        msgs: &[[Block; 2]],
+    ) -> Result<(), SenderError> {
+        let Self { mut keys } = self;
+
+        let derandomize = stream.expect_next().await?.try_into_derandomize()?;
+
+        keys.derandomize(derandomize)?;
+        let payload = keys.encrypt_blocks(msgs)?;
+
+        sink.send(Message::SenderPayload(payload)).await?;
+
+        Ok(())
+    }
+}
+
+/// KOS receiver for a single transfer using precomputed OTs.
+pub struct PrecomputedReceiver {
+    keys: ReceiverKeys,
+}
+
+opaque_debug::implement!(PrecomputedReceiver);
+
+impl PrecomputedReceiver {
+    /// Creates a new receiver from keys which were precomputed with
+    /// [`Receiver::precompute`](super::Receiver::precompute).
+    pub fn from_precomputed(keys: ReceiverKeys) -> Self {
+        Self { keys }
+    }
+
+    /// Receives messages from the sender, consuming the keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The IO sink to the sender.
+    /// * `stream` - The IO stream from the sender.
+    /// * `choices` - The choices made by the receiver.
+    pub async fn receive<
+        T: Send,
+        Si: IoSink<Message<T>> + Send + Unpin,
+        St: IoStream<Message<T>> + Send + Unpin,
+    >(
+        self,
+        sink: &mut Si,
+        stream: &mut St,
+        choices: &[bool],
+    ) -> Result<Vec<Block>, ReceiverError> {
+        let Self { mut keys } = self;
+
+        let choices = choices.into_lsb0_vec();
+        let derandomize = keys.derandomize(&choices)?;
+
+        sink.send(Message::Derandomize(derandomize)).await?;
+
+        let payload = stream.expect_next().await?.try_into_sender_payload()?;
+
+        let received = Backend::spawn(move || keys.decrypt_blocks(payload)).await?;
+
+        Ok(received)
+    }
+}
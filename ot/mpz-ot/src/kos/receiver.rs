@@ -1,10 +1,13 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use futures::SinkExt;
 use itybity::{FromBitIterator, IntoBitIterator};
 use mpz_core::{cointoss, prg::Prg, Block, ProtocolMessage};
 use mpz_ot_core::kos::{
     msgs::{Message, StartExtend},
-    pad_ot_count, receiver_state as state, Receiver as ReceiverCore, ReceiverConfig, CSP,
+    pad_ot_count_with_ssp, receiver_state as state, Receiver as ReceiverCore, ReceiverConfig,
+    ReceiverKeys, CSP,
 };
 
 use enum_try_as_inner::EnumTryAsInner;
@@ -17,7 +20,9 @@ use utils_aio::{
 };
 
 use super::{
-    into_base_sink, into_base_stream, ReceiverError, ReceiverVerifyError, EXTEND_CHUNK_SIZE,
+    check_base, check_size, derandomize_size, extend_size, into_base_sink, into_base_stream,
+    payload_size, FlightHooks, ReceiverError, ReceiverVerifyError, EXTEND_CHUNK_SIZE,
+    SETUP_FLIGHT_SIZE,
 };
 use crate::{
     OTError, OTReceiver, OTSender, OTSetup, RandomOTReceiver, VerifiableOTReceiver,
@@ -40,6 +45,10 @@ pub struct Receiver<BaseOT> {
     base: BaseOT,
 
     cointoss_receiver: Option<cointoss::Receiver<cointoss::receiver_state::Received>>,
+    /// Transfer ids for each application-level id.
+    transfer_ids: HashMap<usize, u32>,
+    /// Hooks invoked when a flight is sent or received.
+    hooks: FlightHooks,
 }
 
 impl<BaseOT> Receiver<BaseOT>
@@ -52,10 +61,20 @@ where
     ///
     /// * `config` - The receiver's configuration
     pub fn new(config: ReceiverConfig, base: BaseOT) -> Self {
+        check_base(
+            "receiver",
+            <BaseOT as OTSender<[Block; 2]>>::IS_IDEAL,
+            std::any::type_name::<BaseOT>(),
+        );
+
+        let hooks = FlightHooks::new(config.on_flight_sent(), config.on_flight_received());
+
         Self {
             state: State::Initialized(Box::new(ReceiverCore::new(config))),
             base,
             cointoss_receiver: None,
+            transfer_ids: HashMap::new(),
+            hooks,
         }
     }
 
@@ -64,6 +83,13 @@ where
         Ok(self.state.try_as_extension()?.remaining())
     }
 
+    /// Returns whether the base OT is one of the insecure ideal implementations.
+    ///
+    /// Applications should refuse to proceed if this returns `true` outside of tests.
+    pub fn base_is_ideal(&self) -> bool {
+        <BaseOT as OTSender<[Block; 2]>>::IS_IDEAL
+    }
+
     /// Returns a reference to the inner receiver state.
     pub(crate) fn state(&self) -> &State {
         &self.state
@@ -93,7 +119,7 @@ where
         let mut ext_receiver =
             std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
 
-        let count = pad_ot_count(count);
+        let count = pad_ot_count_with_ssp(count, ext_receiver.config().ssp());
 
         // Extend the OTs.
         let (mut ext_receiver, extend) = Backend::spawn(move || {
@@ -113,10 +139,16 @@ where
         sink.feed(Message::StartExtend(StartExtend { count }))
             .await?;
         for extend in extend.into_chunks(EXTEND_CHUNK_SIZE) {
+            self.hooks.sent("extend", extend_size(&extend));
             sink.feed(Message::Extend(extend)).await?;
         }
         sink.feed(Message::CointossCommit(cointoss_commitment))
             .await?;
+        #[cfg(feature = "debug-checks")]
+        sink.feed(Message::DebugSample(
+            ext_receiver.debug_sample(super::DEBUG_CHECK_SAMPLES),
+        ))
+        .await?;
         sink.flush().await?;
 
         // Receive coin toss
@@ -140,14 +172,44 @@ where
         let check = check?;
 
         // Send coin toss decommitment and correlation check value.
+        let check_len = check_size(&check);
         sink.feed(Message::CointossSenderPayload(payload)).await?;
         sink.feed(Message::Check(check)).await?;
         sink.flush().await?;
 
+        self.hooks.sent("check", check_len);
+
         self.state = State::Extension(ext_receiver);
 
         Ok(())
     }
+
+    /// Performs OT extension and reserves the keys for `count` OTs, so they can be persisted
+    /// and used for a transfer in a later online phase.
+    ///
+    /// See [`PrecomputedReceiver`](super::PrecomputedReceiver) for how to use the returned keys, and
+    /// the security caveats of persisting them.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the sender
+    /// * `stream` - The stream to receive messages from the sender
+    /// * `count` - The number of OTs to precompute
+    pub async fn precompute<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        count: usize,
+    ) -> Result<ReceiverKeys, ReceiverError> {
+        self.extend(sink, stream, count).await?;
+
+        let keys = self.state.try_as_extension_mut()?.keys(count)?;
+
+        Ok(keys)
+    }
 }
 
 impl<BaseOT> Receiver<BaseOT>
@@ -199,6 +261,99 @@ where
     }
 }
 
+impl<BaseOT> Receiver<BaseOT>
+where
+    BaseOT: ProtocolMessage + Send,
+{
+    /// Receives messages from the sender, tagging the transfer with an application-level id.
+    ///
+    /// The id can later be passed to [`VerifiableOTReceiver::verify`] to verify this transfer.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The IO sink to the sender.
+    /// * `stream` - The IO stream from the sender.
+    /// * `id` - The application-level id of the transfer.
+    /// * `choices` - The choices made by the receiver.
+    pub async fn receive_with_id<
+        T: Send + Sync,
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        id: usize,
+        choices: &[bool],
+    ) -> Result<Vec<T>, OTError>
+    where
+        Self: OTReceiver<bool, T, Msg = Message<BaseOT::Msg>>,
+    {
+        if self.transfer_ids.contains_key(&id) {
+            return Err(ReceiverError::DuplicateTransferId(id).into());
+        }
+
+        let transfer_id = self
+            .state
+            .try_as_extension()
+            .map_err(ReceiverError::from)?
+            .current_transfer_id();
+
+        let received = OTReceiver::<bool, T>::receive(self, sink, stream, choices).await?;
+
+        self.transfer_ids.insert(id, transfer_id);
+
+        Ok(received)
+    }
+
+    /// Returns the transfer id of a transfer tagged with the given application-level id.
+    pub fn transfer_id(&self, id: usize) -> Option<u32> {
+        self.transfer_ids.get(&id).copied()
+    }
+}
+
+impl<BaseOT> Receiver<BaseOT>
+where
+    BaseOT: VerifiableOTSender<bool, [Block; 2]> + ProtocolMessage + Send,
+{
+    /// Verifies the messages of a transfer tagged with an application-level id using
+    /// [`receive_with_id`](Self::receive_with_id).
+    ///
+    /// Unlike [`VerifiableOTReceiver::verify`], which takes the transfer id, this returns an
+    /// error if no transfer was tagged with the id.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The IO sink to the sender.
+    /// * `stream` - The IO stream from the sender.
+    /// * `id` - The application-level id of the transfer.
+    /// * `msgs` - The purported messages sent by the sender.
+    pub async fn verify_with_id<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        id: usize,
+        msgs: &[[Block; 2]],
+    ) -> Result<(), OTError> {
+        let transfer_id = self
+            .transfer_ids
+            .remove(&id)
+            .ok_or(ReceiverError::UnknownTransferId(id))?;
+
+        VerifiableOTReceiver::<bool, Block, [Block; 2]>::verify(
+            self,
+            sink,
+            stream,
+            transfer_id as usize,
+            msgs,
+        )
+        .await
+    }
+}
+
 impl<BaseOT> ProtocolMessage for Receiver<BaseOT>
 where
     BaseOT: ProtocolMessage,
@@ -260,6 +415,8 @@ where
             )
             .await?;
 
+        self.hooks.sent("setup", SETUP_FLIGHT_SIZE);
+
         let ext_receiver = ext_receiver.setup(seeds);
 
         self.state = State::Extension(Box::new(ext_receiver));
@@ -282,6 +439,22 @@ where
         stream: &mut St,
         choices: &[bool],
     ) -> Result<Vec<Block>, OTError> {
+        let mut out = Vec::new();
+        self.receive_into(sink, stream, choices, &mut out).await?;
+
+        Ok(out)
+    }
+
+    async fn receive_into<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        choices: &[bool],
+        out: &mut Vec<Block>,
+    ) -> Result<(), OTError> {
         let receiver = self
             .state
             .try_as_extension_mut()
@@ -295,7 +468,9 @@ where
             .map_err(ReceiverError::from)?;
 
         // Send derandomize message
+        let derandomize_len = derandomize_size(&derandomize);
         sink.send(Message::Derandomize(derandomize)).await?;
+        self.hooks.sent("transfer", derandomize_len);
 
         // Receive payload
         let payload = stream
@@ -304,14 +479,20 @@ where
             .try_into_sender_payload()
             .map_err(ReceiverError::from)?;
 
-        let received = Backend::spawn(move || {
-            receiver_keys
-                .decrypt_blocks(payload)
-                .map_err(ReceiverError::from)
+        self.hooks.received("transfer", payload_size(&payload));
+
+        // Move the buffer to the backend and back, to decrypt into it without reallocating.
+        let mut buffer = std::mem::take(out);
+        let (buffer, res) = Backend::spawn(move || {
+            let res = receiver_keys.decrypt_blocks_into(payload, &mut buffer);
+            (buffer, res)
         })
-        .await?;
+        .await;
+        *out = buffer;
 
-        Ok(received)
+        res.map_err(ReceiverError::from)?;
+
+        Ok(())
     }
 }
 
@@ -370,7 +551,9 @@ where
             .map_err(ReceiverError::from)?;
 
         // Send derandomize message
+        let derandomize_len = derandomize_size(&derandomize);
         sink.send(Message::Derandomize(derandomize)).await?;
+        self.hooks.sent("transfer", derandomize_len);
 
         // Receive payload
         let payload = stream
@@ -379,6 +562,8 @@ where
             .try_into_sender_payload()
             .map_err(ReceiverError::from)?;
 
+        self.hooks.received("transfer", payload_size(&payload));
+
         let received = Backend::spawn(move || {
             receiver_keys
                 .decrypt_bytes(payload)
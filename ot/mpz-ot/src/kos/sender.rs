@@ -6,7 +6,8 @@ use mpz_core::{cointoss, prg::Prg, Block, ProtocolMessage};
 use mpz_ot_core::kos::{
     extension_matrix_size,
     msgs::{Extend, Message, StartExtend},
-    pad_ot_count, sender_state as state, Sender as SenderCore, SenderConfig, CSP,
+    pad_ot_count_with_ssp, sender_state as state, Sender as SenderCore, SenderConfig, SenderKeys,
+    CSP,
 };
 use rand::{thread_rng, Rng};
 use rand_core::{RngCore, SeedableRng};
@@ -16,7 +17,10 @@ use utils_aio::{
     stream::{ExpectStreamExt, IoStream},
 };
 
-use super::{into_base_sink, into_base_stream};
+use super::{
+    check_base, check_size, derandomize_size, extend_size, into_base_sink, into_base_stream,
+    payload_size, FlightHooks, SETUP_FLIGHT_SIZE,
+};
 use crate::{
     kos::SenderError, CommittedOTReceiver, CommittedOTSender, OTError, OTReceiver, OTSender,
     OTSetup, RandomOTSender,
@@ -38,6 +42,8 @@ pub struct Sender<BaseOT> {
     base: BaseOT,
 
     cointoss_payload: Option<cointoss::msgs::SenderPayload>,
+    /// Hooks invoked when a flight is sent or received.
+    hooks: FlightHooks,
 }
 
 impl<BaseOT> Sender<BaseOT>
@@ -50,10 +56,19 @@ where
     ///
     /// * `config` - The Sender's configuration
     pub fn new(config: SenderConfig, base: BaseOT) -> Self {
+        check_base(
+            "sender",
+            <BaseOT as OTReceiver<bool, Block>>::IS_IDEAL,
+            std::any::type_name::<BaseOT>(),
+        );
+
+        let hooks = FlightHooks::new(config.on_flight_sent(), config.on_flight_received());
+
         Self {
             state: State::Initialized(SenderCore::new(config)),
             base,
             cointoss_payload: None,
+            hooks,
         }
     }
 
@@ -62,6 +77,13 @@ where
         Ok(self.state.try_as_extension()?.remaining())
     }
 
+    /// Returns whether the base OT is one of the insecure ideal implementations.
+    ///
+    /// Applications should refuse to proceed if this returns `true` outside of tests.
+    pub fn base_is_ideal(&self) -> bool {
+        <BaseOT as OTReceiver<bool, Block>>::IS_IDEAL
+    }
+
     /// Returns a mutable reference to the inner sender state.
     pub(crate) fn state_mut(&mut self) -> &mut State {
         &mut self.state
@@ -113,6 +135,8 @@ where
             )
             .await?;
 
+        self.hooks.received("setup", SETUP_FLIGHT_SIZE);
+
         let seeds: [Block; CSP] = seeds.try_into().expect("seeds should be CSP length");
 
         let ext_sender = ext_sender.setup(delta, seeds);
@@ -140,7 +164,7 @@ where
         let mut ext_sender =
             std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
 
-        let count = pad_ot_count(count);
+        let count = pad_ot_count_with_ssp(count, ext_sender.config().ssp());
 
         let StartExtend {
             count: receiver_count,
@@ -163,22 +187,30 @@ where
 
         // Receive extension matrix from the receiver.
         while extend.us.len() < expected_us {
-            let Extend { us: chunk } = stream
+            let chunk = stream
                 .expect_next()
                 .await?
                 .try_into_extend()
                 .map_err(SenderError::from)?;
 
-            extend.us.extend(chunk);
+            self.hooks.received("extend", extend_size(&chunk));
+
+            extend.us.extend(chunk.us);
         }
 
         // Receive coin toss commitments from the receiver.
         let commitment = stream.expect_next().await?.try_into_cointoss_commit()?;
 
+        #[cfg(feature = "debug-checks")]
+        let debug_sample = stream.expect_next().await?.try_into_debug_sample()?;
+
         // Extend the OTs.
         let mut ext_sender =
             Backend::spawn(move || ext_sender.extend(count, extend).map(|_| ext_sender)).await?;
 
+        #[cfg(feature = "debug-checks")]
+        ext_sender.debug_check(&debug_sample)?;
+
         // Execute coin toss protocol for consistency check.
         let seed: Block = thread_rng().gen();
         let cointoss_receiver = cointoss::Receiver::new(vec![seed]);
@@ -198,6 +230,8 @@ where
         // Receive consistency check from the receiver.
         let receiver_check = stream.expect_next().await?.try_into_check()?;
 
+        self.hooks.received("check", check_size(&receiver_check));
+
         // Derive chi seed for the consistency check.
         let chi_seed = cointoss_receiver.finalize(cointoss_sender_payload)?[0];
 
@@ -213,6 +247,33 @@ where
 
         Ok(())
     }
+
+    /// Performs OT extension and reserves the keys for `count` OTs, so they can be persisted
+    /// and used for a transfer in a later online phase.
+    ///
+    /// See [`PrecomputedSender`](super::PrecomputedSender) for how to use the returned keys, and the
+    /// security caveats of persisting them.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The IO sink to the receiver.
+    /// * `stream` - The IO stream from the receiver.
+    /// * `count` - The number of OTs to precompute.
+    pub async fn precompute<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        count: usize,
+    ) -> Result<SenderKeys, SenderError> {
+        self.extend(sink, stream, count).await?;
+
+        let keys = self.state.try_as_extension_mut()?.keys(count)?;
+
+        Ok(keys)
+    }
 }
 
 impl<BaseOT> Sender<BaseOT>
@@ -252,6 +313,40 @@ where
     }
 }
 
+impl<BaseOT> Sender<BaseOT>
+where
+    BaseOT: ProtocolMessage + Send,
+{
+    /// Sends messages to the receiver, tagging the transfer with an application-level id.
+    ///
+    /// The receiver should tag the transfer with the same id using
+    /// [`Receiver::receive_with_id`](super::Receiver::receive_with_id). Only the receiver
+    /// verifies transfers, so the sender does not record the id.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The IO sink to the receiver.
+    /// * `stream` - The IO stream from the receiver.
+    /// * `id` - The application-level id of the transfer.
+    /// * `msgs` - The messages to send.
+    pub async fn send_with_id<
+        T: Send + Sync,
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        _id: usize,
+        msgs: &[T],
+    ) -> Result<(), OTError>
+    where
+        Self: OTSender<T, Msg = Message<BaseOT::Msg>>,
+    {
+        OTSender::<T>::send(self, sink, stream, msgs).await
+    }
+}
+
 impl<BaseOT> ProtocolMessage for Sender<BaseOT>
 where
     BaseOT: ProtocolMessage,
@@ -342,6 +437,9 @@ where
             .try_into_derandomize()
             .map_err(SenderError::from)?;
 
+        self.hooks
+            .received("transfer", derandomize_size(&derandomize));
+
         let mut sender_keys = sender.keys(msgs.len()).map_err(SenderError::from)?;
         sender_keys
             .derandomize(derandomize)
@@ -350,6 +448,8 @@ where
             .encrypt_blocks(msgs)
             .map_err(SenderError::from)?;
 
+        self.hooks.sent("transfer", payload_size(&payload));
+
         sink.send(Message::SenderPayload(payload))
             .await
             .map_err(SenderError::from)?;
@@ -407,12 +507,17 @@ where
             .try_into_derandomize()
             .map_err(SenderError::from)?;
 
+        self.hooks
+            .received("transfer", derandomize_size(&derandomize));
+
         let mut sender_keys = sender.keys(msgs.len()).map_err(SenderError::from)?;
         sender_keys
             .derandomize(derandomize)
             .map_err(SenderError::from)?;
         let payload = sender_keys.encrypt_bytes(msgs).map_err(SenderError::from)?;
 
+        self.hooks.sent("transfer", payload_size(&payload));
+
         sink.send(Message::SenderPayload(payload))
             .await
             .map_err(SenderError::from)?;
@@ -6,12 +6,14 @@
 
 #[cfg(feature = "actor")]
 pub mod actor;
+pub mod any;
 pub mod chou_orlandi;
 #[cfg(feature = "ideal")]
 pub mod ideal;
 pub mod kos;
 
 use async_trait::async_trait;
+use itybity::FromBitIterator;
 use mpz_core::ProtocolMessage;
 use utils_aio::{sink::IoSink, stream::IoStream};
 
@@ -27,6 +29,15 @@ pub enum OTError {
     ReceiverError(Box<dyn std::error::Error + Send + Sync>),
 }
 
+impl From<OTError> for std::io::Error {
+    fn from(err: OTError) -> Self {
+        match err {
+            OTError::IOError(e) => e,
+            e => std::io::Error::other(e),
+        }
+    }
+}
+
 // ########################################################################
 // ######################## Exclusive Reference ###########################
 // ########################################################################
@@ -53,6 +64,9 @@ pub trait OTSender<T>: ProtocolMessage
 where
     T: Send + Sync,
 {
+    /// Whether this is an insecure ideal implementation, which must only be used for testing.
+    const IS_IDEAL: bool = false;
+
     /// Obliviously transfers the messages to the receiver.
     ///
     /// # Arguments
@@ -149,6 +163,9 @@ where
     T: Send + Sync,
     U: Send + Sync,
 {
+    /// Whether this is an insecure ideal implementation, which must only be used for testing.
+    const IS_IDEAL: bool = false;
+
     /// Obliviously receives data from the sender.
     ///
     /// # Arguments
@@ -162,6 +179,38 @@ where
         stream: &mut St,
         choices: &[T],
     ) -> Result<Vec<U>, OTError>;
+
+    /// Obliviously receives data from the sender, writing it into the provided buffer.
+    ///
+    /// The buffer is cleared before the received data is written to it.
+    ///
+    /// The default implementation delegates to [`receive`](Self::receive) and copies the result
+    /// into the buffer, so it still allocates on every call. Implementations which can write the
+    /// data directly into the buffer, such as the KOS receiver, override it so that the buffer's
+    /// allocation is reused across calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The IO sink to the sender.
+    /// * `stream` - The IO stream from the sender.
+    /// * `choices` - The choices made by the receiver.
+    /// * `out` - The buffer to write the received data into.
+    async fn receive_into<
+        Si: IoSink<Self::Msg> + Send + Unpin,
+        St: IoStream<Self::Msg> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        choices: &[T],
+        out: &mut Vec<U>,
+    ) -> Result<(), OTError> {
+        out.clear();
+        out.reserve(choices.len());
+        out.extend(self.receive(sink, stream, choices).await?);
+
+        Ok(())
+    }
 }
 
 /// A correlated oblivious transfer receiver.
@@ -239,6 +288,40 @@ where
         stream: &mut St,
         count: usize,
     ) -> Result<(Vec<T>, Vec<U>), OTError>;
+
+    /// Obliviously receives correlated messages with random choices, returning the
+    /// choices packed into bytes.
+    ///
+    /// Returns a tuple of the choices, packed in LSB0 bit order, and the messages, respectively.
+    ///
+    /// The default implementation packs the choices after receiving them unpacked, so it does
+    /// not reduce the peak memory usage. Implementations should override it to pack the choices
+    /// as they are produced.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The IO sink to the sender.
+    /// * `stream` - The IO stream from the sender.
+    /// * `count` - The number of correlated messages to obliviously receive.
+    async fn receive_random_correlated_packed<
+        Si: IoSink<Self::Msg> + Send + Unpin,
+        St: IoStream<Self::Msg> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        count: usize,
+    ) -> Result<(Vec<u8>, Vec<U>), OTError>
+    where
+        T: Into<bool>,
+    {
+        let (choices, msgs) = self.receive_random_correlated(sink, stream, count).await?;
+
+        Ok((
+            Vec::<u8>::from_lsb0_iter(choices.into_iter().map(Into::into)),
+            msgs,
+        ))
+    }
 }
 
 /// An oblivious transfer sender that is committed to its messages and can reveal them
@@ -446,6 +529,34 @@ pub trait RandomCOTReceiverShared<T, U> {
         id: &str,
         count: usize,
     ) -> Result<(Vec<T>, Vec<U>), OTError>;
+
+    /// Obliviously receives correlated messages with random choices, returning the
+    /// choices packed into bytes.
+    ///
+    /// Returns a tuple of the choices, packed in LSB0 bit order, and the messages, respectively.
+    ///
+    /// The default implementation packs the choices after receiving them unpacked, so it does
+    /// not reduce the peak memory usage.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The unique identifier for this transfer.
+    /// * `count` - The number of correlated messages to obliviously receive.
+    async fn receive_random_correlated_packed(
+        &self,
+        id: &str,
+        count: usize,
+    ) -> Result<(Vec<u8>, Vec<U>), OTError>
+    where
+        T: Into<bool>,
+    {
+        let (choices, msgs) = self.receive_random_correlated(id, count).await?;
+
+        Ok((
+            Vec::<u8>::from_lsb0_iter(choices.into_iter().map(Into::into)),
+            msgs,
+        ))
+    }
 }
 
 /// An oblivious transfer sender that is committed to its messages and can reveal them
@@ -473,3 +584,87 @@ pub trait VerifiableOTReceiverShared<T, U, V>: OTReceiverShared<T, U> {
     /// * `msgs` - The purported messages sent by the sender.
     async fn verify(&self, id: &str, msgs: &[V]) -> Result<(), OTError>;
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::VecDeque,
+        io::ErrorKind,
+        pin::Pin,
+        task::{ready, Context, Poll},
+    };
+
+    use futures::Sink;
+
+    use super::*;
+
+    /// A sink which buffers messages, and only delivers them to the inner sink when flushed.
+    ///
+    /// This is used to test that protocols flush their sinks before awaiting a response.
+    pub(crate) struct FlushOnlySink<Si, T> {
+        inner: Si,
+        buffer: VecDeque<T>,
+    }
+
+    impl<Si, T> FlushOnlySink<Si, T> {
+        pub(crate) fn new(inner: Si) -> Self {
+            Self {
+                inner,
+                buffer: VecDeque::new(),
+            }
+        }
+    }
+
+    impl<Si, T> Sink<T> for FlushOnlySink<Si, T>
+    where
+        Si: Sink<T> + Unpin,
+        T: Unpin,
+    {
+        type Error = Si::Error;
+
+        fn poll_ready(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+            self.get_mut().buffer.push_back(item);
+            Ok(())
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            let this = self.get_mut();
+
+            while !this.buffer.is_empty() {
+                ready!(Pin::new(&mut this.inner).poll_ready(cx))?;
+                let item = this.buffer.pop_front().expect("buffer is not empty");
+                Pin::new(&mut this.inner).start_send(item)?;
+            }
+
+            Pin::new(&mut this.inner).poll_flush(cx)
+        }
+
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            ready!(self.as_mut().poll_flush(cx))?;
+            Pin::new(&mut self.get_mut().inner).poll_close(cx)
+        }
+    }
+
+    #[test]
+    fn test_ot_error_into_io_error() {
+        let err: OTError = kos::SenderError::Other("error".to_string()).into();
+        let msg = err.to_string();
+        let err: std::io::Error = err.into();
+
+        assert_eq!(err.kind(), ErrorKind::Other);
+        assert_eq!(err.to_string(), msg);
+
+        let err: OTError = std::io::Error::new(ErrorKind::UnexpectedEof, "eof").into();
+        let err: std::io::Error = err.into();
+
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(err.to_string(), "eof");
+    }
+}
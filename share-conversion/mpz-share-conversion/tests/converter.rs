@@ -2,7 +2,10 @@ use std::marker::PhantomData;
 
 use rstest::*;
 
-use mpz_ot::ideal::{ideal_ot_shared_pair, IdealSharedOTReceiver, IdealSharedOTSender};
+use mpz_core::Block;
+use mpz_ot::ideal::{
+    ideal_ot_shared_pair, IdealSharedOTReceiver, IdealSharedOTSender, RecordingIdealOT,
+};
 use mpz_share_conversion::{
     AdditiveToMultiplicative, ConverterReceiver, ConverterSender, Field, Gf2_128,
     MultiplicativeToAdditive, OTReceiveElement, OTSendElement, ReceiverConfig, SenderConfig,
@@ -10,7 +13,7 @@ use mpz_share_conversion::{
 };
 use utils_aio::duplex::MemoryDuplex;
 
-use rand::SeedableRng;
+use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha12Rng;
 
 #[rstest]
@@ -65,3 +68,66 @@ where
 
     tokio::try_join!(sender.reveal(), receiver.verify()).unwrap();
 }
+
+#[tokio::test]
+async fn test_converter_recorded_transfers() {
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+
+    let ot = RecordingIdealOT::<Block>::new();
+    let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+    let mut sender = ConverterSender::<Gf2_128, _>::new(
+        SenderConfig::builder().id("test").build().unwrap(),
+        ot.clone(),
+        Box::new(sender_channel),
+    );
+
+    let mut receiver = ConverterReceiver::<Gf2_128, _>::new(
+        ReceiverConfig::builder().id("test").build().unwrap(),
+        ot.clone(),
+        Box::new(receiver_channel),
+    );
+
+    let sender_handle = sender.handle().unwrap();
+    let receiver_handle = receiver.handle().unwrap();
+
+    let a: Vec<Gf2_128> = (0..4).map(|_| rng.gen()).collect();
+    let b: Vec<Gf2_128> = (0..4).map(|_| rng.gen()).collect();
+
+    let (x, y) = tokio::join!(
+        async { sender_handle.to_additive(a.clone()).await.unwrap() },
+        async { receiver_handle.to_additive(b.clone()).await.unwrap() }
+    );
+
+    for (((a, b), x), y) in a.iter().zip(&b).zip(&x).zip(&y) {
+        assert_eq!(*a * *b, *x + *y);
+    }
+
+    let (x, y) = tokio::join!(
+        async { sender_handle.to_multiplicative(a.clone()).await.unwrap() },
+        async { receiver_handle.to_multiplicative(b.clone()).await.unwrap() }
+    );
+
+    for (((a, b), x), y) in a.iter().zip(&b).zip(&x).zip(&y) {
+        assert_eq!(*a + *b, *x * *y);
+    }
+
+    let transfers = ot.transfers();
+    assert_eq!(transfers.len(), 2);
+
+    for (i, transfer) in transfers.iter().enumerate() {
+        assert_eq!(transfer.id, format!("test/{i}"));
+        assert_eq!(transfer.choices.len(), 4 * Gf2_128::BIT_SIZE as usize);
+        assert_eq!(transfer.sent.len(), transfer.choices.len());
+        assert_eq!(transfer.received.len(), transfer.choices.len());
+
+        for ((msgs, choice), received) in transfer
+            .sent
+            .iter()
+            .zip(&transfer.choices)
+            .zip(&transfer.received)
+        {
+            assert_eq!(msgs[*choice as usize], *received);
+        }
+    }
+}